@@ -7,6 +7,7 @@ Runs twice daily via cron job.
 
 import os
 import sys
+import hashlib
 import logging
 import shutil
 import threading
@@ -22,6 +23,8 @@ from concurrent.futures import ThreadPoolExecutor, as_completed
 CONFIG_FILE = "azure_config.json"
 LOG_DIR = "logs"
 DOWNLOAD_DIR = "downloads"
+# Size and SHA-256 of every downloaded file, read by the backend's /api/verify
+HASH_MANIFEST = "dictionary_hashes.json"
 
 # Resource optimization constants
 CHUNK_SIZE = 8 * 1024 * 1024  # 8MB chunks for streaming downloads
@@ -116,11 +119,32 @@ def clear_download_directory(download_dir, logger):
         logger.error(f"Error clearing download directory: {e}")
         raise
 
+# Serializes manifest updates from concurrent downloads
+manifest_lock = threading.Lock()
+
+def record_download_hash(download_dir, blob_name, size, sha256):
+    """Record a finished download's size and hash in the manifest next to the downloads"""
+    manifest_path = download_dir / HASH_MANIFEST
+    with manifest_lock:
+        try:
+            with open(manifest_path, 'r') as f:
+                manifest = json.load(f)
+        except (FileNotFoundError, json.JSONDecodeError):
+            manifest = {}
+        manifest[blob_name] = {"size": size, "sha256": sha256}
+        # Replace the manifest atomically so a reader never sees it half written
+        temp_path = manifest_path.with_suffix('.tmp')
+        with open(temp_path, 'w') as f:
+            json.dump(manifest, f, indent=2)
+        os.replace(temp_path, manifest_path)
+
 def download_blob_with_progress(blob_client, local_path, blob_size, logger):
-    """Download blob with progress reporting and memory efficiency"""
+    """Download blob with progress reporting and memory efficiency.
+    Returns the SHA-256 of the downloaded bytes, or None if the download failed."""
     try:
         downloaded_bytes = 0
         last_progress_report = 0
+        hasher = hashlib.sha256()
         
         with open(local_path, 'wb') as download_file:
             # Stream download in chunks to avoid loading entire file into memory
@@ -128,6 +152,7 @@ def download_blob_with_progress(blob_client, local_path, blob_size, logger):
             
             for chunk in stream.chunks():
                 download_file.write(chunk)
+                hasher.update(chunk)
                 downloaded_bytes += len(chunk)
                 
                 # Report progress every PROGRESS_REPORT_INTERVAL MB
@@ -136,14 +161,17 @@ def download_blob_with_progress(blob_client, local_path, blob_size, logger):
                     logger.info(f"Downloaded {downloaded_bytes / (1024*1024):.1f}MB of {blob_size / (1024*1024):.1f}MB ({progress_pct:.1f}%)")
                     last_progress_report = downloaded_bytes
         
-        return True
+        if downloaded_bytes != blob_size:
+            raise IOError(f"received {downloaded_bytes} of {blob_size} bytes")
+        
+        return hasher.hexdigest()
         
     except Exception as e:
         logger.error(f"Error during streaming download: {e}")
         # Clean up partial file
         if local_path.exists():
             local_path.unlink()
-        return False
+        return None
 
 def download_single_blob(blob_service_client, container_name, blob, config, logger):
     """Download a single blob with resource management"""
@@ -176,7 +204,9 @@ def download_single_blob(blob_service_client, container_name, blob, config, logg
         )
         
         # Use streaming download for memory efficiency
-        if download_blob_with_progress(blob_client, local_path, blob.size, logger):
+        sha256 = download_blob_with_progress(blob_client, local_path, blob.size, logger)
+        if sha256 is not None:
+            record_download_hash(download_dir, blob_name, blob.size, sha256)
             logger.info(f"Successfully downloaded: {blob_name}")
             return 'downloaded'
         else:
@@ -1,10 +1,41 @@
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+
+use crate::services::{JobStore, ResultCache};
+
+// Default cap on the total number of arguments a single decode may read
+const DEFAULT_MAX_TOTAL_ARGS: usize = 50_000_000;
+
+// Default number of decode results kept for repeated identical requests
+const DEFAULT_DECODE_CACHE_SIZE: usize = 16;
+
+// Default number of finished decodes kept for session archive downloads
+const DEFAULT_DECODE_JOB_HISTORY: usize = 8;
+
+// Default time the dictionary refresh script may run before it is killed
+const DEFAULT_REFRESH_TIMEOUT_SECS: u64 = 10 * 60;
+
+// Default number of bytes of the refresh script's error output kept for the response
+const DEFAULT_MAX_SCRIPT_OUTPUT: usize = 64 * 1024;
 
 #[derive(Clone)]
 pub struct Config {
     pub downloads_path: String,
     pub temp_dir: String,
     pub bind_address: String,
+    pub max_total_args: usize,
+    // Bounds how many decodes run at once on the blocking thread pool
+    pub decode_slots: Arc<Semaphore>,
+    // Recent decode results keyed by input hash, version and level
+    pub result_cache: Arc<ResultCache>,
+    // Recent decode results addressable by job id
+    pub decode_jobs: Arc<JobStore>,
+    // CSS color per log level name, sent to the frontend to theme severities
+    pub level_colors: BTreeMap<String, String>,
+    // How long the dictionary refresh script may run before it is killed
+    pub refresh_timeout: Duration,
+    // Most bytes of the refresh script's error output kept for the response
+    pub max_script_output: usize,
 }
 
 impl Config {
@@ -16,6 +47,42 @@ impl Config {
                 .unwrap_or_else(|_| "/tmp".to_string()),
             bind_address: std::env::var("BIND_ADDRESS")
                 .unwrap_or_else(|_| "127.0.0.1:3000".to_string()),
+            max_total_args: std::env::var("MAX_TOTAL_ARGS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_MAX_TOTAL_ARGS),
+            decode_slots: Arc::new(Semaphore::new(
+                std::env::var("DECODE_THREADS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&threads| threads > 0)
+                    .unwrap_or_else(default_decode_threads),
+            )),
+            result_cache: Arc::new(ResultCache::new(
+                std::env::var("DECODE_CACHE_SIZE")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(DEFAULT_DECODE_CACHE_SIZE),
+            )),
+            decode_jobs: Arc::new(JobStore::new(
+                std::env::var("DECODE_JOB_HISTORY")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(DEFAULT_DECODE_JOB_HISTORY),
+            )),
+            level_colors: std::env::var("LEVEL_COLORS")
+                .map(|value| parse_level_colors(&value))
+                .unwrap_or_default(),
+            refresh_timeout: Duration::from_secs(
+                std::env::var("REFRESH_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(DEFAULT_REFRESH_TIMEOUT_SECS),
+            ),
+            max_script_output: std::env::var("MAX_SCRIPT_OUTPUT")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_MAX_SCRIPT_OUTPUT),
         }
     }
 
@@ -28,18 +95,16 @@ impl Config {
     }
 }
 
-pub fn cleanup_temp_files(temp_dir: &PathBuf) -> Result<(), std::io::Error> {
-    if let Ok(entries) = fs::read_dir(temp_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.ends_with(".log") || name.ends_with(".bin") {
-                        let _ = fs::remove_file(&path);
-                    }
-                }
-            }
-        }
-    }
-    Ok(())
+// Parse `Error=#dc3545,Warning=#fd7e14` into a level name to color map, skipping malformed pairs
+fn parse_level_colors(value: &str) -> BTreeMap<String, String> {
+    value.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(level, color)| (level.trim().to_string(), color.trim().to_string()))
+        .filter(|(level, color)| !level.is_empty() && !color.is_empty())
+        .collect()
+}
+
+// One decode per available core by default
+fn default_decode_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get())
 }
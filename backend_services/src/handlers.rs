@@ -1,15 +1,21 @@
 use axum::{
-    extract::{Multipart, Query, State},
+    body::Body,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Multipart, Path, Query, State,
+    },
     http::{Response, StatusCode, header},
-    response::Json,
+    response::{IntoResponse, Json},
 };
-use std::{sync::Arc, process::Command};
-use tokio::task;
+use std::{io::{BufWriter, Write}, sync::Arc};
+use syslog_decoder::{session::LogSession, validate_dictionary_bytes, FormatOptions, ParserConfig, SyslogParser};
+use tokio::{sync::mpsc, task};
 
 use crate::{
     config::Config,
-    services::{get_available_decoders, FileProcessor, ServiceError},
-    types::DecoderQuery,
+    parser::session_parser::session_page_fields,
+    services::{get_available_decoders, run_downloader_script, verify_decoders, write_session_archive, ChunkWriter, FileProcessor, ServiceError, UploadCleanup, MAX_UPLOAD_SIZE, STREAM_MIN_UPLOAD_SIZE},
+    types::{DecoderQuery, PreflightQuery, SessionPageQuery, StreamQuery},
 };
 
 pub async fn get_versions(State(config): State<Arc<Config>>) -> Result<Json<Vec<String>>, StatusCode> {
@@ -19,26 +25,72 @@ pub async fn get_versions(State(config): State<Arc<Config>>) -> Result<Json<Vec<
     }
 }
 
+/// Report downloaded dictionaries that look truncated or corrupt
+pub async fn verify_dictionaries(State(config): State<Arc<Config>>) -> Result<Json<serde_json::Value>, StatusCode> {
+    match task::spawn_blocking(move || verify_decoders(&config)).await {
+        Ok(Ok(checks)) => {
+            let inconsistent = checks.iter().filter(|check| !check.problems.is_empty()).count();
+            Ok(Json(serde_json::json!({
+                "status": if inconsistent == 0 { "success" } else { "error" },
+                "inconsistent": inconsistent,
+                "dictionaries": checks,
+            })))
+        }
+        Ok(Err(_)) | Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
 pub async fn decode_file(
     State(config): State<Arc<Config>>,
     Query(query): Query<DecoderQuery>,
     multipart: Multipart,
-) -> Result<Response<String>, StatusCode> {
+) -> Result<Response<Body>, StatusCode> {
     let file_processor = FileProcessor::new((*config).clone());
+
+    if query.page == Some(0) || query.page_size == Some(0) {
+        return Ok(create_error_response(
+            StatusCode::BAD_REQUEST,
+            "page and page_size must be at least 1",
+        ).map(Body::from));
+    }
     
     // Process file upload
-    let uploaded_files = match file_processor.process_upload(multipart).await {
-        Ok(files) => files,
-        Err(ServiceError::InvalidInput(msg)) => {
-            return Ok(create_error_response(StatusCode::BAD_REQUEST, &msg));
-        }
-        Err(_) => {
-            return Ok(create_error_response(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to process upload",
-            ));
-        }
+    let mut uploads = match file_processor.process_upload(multipart).await {
+        Ok(uploads) => uploads,
+        Err(e) => return Ok(upload_error_response(e).map(Body::from)),
     };
+    let upload_cleanup = UploadCleanup::new(&uploads);
+    if uploads.len() > 1 {
+        return Ok(create_error_response(
+            StatusCode::BAD_REQUEST,
+            "Several files were uploaded; use /api/decode/batch to decode them together",
+        ).map(Body::from));
+    }
+    let uploaded_files = uploads.remove(0);
+
+    if query.use_custom_decoder && uploaded_files.custom_decoder_file.is_none() {
+        return Ok(create_error_response(
+            StatusCode::BAD_REQUEST,
+            "Custom decoder enabled but no decoder file was uploaded",
+        ).map(Body::from));
+    }
+
+    // Large captures are sent session by session instead of after the whole decode,
+    // unless only one page of sessions was asked for
+    let upload_size = std::fs::metadata(&uploaded_files.binary_file).map(|metadata| metadata.len()).unwrap_or(0);
+    if query.stream && query.requested_page().is_none() && upload_size >= STREAM_MIN_UPLOAD_SIZE {
+        return match file_processor.stream_decoder(
+            &uploaded_files.binary_file,
+            &query.version,
+            &query.log_level,
+            query.include_summary,
+            uploaded_files.custom_decoder_file.as_ref(),
+            upload_cleanup,
+        ).await {
+            Ok(receiver) => Ok(ndjson_response(receiver)),
+            Err(e) => Ok(decode_error_response(e).map(Body::from)),
+        };
+    }
 
     // Run decoder
     match file_processor.run_decoder(
@@ -46,64 +98,408 @@ pub async fn decode_file(
         &query.version, 
         &query.log_level, 
         query.include_log_level,
+        query.include_summary,
         uploaded_files.custom_decoder_file.as_ref()
     ).await {
-        Ok(result) => Ok(Response::builder()
-            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
-            .body(result)
-            .unwrap()),
-        Err(ServiceError::NotFound(msg)) => {
-            Ok(create_error_response(StatusCode::NOT_FOUND, &msg))
+        Ok(output) => {
+            // Later exports such as the session archive refer to this decode by job id
+            let job_id = config.decode_jobs.insert(output.sessions_json.clone());
+            let page_fields = match query.requested_page() {
+                Some((page, page_size)) => match session_page_fields(&output.sessions_json, page, page_size) {
+                    Ok(fields) => Some(fields),
+                    Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+                },
+                None => None,
+            };
+            let body = if query.include_warnings || query.include_level_colors || output.summary.is_some() || page_fields.is_some() {
+                let mut fields = page_fields
+                    .unwrap_or_else(|| vec![format!("\"sessions\":{}", output.sessions_json)]);
+                if query.include_warnings {
+                    let warnings_json = serde_json::to_string(&output.warnings).unwrap_or_else(|_| "[]".to_string());
+                    fields.push(format!("\"warnings\":{}", warnings_json));
+                }
+                if let Some(summary) = &output.summary {
+                    fields.push(format!("\"summary\":{}", serde_json::Value::from(summary.as_str())));
+                }
+                if query.include_level_colors {
+                    let colors_json = serde_json::to_string(&config.level_colors).unwrap_or_else(|_| "{}".to_string());
+                    fields.push(format!("\"level_colors\":{}", colors_json));
+                }
+                format!("{{{}}}", fields.join(","))
+            } else {
+                output.sessions_json
+            };
+            Ok(Response::builder()
+                .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+                .header("X-Decode-Job-Id", job_id)
+                .body(Body::from(body))
+                .unwrap())
         }
-        Err(ServiceError::InvalidInput(msg)) => {
-            Ok(create_error_response(StatusCode::BAD_REQUEST, &msg))
+        Err(e) => Ok(decode_error_response(e).map(Body::from)),
+    }
+}
+
+/// Check the start of an uploaded capture against the chosen dictionary before a full decode:
+/// `{"records": N, "matched_offsets": M, "match_rate": 0.98, "sample_lines": [...]}`.
+///
+/// A low match rate means the capture was most likely written by another firmware version.
+pub async fn preflight_capture(
+    State(config): State<Arc<Config>>,
+    Query(query): Query<PreflightQuery>,
+    multipart: Multipart,
+) -> Result<Response<String>, StatusCode> {
+    let file_processor = FileProcessor::new((*config).clone());
+
+    let mut uploads = match file_processor.process_upload(multipart).await {
+        Ok(uploads) => uploads,
+        Err(e) => return Ok(upload_error_response(e)),
+    };
+    let _upload_cleanup = UploadCleanup::new(&uploads);
+    if uploads.len() > 1 {
+        return Ok(create_error_response(StatusCode::BAD_REQUEST, "Upload a single capture to check"));
+    }
+    let uploaded_files = uploads.remove(0);
+    if query.use_custom_decoder && uploaded_files.custom_decoder_file.is_none() {
+        return Ok(create_error_response(
+            StatusCode::BAD_REQUEST,
+            "Custom decoder enabled but no decoder file was uploaded",
+        ));
+    }
+
+    match file_processor.run_preflight(
+        &uploaded_files.binary_file,
+        &query.version,
+        uploaded_files.custom_decoder_file.as_ref(),
+        query.records,
+    ).await {
+        Ok(report) => {
+            let result_json = serde_json::json!({
+                "records": report.records,
+                "matched_offsets": report.matched_offsets,
+                "match_rate": report.match_rate(),
+                "sample_lines": report.sample_lines,
+            });
+            Ok(Response::builder()
+                .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+                .body(result_json.to_string())
+                .unwrap())
+        }
+        Err(e) => Ok(decode_error_response(e)),
+    }
+}
+
+fn decode_error_response(error: ServiceError) -> Response<String> {
+    match error {
+        ServiceError::NotFound(msg) => create_error_response(StatusCode::NOT_FOUND, &msg),
+        ServiceError::InvalidInput(msg) => create_error_response(StatusCode::BAD_REQUEST, &msg),
+        ServiceError::PayloadTooLarge(msg) => create_error_response(StatusCode::PAYLOAD_TOO_LARGE, &msg),
+        ServiceError::Internal(msg) => {
+            println!("Decode failed: {}", msg);
+            create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
         }
-        Err(_) => Ok(create_error_response(
+        _ => create_error_response(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal server error",
-        )),
+        ),
     }
 }
 
-pub async fn refresh_azure_files(State(_config): State<Arc<Config>>) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Run the Azure blob downloader script in the background with virtual environment activated
-    // Note: Not using --clear-existing to avoid directory locking issues
-    let result = task::spawn_blocking(move || {
-        let output = Command::new("bash")
-            .arg("-c")
-            .arg("cd /app && source venv_azure/bin/activate && python3 azure_blob_downloader.py")
-            .output();
-        
-        match output {
-            Ok(output) => {
-                if output.status.success() {
-                    // Return a clean success message instead of verbose logs
-                    Ok("Files refreshed successfully".to_string())
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    Err(format!("Azure files refresh failed: {}", stderr))
+/// Decode several captures in one request: `{"<filename>": {"status": "ok", "sessions": [...],
+/// "warnings": [...], "summary": "..." or null, "job_id": "..."}, "<filename>": {"status": "error", "message": "..."}}`.
+///
+/// A capture that fails to decode gets an error entry and the others are still decoded.
+pub async fn decode_batch(
+    State(config): State<Arc<Config>>,
+    Query(query): Query<DecoderQuery>,
+    multipart: Multipart,
+) -> Result<Response<String>, StatusCode> {
+    let file_processor = FileProcessor::new((*config).clone());
+
+    let uploads = match file_processor.process_upload(multipart).await {
+        Ok(uploads) => uploads,
+        Err(e) => return Ok(upload_error_response(e)),
+    };
+    let _upload_cleanup = UploadCleanup::new(&uploads);
+
+    if query.use_custom_decoder && uploads[0].custom_decoder_file.is_none() {
+        return Ok(create_error_response(
+            StatusCode::BAD_REQUEST,
+            "Custom decoder enabled but no decoder file was uploaded",
+        ));
+    }
+    // Results are keyed by filename, so two captures with one name would hide each other
+    for (index, upload) in uploads.iter().enumerate() {
+        if uploads[..index].iter().any(|other| other.original_name == upload.original_name) {
+            return Ok(create_error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Duplicate file name in batch: {}", upload.original_name),
+            ));
+        }
+    }
+
+    let results = file_processor.run_batch_decoder(&uploads, &query.version, &query.log_level, query.include_summary).await;
+    let entries: Vec<String> = results.into_iter()
+        .map(|(filename, result)| {
+            let entry = match result {
+                Ok(output) => {
+                    let job_id = config.decode_jobs.insert(output.sessions_json.clone());
+                    let warnings_json = serde_json::to_string(&output.warnings).unwrap_or_else(|_| "[]".to_string());
+                    format!("{{\"status\":\"ok\",\"sessions\":{},\"warnings\":{},\"summary\":{},\"job_id\":{}}}",
+                            output.sessions_json, warnings_json, serde_json::Value::from(output.summary), serde_json::Value::from(job_id))
+                }
+                Err(e) => {
+                    let message = match e {
+                        ServiceError::NotFound(msg) | ServiceError::InvalidInput(msg) | ServiceError::PayloadTooLarge(msg) => msg,
+                        ServiceError::IoError(_) | ServiceError::Internal(_) => "Internal server error".to_string(),
+                    };
+                    serde_json::json!({"status": "error", "message": message}).to_string()
                 }
+            };
+            format!("{}:{}", serde_json::Value::from(filename), entry)
+        })
+        .collect();
+
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(format!("{{{}}}", entries.join(",")))
+        .unwrap())
+}
+
+/// Error response for an upload that could not be stored
+fn upload_error_response(error: ServiceError) -> Response<String> {
+    match error {
+        ServiceError::InvalidInput(msg) => create_error_response(StatusCode::BAD_REQUEST, &msg),
+        ServiceError::PayloadTooLarge(msg) => create_error_response(StatusCode::PAYLOAD_TOO_LARGE, &msg),
+        ServiceError::IoError(e) => {
+            println!("Failed to store uploaded file: {}", e);
+            create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to process upload")
+        }
+        _ => create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to process upload"),
+    }
+}
+
+/// Send the lines of a streamed decode as they are produced
+fn ndjson_response(receiver: mpsc::Receiver<Result<String, std::io::Error>>) -> Response<Body> {
+    let lines = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|line| (line, receiver))
+    });
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/x-ndjson; charset=utf-8")
+        .body(Body::from_stream(lines))
+        .unwrap()
+}
+
+/// One page of a finished decode's sessions, wrapped like a paged `/api/decode` response
+pub async fn get_session_page(
+    State(config): State<Arc<Config>>,
+    Path(job_id): Path<String>,
+    Query(query): Query<SessionPageQuery>,
+) -> Response<String> {
+    if query.page == 0 || query.page_size == 0 {
+        return create_error_response(StatusCode::BAD_REQUEST, "page and page_size must be at least 1");
+    }
+    let Some(sessions_json) = config.decode_jobs.get(&job_id) else {
+        return create_error_response(StatusCode::NOT_FOUND, &format!("Decode job not found: {}", job_id));
+    };
+    match session_page_fields(&sessions_json, query.page, query.page_size) {
+        Ok(fields) => Response::builder()
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(format!("{{{}}}", fields.join(",")))
+            .unwrap(),
+        Err(_) => create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read decoded sessions"),
+    }
+}
+
+/// Stream the sessions of an earlier decode as a zip with one `.log` file per session
+pub async fn download_session_archive(
+    State(config): State<Arc<Config>>,
+    Path(job_id): Path<String>,
+) -> impl IntoResponse {
+    let Some(sessions_json) = config.decode_jobs.get(&job_id) else {
+        return create_error_response(StatusCode::NOT_FOUND, &format!("Decode job not found: {}", job_id)).into_response();
+    };
+    let sessions: Vec<LogSession> = match serde_json::from_str(&sessions_json) {
+        Ok(sessions) => sessions,
+        Err(_) => return create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read decoded sessions").into_response(),
+    };
+
+    // The zip is written on the blocking pool and sent out chunk by chunk as it is produced
+    let (sender, receiver) = mpsc::channel(16);
+    task::spawn_blocking(move || {
+        let writer = BufWriter::with_capacity(64 * 1024, ChunkWriter::new(sender.clone()));
+        let result = write_session_archive(&sessions, writer)
+            .map_err(std::io::Error::other)
+            .and_then(|mut writer| writer.flush());
+        if let Err(e) = result {
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+    let chunks = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"sessions_{}.zip\"", job_id))
+        .body(Body::from_stream(chunks))
+        .unwrap()
+        .into_response()
+}
+
+/// Check an uploaded dictionary for malformed lines and argument count mismatches
+/// so a broken custom decoder is reported before any binary is decoded
+pub async fn validate_dictionary(multipart: Multipart) -> Result<Response<String>, StatusCode> {
+    validate_dictionary_with_limit(multipart, MAX_UPLOAD_SIZE).await
+}
+
+/// `validate_dictionary` with the largest accepted dictionary size in bytes
+async fn validate_dictionary_with_limit(mut multipart: Multipart, max_upload_size: usize) -> Result<Response<String>, StatusCode> {
+    let mut dictionary = None;
+    loop {
+        match multipart.next_field().await {
+            Ok(Some(mut field)) if field.file_name().is_some() => {
+                let mut bytes = Vec::new();
+                loop {
+                    match field.chunk().await {
+                        Ok(Some(chunk)) => {
+                            // Stop reading as soon as the limit is passed, like decode uploads
+                            if bytes.len() + chunk.len() > max_upload_size {
+                                return Ok(create_error_response(StatusCode::PAYLOAD_TOO_LARGE,
+                                    &format!("Dictionary too large (max: {} bytes)", max_upload_size)));
+                            }
+                            bytes.extend_from_slice(&chunk);
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            return Ok(create_error_response(StatusCode::BAD_REQUEST, &format!("Failed to read dictionary: {}", e)));
+                        }
+                    }
+                }
+                dictionary = Some(bytes);
+                break;
+            }
+            Ok(Some(_)) => continue,
+            Ok(None) => break,
+            Err(e) => {
+                return Ok(create_error_response(StatusCode::BAD_REQUEST, &format!("Invalid multipart data: {}", e)));
             }
-            Err(e) => Err(format!("Failed to execute Azure downloader script: {}", e))
         }
-    }).await;
-    
+    }
+
+    let Some(dictionary) = dictionary else {
+        return Ok(create_error_response(StatusCode::BAD_REQUEST, "No dictionary file was uploaded"));
+    };
+
+    let validation = match task::spawn_blocking(move || validate_dictionary_bytes(&dictionary)).await {
+        Ok(validation) => validation,
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let result_json = serde_json::json!({
+        "status": if validation.issues.is_empty() { "success" } else { "error" },
+        "entry_count": validation.entry_count,
+        "modules": validation.modules,
+        "issues": validation.issues,
+    });
+
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(result_json.to_string())
+        .unwrap())
+}
+
+/// Live console: the client sends the dictionary version as its first text message,
+/// then raw binary capture frames, and receives each decoded line as a text message
+pub async fn stream_logs(
+    State(config): State<Arc<Config>>,
+    Query(query): Query<StreamQuery>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_stream(socket, config, query))
+}
+
+async fn handle_stream(mut socket: WebSocket, config: Arc<Config>, query: StreamQuery) {
+    let version = match socket.recv().await {
+        Some(Ok(Message::Text(version))) => version,
+        _ => return send_stream_error(&mut socket, "Expected the dictionary version as the first message").await,
+    };
+
+    let file_processor = FileProcessor::new((*config).clone());
+    let dict_path = match file_processor.dictionary_path(version.trim()) {
+        Ok(dict_path) => dict_path,
+        Err(ServiceError::NotFound(msg) | ServiceError::InvalidInput(msg)) => return send_stream_error(&mut socket, &msg).await,
+        Err(_) => return send_stream_error(&mut socket, "Failed to find dictionary").await,
+    };
+
+    // A long-lived stream would otherwise print the parser's banner and warnings per chunk
+    let parser_config = ParserConfig {
+        max_total_args: Some(config.max_total_args),
+        quiet: true,
+        ..Default::default()
+    };
+    // Loading reads and parses the whole dictionary, so keep it off the async executor
+    let parser = match task::spawn_blocking(move || SyslogParser::with_config(&dict_path, parser_config)).await {
+        Ok(Ok(parser)) => parser,
+        Ok(Err(e)) => return send_stream_error(&mut socket, &format!("Failed to load dictionary: {}", e)).await,
+        Err(_) => return send_stream_error(&mut socket, "Failed to load dictionary").await,
+    };
+
+    let format_options = FormatOptions {
+        include_log_level: true,
+        ..Default::default()
+    };
+    let mut decoder = parser.stream_decoder(query.log_level);
+
+    while let Some(Ok(message)) = socket.recv().await {
+        match message {
+            Message::Binary(data) => {
+                let parsed_logs = match decoder.push(&data) {
+                    Ok(parsed_logs) => parsed_logs,
+                    Err(e) => return send_stream_error(&mut socket, &format!("Failed to decode stream: {}", e)).await,
+                };
+                for log in &parsed_logs {
+                    if socket.send(Message::Text(parser.format_log(log, &format_options))).await.is_err() {
+                        return; // Client went away
+                    }
+                }
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+}
+
+async fn send_stream_error(socket: &mut WebSocket, message: &str) {
+    let error_json = serde_json::json!({
+        "status": "error",
+        "message": message
+    });
+    let _ = socket.send(Message::Text(error_json.to_string())).await;
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+pub async fn refresh_azure_files(State(config): State<Arc<Config>>) -> Result<Json<serde_json::Value>, StatusCode> {
+    // Run the Azure blob downloader script with virtual environment activated, bounded by
+    // the configured timeout and error output size
+    // Note: Not using --clear-existing to avoid directory locking issues
+    let result = run_downloader_script(
+        "cd /app && source venv_azure/bin/activate && exec python3 azure_blob_downloader.py",
+        config.refresh_timeout,
+        config.max_script_output,
+    ).await;
+
     match result {
-        Ok(Ok(message)) => {
+        Ok(()) => {
             Ok(Json(serde_json::json!({
                 "status": "success",
-                "message": message
+                "message": "Files refreshed successfully"
             })))
         }
-        Ok(Err(error)) => {
+        Err(error) => {
             Ok(Json(serde_json::json!({
                 "status": "error",
                 "message": error
             })))
         }
-        Err(_) => {
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
     }
 }
 
@@ -119,3 +515,444 @@ fn create_error_response(status: StatusCode, message: &str) -> Response<String>
         .body(error_json.to_string())
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use futures_util::{SinkExt, StreamExt};
+    use tempfile::TempDir;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{connect_async, tungstenite::Message as ClientMessage};
+    use crate::parser::session_parser::DEFAULT_SESSION_PAGE_SIZE;
+    use crate::test_support::{multipart_request, test_config};
+
+    async fn body_string(response: Response<Body>) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stream_logs_decodes_frames() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("TEST.log"),
+            "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00",
+        ).unwrap();
+        let config = Arc::new(test_config(temp_dir.path()));
+
+        let app = Router::new()
+            .route("/api/stream", get(stream_logs))
+            .with_state(config);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (mut ws, _) = connect_async(format!("ws://{}/api/stream?log_level=5", addr)).await.unwrap();
+        ws.send(ClientMessage::Text("TEST".to_string())).await.unwrap();
+
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes()); // 2 args, byte offset 0
+        binary_data.extend_from_slice(&42u32.to_le_bytes());
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes()); // 0 args, byte offset 47
+
+        // Split the first record across two frames
+        ws.send(ClientMessage::Binary(binary_data[..10].to_vec())).await.unwrap();
+        ws.send(ClientMessage::Binary(binary_data[10..].to_vec())).await.unwrap();
+
+        let mut lines = Vec::new();
+        while lines.len() < 2 {
+            match ws.next().await.unwrap().unwrap() {
+                ClientMessage::Text(line) => lines.push(line),
+                other => panic!("Unexpected message: {:?}", other),
+            }
+        }
+        assert!(lines[0].ends_with("[Info]\t[TEST_MODULE]\tTrigger no 42 at 100"));
+        assert!(lines[1].ends_with("[FatalError]\t[SYS_INIT]\tSystem started"));
+    }
+
+    #[test]
+    fn test_queries_default_to_library_log_level() {
+        let uri: axum::http::Uri = "/api/decode?version=9.17.3.0".parse().unwrap();
+        let Query(decode_query) = Query::<DecoderQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(decode_query.log_level, syslog_decoder::DEFAULT_LOG_LEVEL.to_string());
+
+        let uri: axum::http::Uri = "/api/stream?version=9.17.3.0".parse().unwrap();
+        let Query(stream_query) = Query::<StreamQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(stream_query.log_level, syslog_decoder::DEFAULT_LOG_LEVEL);
+    }
+
+    #[tokio::test]
+    async fn test_validate_malformed_dictionary() {
+        let multipart = multipart_request(vec![(
+            "dictionary",
+            "custom.log",
+            b"2;4;test.c:123;TEST_MODULE;Trigger no %d\x00garbage line\x000;1;init.c:45;SYS_INIT;System started\x00".to_vec(),
+        )]).await;
+
+        let response = validate_dictionary(multipart).await.unwrap();
+        let result: serde_json::Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(result["status"], "error");
+        assert_eq!(result["entry_count"], 2);
+        assert_eq!(result["modules"], serde_json::json!(["SYS_INIT", "TEST_MODULE"]));
+        let kinds: Vec<&str> = result["issues"].as_array().unwrap().iter()
+            .map(|issue| issue["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, vec!["arg_count_mismatch", "malformed_line"]);
+
+        let multipart = multipart_request(vec![("dictionary", "custom.log", vec![b'x'; 64])]).await;
+        let response = validate_dictionary_with_limit(multipart, 16).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_session_archive_contains_each_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(Config {
+            decode_jobs: Arc::new(crate::services::JobStore::new(2)),
+            ..test_config(temp_dir.path())
+        });
+
+        let sessions = syslog_decoder::session::parse_log_sessions("\
+0ms\t[SYS]\tSystem Reset Cause: POR
+100ms\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: 1756474625
+200ms\t[APP]\tFirst boot
+0ms\t[SYS]\tSystem Reset Cause: WDT
+300ms\t[APP]\tSecond boot");
+        let job_id = config.decode_jobs.insert(serde_json::to_string(&sessions).unwrap());
+        // Ids are 128 random bits, not a guessable sequence
+        assert!(job_id.len() == 32 && job_id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(config.decode_jobs.insert("[]".to_string()), job_id);
+
+        let response = download_session_archive(State(config.clone()), Path(job_id)).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/zip");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(names, vec!["session_000_Epoch_1756474625.log", "session_001.log"]);
+
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("session_001.log").unwrap(), &mut content).unwrap();
+        assert_eq!(content, "0ms\t[SYS]\tSystem Reset Cause: WDT\n300ms\t[APP]\tSecond boot\n");
+
+        let response = download_session_archive(State(config), Path("missing".to_string())).await.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_decode_returns_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(test_config(temp_dir.path()));
+
+        // One valid record, one whose offset is not in the dictionary
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0u32.to_le_bytes());
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0x400u32.to_le_bytes());
+
+        let multipart = multipart_request(vec![
+            ("file", "capture.bin", binary_data),
+            ("custom_decoder", "custom.log", b"0;1;init.c:45;SYS_INIT;System started\x00broken line\x00".to_vec()),
+        ]).await;
+
+        let query = DecoderQuery {
+            version: String::new(),
+            log_level: "5".to_string(),
+            include_log_level: true,
+            use_custom_decoder: true,
+            include_summary: false,
+            include_warnings: true,
+            include_level_colors: false,
+            stream: false,
+            page: None,
+            page_size: None,
+        };
+        let response = decode_file(State(config), Query(query), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let result: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+        assert!(result["sessions"].is_array());
+        let warnings: Vec<&str> = result["warnings"].as_array().unwrap().iter()
+            .map(|warning| warning.as_str().unwrap())
+            .collect();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("broken line"));
+        assert_eq!(warnings[1], "1 of 2 records reference offsets missing from the dictionary");
+    }
+
+    #[tokio::test]
+    async fn test_decode_returns_level_colors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(Config {
+            level_colors: [("FatalError".to_string(), "#800000".to_string())].into(),
+            ..test_config(temp_dir.path())
+        });
+
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0u32.to_le_bytes());
+
+        let multipart = multipart_request(vec![
+            ("file", "capture.bin", binary_data),
+            ("custom_decoder", "custom.log", b"0;1;init.c:45;SYS_INIT;System started\x00".to_vec()),
+        ]).await;
+
+        let query = DecoderQuery {
+            version: String::new(),
+            log_level: "5".to_string(),
+            include_log_level: true,
+            use_custom_decoder: true,
+            include_summary: false,
+            include_warnings: false,
+            include_level_colors: true,
+            // Small uploads get the single JSON document even when streaming is asked for
+            stream: true,
+            page: None,
+            page_size: None,
+        };
+        let response = decode_file(State(config), Query(query), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json; charset=utf-8");
+        let result: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+        assert_eq!(result["level_colors"], serde_json::json!({"FatalError": "#800000"}));
+        assert!(result["sessions"].is_array());
+        assert!(result.get("warnings").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_reports_match_rate() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(test_config(temp_dir.path()));
+
+        // One record resolves, the other points past the dictionary
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0u32.to_le_bytes());
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0x400u32.to_le_bytes());
+
+        let multipart = multipart_request(vec![
+            ("file", "capture.bin", binary_data),
+            ("custom_decoder", "custom.log", b"0;1;init.c:45;SYS_INIT;System started\x00".to_vec()),
+        ]).await;
+
+        let query = PreflightQuery { version: String::new(), use_custom_decoder: true, records: 1000 };
+        let response = preflight_capture(State(config), Query(query), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let result: serde_json::Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(result["records"], 2);
+        assert_eq!(result["matched_offsets"], 1);
+        assert_eq!(result["match_rate"], 0.5);
+        assert_eq!(result["sample_lines"], serde_json::json!(["1000ms      \t[FatalError]\t[SYS_INIT]\tSystem started"]));
+    }
+
+    #[tokio::test]
+    async fn test_decode_batch_reports_each_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(Config {
+            max_total_args: 1,
+            decode_jobs: Arc::new(crate::services::JobStore::new(4)),
+            ..test_config(temp_dir.path())
+        });
+
+        // The second capture has two arguments, over the argument budget
+        let mut good_capture = Vec::new();
+        for timestamp in [1000u32, 2000] {
+            good_capture.extend_from_slice(&timestamp.to_le_bytes());
+            good_capture.extend_from_slice(&0u32.to_le_bytes());
+        }
+        let mut bad_capture = Vec::new();
+        bad_capture.extend_from_slice(&1000u32.to_le_bytes());
+        bad_capture.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        bad_capture.extend_from_slice(&[0; 8]);
+
+        let query = || DecoderQuery {
+            version: String::new(),
+            log_level: "5".to_string(),
+            include_log_level: true,
+            use_custom_decoder: true,
+            include_summary: false,
+            include_warnings: false,
+            include_level_colors: false,
+            stream: false,
+            page: None,
+            page_size: None,
+        };
+        let dictionary = b"0;1;init.c:45;SYS_INIT;System started\x00".to_vec();
+
+        let multipart = multipart_request(vec![
+            ("file", "run1.bin", good_capture.clone()),
+            ("file", "run2.bin", bad_capture),
+            ("custom_decoder", "custom.log", dictionary.clone()),
+        ]).await;
+        let response = decode_batch(State(config.clone()), Query(query()), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let result: serde_json::Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(result["run1.bin"]["status"], "ok");
+        assert_eq!(result["run1.bin"]["sessions"].as_array().unwrap().len(), 1);
+        assert!(config.decode_jobs.get(result["run1.bin"]["job_id"].as_str().unwrap()).is_some());
+        assert_eq!(result["run2.bin"]["status"], "error");
+        assert!(result["run2.bin"]["message"].as_str().unwrap().contains("Argument budget exceeded"));
+
+        // The single-file endpoint points batches elsewhere
+        let multipart = multipart_request(vec![
+            ("file", "run1.bin", good_capture.clone()),
+            ("file", "run2.bin", good_capture.clone()),
+            ("custom_decoder", "custom.log", dictionary.clone()),
+        ]).await;
+        let response = decode_file(State(config.clone()), Query(query()), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let multipart = multipart_request(vec![
+            ("file", "run1.bin", good_capture.clone()),
+            ("file", "run1.bin", good_capture),
+            ("custom_decoder", "custom.log", dictionary),
+        ]).await;
+        let response = decode_batch(State(config), Query(query()), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response.body().contains("Duplicate file name in batch: run1.bin"));
+    }
+
+    #[tokio::test]
+    async fn test_decode_returns_requested_page() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(test_config(temp_dir.path()));
+
+        // Five boot cycles of two records each, every cycle restarting at 0ms
+        let mut binary_data = Vec::new();
+        for timestamp in [0u32, 100].repeat(5) {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&0u32.to_le_bytes());
+        }
+
+        let decode_page = |page: Option<usize>, page_size: Option<usize>| {
+            let config = config.clone();
+            let binary_data = binary_data.clone();
+            async move {
+                let multipart = multipart_request(vec![
+                    ("file", "capture.bin", binary_data),
+                    ("custom_decoder", "custom.log", b"0;1;init.c:45;SYS_INIT;System started\x00".to_vec()),
+                ]).await;
+
+                let query = DecoderQuery {
+                    version: String::new(),
+                    log_level: "5".to_string(),
+                    include_log_level: true,
+                    use_custom_decoder: true,
+                    include_summary: false,
+                    include_warnings: false,
+                    include_level_colors: false,
+                    stream: false,
+                    page,
+                    page_size,
+                };
+                decode_file(State(config), Query(query), multipart).await.unwrap()
+            }
+        };
+
+        let response = decode_page(Some(2), Some(2)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+        let ids: Vec<u64> = result["sessions"].as_array().unwrap().iter()
+            .map(|session| session["id"].as_u64().unwrap())
+            .collect();
+        let all_sessions: Vec<serde_json::Value> = serde_json::from_str(
+            &body_string(decode_page(None, None).await).await).unwrap();
+        assert_eq!(all_sessions.len(), 5);
+        assert_eq!(ids, [all_sessions[2]["id"].as_u64().unwrap(), all_sessions[3]["id"].as_u64().unwrap()]);
+        assert_eq!(result["total_sessions"], 5);
+        assert_eq!(result["has_more"], true);
+        assert_eq!(result["page"], 2);
+
+        // The last page is short, and pages past the end are empty
+        let result: serde_json::Value = serde_json::from_str(&body_string(decode_page(Some(3), Some(2)).await).await).unwrap();
+        assert_eq!(result["sessions"].as_array().unwrap().len(), 1);
+        assert_eq!(result["has_more"], false);
+        let result: serde_json::Value = serde_json::from_str(&body_string(decode_page(Some(9), Some(2)).await).await).unwrap();
+        assert_eq!(result["sessions"], serde_json::json!([]));
+
+        assert_eq!(decode_page(Some(0), Some(2)).await.status(), StatusCode::BAD_REQUEST);
+
+        // A page without a size uses the default size
+        let result: serde_json::Value = serde_json::from_str(&body_string(decode_page(Some(1), None).await).await).unwrap();
+        assert_eq!(result["sessions"].as_array().unwrap().len(), 5);
+        assert_eq!(result["page_size"], DEFAULT_SESSION_PAGE_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_stored_job_sessions_are_paged() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(Config {
+            decode_jobs: Arc::new(crate::services::JobStore::new(2)),
+            ..test_config(temp_dir.path())
+        });
+        let sessions = syslog_decoder::session::parse_log_sessions("\
+0ms\t[SYS]\tSystem Reset Cause: POR
+100ms\t[APP]\tFirst boot
+0ms\t[SYS]\tSystem Reset Cause: WDT
+100ms\t[APP]\tSecond boot
+0ms\t[SYS]\tSystem Reset Cause: BOR
+100ms\t[APP]\tThird boot");
+        assert_eq!(sessions.len(), 3);
+        let job_id = config.decode_jobs.insert(serde_json::to_string(&sessions).unwrap());
+        let get_page = |page: Option<usize>, page_size: Option<usize>| {
+            let uri: axum::http::Uri = match (page, page_size) {
+                (Some(page), Some(page_size)) => format!("/?page={}&page_size={}", page, page_size),
+                (Some(page), None) => format!("/?page={}", page),
+                _ => "/".to_string(),
+            }.parse().unwrap();
+            get_session_page(State(config.clone()), Path(job_id.clone()), Query::try_from_uri(&uri).unwrap())
+        };
+
+        let response = get_page(Some(2), Some(2)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: serde_json::Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(result["sessions"].as_array().unwrap().len(), 1);
+        assert_eq!(result["sessions"][0]["id"], sessions[2].id);
+        assert_eq!(result["total_sessions"], 3);
+        assert_eq!(result["has_more"], false);
+
+        let result: serde_json::Value = serde_json::from_str(get_page(None, None).await.body()).unwrap();
+        assert_eq!(result["sessions"].as_array().unwrap().len(), 3);
+        assert_eq!(result["page"], 1);
+        assert_eq!(result["page_size"], DEFAULT_SESSION_PAGE_SIZE);
+
+        assert_eq!(get_page(Some(0), None).await.status(), StatusCode::BAD_REQUEST);
+        let missing = get_session_page(State(config.clone()), Path("missing".to_string()), Query::try_from_uri(&"/".parse().unwrap()).unwrap());
+        assert_eq!(missing.await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_decode_rejects_empty_upload() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Arc::new(test_config(temp_dir.path()));
+
+        let multipart = multipart_request(vec![("file", "capture.bin", Vec::new())]).await;
+
+        let query = DecoderQuery {
+            version: "9.17.3.0".to_string(),
+            log_level: "5".to_string(),
+            include_log_level: true,
+            use_custom_decoder: false,
+            include_summary: false,
+            include_warnings: false,
+            include_level_colors: false,
+            stream: false,
+            page: None,
+            page_size: None,
+        };
+        let response = decode_file(State(config), Query(query), multipart).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(body_string(response).await.contains("Uploaded file capture.bin is empty"));
+    }
+}
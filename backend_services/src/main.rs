@@ -3,6 +3,8 @@ mod handlers;
 mod services;
 mod types;
 mod parser;
+#[cfg(test)]
+mod test_support;
 
 use axum::{
     extract::DefaultBodyLimit,
@@ -14,7 +16,10 @@ use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 
 use config::Config;
-use handlers::{decode_file, get_versions, refresh_azure_files};
+use handlers::{
+    decode_batch, decode_file, download_session_archive, get_session_page, get_versions, preflight_capture, refresh_azure_files, stream_logs,
+    validate_dictionary, verify_dictionaries,
+};
 
 #[tokio::main]
 async fn main() {
@@ -23,7 +28,14 @@ async fn main() {
     let app = Router::new()
         .route("/api/versions", get(get_versions))
         .route("/api/decode", post(decode_file))
+        .route("/api/decode/batch", post(decode_batch))
+        .route("/api/preflight", post(preflight_capture))
+        .route("/api/decode/:job_id/archive", get(download_session_archive))
+        .route("/api/decode/:job_id/sessions", get(get_session_page))
+        .route("/api/dictionary/validate", post(validate_dictionary))
         .route("/api/refresh", post(refresh_azure_files))
+        .route("/api/verify", get(verify_dictionaries))
+        .route("/api/stream", get(stream_logs))
         .layer(DefaultBodyLimit::max(500 * 1024 * 1024)) // 500MB body limit
         .layer(CorsLayer::permissive())
         .with_state(config.clone());
@@ -1,11 +1,19 @@
-use std::fs;
-use crate::config::Config;
+use std::{collections::HashMap, fs, path::Path, process::Stdio, time::Duration};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use syslog_decoder::validate_dictionary_bytes;
+use tokio::{io::AsyncReadExt, process::Command, time::timeout};
+use crate::{config::Config, services::file_service::describe_time_limit};
 
 #[derive(Debug)]
 pub enum ServiceError {
     IoError(std::io::Error),
     NotFound(String),
     InvalidInput(String),
+    /// An uploaded file exceeded the upload size limit
+    PayloadTooLarge(String),
+    /// A decode task panicked or was cancelled; reported as a server error
+    Internal(String),
 }
 
 impl From<std::io::Error> for ServiceError {
@@ -40,3 +48,165 @@ pub fn get_available_decoders(config: &Config) -> Result<Vec<String>, ServiceErr
     
     Ok(result)
 }
+
+/// Manifest the downloader writes next to the dictionaries, holding the size and SHA-256
+/// of each file as it was downloaded
+pub const DOWNLOAD_MANIFEST: &str = "dictionary_hashes.json";
+
+/// One dictionary's entry in the download manifest
+#[derive(Debug, Deserialize)]
+struct DownloadRecord {
+    size: u64,
+    sha256: String,
+}
+
+/// Download records keyed by dictionary file name; empty when the manifest is missing or
+/// unreadable, so every dictionary is then reported as unrecorded
+fn load_download_manifest(downloads_dir: &Path) -> HashMap<String, DownloadRecord> {
+    fs::read(downloads_dir.join(DOWNLOAD_MANIFEST))
+        .ok()
+        .and_then(|contents| serde_json::from_slice(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Integrity of one downloaded dictionary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DictionaryCheck {
+    pub version: String,
+    pub size: u64,
+    pub sha256: String,
+    pub entry_count: usize,
+    /// Signs of a truncated or corrupt download; empty when the dictionary looks intact
+    pub problems: Vec<String>,
+}
+
+/// Check every dictionary in downloads for signs of a truncated or corrupt download
+pub fn verify_decoders(config: &Config) -> Result<Vec<DictionaryCheck>, ServiceError> {
+    let downloads_dir = config.downloads_dir();
+    let download_records = load_download_manifest(&downloads_dir);
+    let mut checks = Vec::new();
+
+    for version in get_available_decoders(config)? {
+        let file_name = format!("{}.log", version);
+        let contents = fs::read(downloads_dir.join(&file_name))?;
+        let size = contents.len() as u64;
+        let sha256: String = Sha256::digest(&contents).iter().map(|byte| format!("{:02x}", byte)).collect();
+        let validation = validate_dictionary_bytes(&contents);
+
+        let mut problems = Vec::new();
+        // Every entry is NUL terminated, so a download cut short ends part way through one
+        if contents.last() != Some(&0) {
+            problems.push("Does not end on a NULL boundary; the download may be truncated".to_string());
+        }
+        if validation.entry_count == 0 {
+            problems.push("Contains no parseable entries".to_string());
+        }
+        match download_records.get(&file_name) {
+            Some(record) if record.size != size || record.sha256 != sha256 => {
+                problems.push(format!("Differs from the downloaded file ({} bytes, sha256 {})", record.size, record.sha256));
+            }
+            Some(_) => {}
+            None => problems.push("Has no download record, so its contents cannot be confirmed".to_string()),
+        }
+
+        checks.push(DictionaryCheck {
+            version,
+            size,
+            sha256,
+            entry_count: validation.entry_count,
+            problems,
+        });
+    }
+
+    Ok(checks)
+}
+
+/// Run the dictionary downloader `script` under bash, killing it once `time_limit` passes.
+/// A failure reports at most `max_output` bytes of the script's stderr; the rest is drained
+/// and dropped so a chatty script can neither block on a full pipe nor grow the response.
+pub async fn run_downloader_script(script: &str, time_limit: Duration, max_output: usize) -> Result<(), String> {
+    let mut child = Command::new("bash")
+        .arg("-c")
+        .arg(script)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to execute Azure downloader script: {}", e))?;
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let run = async {
+        let mut output = Vec::new();
+        (&mut stderr).take(max_output as u64).read_to_end(&mut output).await?;
+        let truncated = tokio::io::copy(&mut stderr, &mut tokio::io::sink()).await? > 0;
+        let status = child.wait().await?;
+        Ok::<_, std::io::Error>((status, output, truncated))
+    };
+    let result = timeout(time_limit, run).await;
+
+    match result {
+        Ok(Ok((status, _, _))) if status.success() => Ok(()),
+        Ok(Ok((_, output, truncated))) => Err(format!(
+            "Azure files refresh failed: {}{}",
+            String::from_utf8_lossy(&output),
+            if truncated { " [output truncated]" } else { "" }
+        )),
+        Ok(Err(e)) => Err(format!("Failed to execute Azure downloader script: {}", e)),
+        Err(_) => {
+            let _ = child.kill().await;
+            Err(format!("Azure files refresh timed out after {}", describe_time_limit(time_limit)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::test_config;
+
+    #[test]
+    fn test_verify_decoders_reports_corrupt_dictionaries() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(temp_dir.path());
+        let intact = "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00";
+        let intact_sha256: String = Sha256::digest(intact).iter().map(|byte| format!("{:02x}", byte)).collect();
+        fs::write(temp_dir.path().join("1.0.log"), intact).unwrap();
+        fs::write(temp_dir.path().join("1.1.log"), &intact[..60]).unwrap();
+        fs::write(temp_dir.path().join("1.2.log"), b"\xff\xfe garbage\x00").unwrap();
+        // 1.1 was truncated after download and 1.2 was never recorded
+        let record = serde_json::json!({ "size": intact.len(), "sha256": intact_sha256 });
+        let manifest = serde_json::json!({ "1.0.log": record, "1.1.log": record });
+        fs::write(temp_dir.path().join(DOWNLOAD_MANIFEST), manifest.to_string()).unwrap();
+
+        let checks = verify_decoders(&config).unwrap();
+        let problems: Vec<(&str, usize)> = checks.iter().map(|check| (check.version.as_str(), check.problems.len())).collect();
+        assert_eq!(problems, [("1.0", 0), ("1.1", 2), ("1.2", 2)]);
+        assert_eq!(checks[0].entry_count, 2);
+        assert_eq!(checks[0].size, intact.len() as u64);
+        assert_eq!(checks[0].sha256, intact_sha256);
+        assert!(checks[1].problems[0].contains("NULL boundary"));
+        assert!(checks[1].problems[1].starts_with(&format!("Differs from the downloaded file ({} bytes", intact.len())));
+        assert_eq!(checks[2].problems, ["Contains no parseable entries", "Has no download record, so its contents cannot be confirmed"]);
+
+        // Without a manifest nothing is trusted, even a dictionary that looks intact
+        fs::remove_file(temp_dir.path().join(DOWNLOAD_MANIFEST)).unwrap();
+        assert_eq!(verify_decoders(&config).unwrap()[0].problems.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_downloader_script_limits() {
+        assert_eq!(run_downloader_script("exit 0", Duration::from_secs(5), 16).await, Ok(()));
+
+        let error = run_downloader_script("sleep 5", Duration::from_millis(100), 16).await.unwrap_err();
+        assert_eq!(error, "Azure files refresh timed out after 100 milliseconds");
+
+        // Only the first 16 bytes of a large error output are kept
+        let error = run_downloader_script("head -c 100000 /dev/zero | tr '\\0' x >&2; exit 1", Duration::from_secs(5), 16).await.unwrap_err();
+        assert_eq!(error, format!("Azure files refresh failed: {} [output truncated]", "x".repeat(16)));
+
+        let error = run_downloader_script("echo missing credentials >&2; exit 1", Duration::from_secs(5), 64).await.unwrap_err();
+        assert_eq!(error, "Azure files refresh failed: missing credentials\n");
+    }
+}
@@ -1,20 +1,27 @@
 use std::{
-    path::PathBuf,
-    time::{SystemTime, UNIX_EPOCH, Duration},
+    path::{Path, PathBuf},
+    sync::{atomic::{AtomicBool, Ordering}, Arc},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use axum::extract::Multipart;
-use syslog_decoder::SyslogParser;
-use tokio::time::timeout;
+use syslog_decoder::{conformance::PreflightReport, session::{SessionOptions, SessionSplitter}, DecodeError, DecodeStats, FormatOptions, ParserConfig, SyslogParser};
+use tokio::{fs::File, io::AsyncWriteExt, sync::mpsc, task, time::timeout};
 use crate::{
     config::Config, 
     services::decoder_service::ServiceError, 
+    services::result_cache::{sha256_file, CacheKey},
     parser::session_parser::parse_log_sessions,
-    types::UploadedFiles,
+    types::{DecodeOutput, UploadedFiles},
 };
 
 // Resource management constants
 const PROCESSING_TIMEOUT: Duration = Duration::from_secs(45 * 60); // 45 minutes for very large files
-const MAX_UPLOAD_SIZE: usize = 500 * 1024 * 1024; // 500MB upload limit
+pub const MAX_UPLOAD_SIZE: usize = 500 * 1024 * 1024; // 500MB upload limit
+/// Smallest upload streamed when the client asks for it; smaller decodes finish quickly
+/// enough to be sent as one JSON document
+pub const STREAM_MIN_UPLOAD_SIZE: u64 = 8 * 1024 * 1024;
+/// Decoded lines returned by a preflight check
+const PREFLIGHT_SAMPLE_LINES: usize = 5;
 
 pub struct FileProcessor {
     config: Config,
@@ -25,148 +32,775 @@ impl FileProcessor {
         Self { config }
     }
 
-    pub async fn process_upload(&self, mut multipart: Multipart) -> Result<UploadedFiles, ServiceError> {
+    /// Store the uploaded captures, one `UploadedFiles` per `file` field in upload order,
+    /// each sharing the custom dictionary if one was sent
+    pub async fn process_upload(&self, multipart: Multipart) -> Result<Vec<UploadedFiles>, ServiceError> {
+        self.process_upload_with_limit(multipart, MAX_UPLOAD_SIZE).await
+    }
+
+    /// `process_upload` with the largest accepted file size in bytes
+    async fn process_upload_with_limit(&self, mut multipart: Multipart, max_upload_size: usize) -> Result<Vec<UploadedFiles>, ServiceError> {
         let temp_dir = self.config.temp_dir();
-        
-        // Clean up previous temp files
-        crate::config::cleanup_temp_files(&temp_dir)?;
 
-        let mut binary_file: Option<PathBuf> = None;
+        let mut binary_files: Vec<(String, PathBuf)> = Vec::new();
         let mut custom_decoder_file: Option<PathBuf> = None;
+        // Every file written so far, removed again if any part of the upload is rejected
+        let mut stored_files: Vec<PathBuf> = Vec::new();
+
+        let stored = async {
+            while let Some(mut field) = multipart
+                .next_field()
+                .await
+                .map_err(|e| ServiceError::InvalidInput(format!("Invalid multipart data: {}", e)))?
+            {
+                let Some(field_name) = field.name().map(str::to_string) else {
+                    continue;
+                };
+                // Browsers send a file part with an empty filename when nothing was selected
+                let Some(filename) = field.file_name().filter(|filename| !filename.is_empty()).map(str::to_string) else {
+                    continue;
+                };
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis();
+                // Captures of a batch often share a name, so number them within the upload
+                let temp_filename = format!("{}_{}_{}", now, stored_files.len(), filename);
+                let filepath = temp_dir.join(&temp_filename);
+
+                // Write chunks to the temp file as they arrive so the upload is never held in memory
+                let mut file = File::create(&filepath).await.map_err(ServiceError::IoError)?;
+                stored_files.push(filepath.clone());
+                let mut total_size = 0;
+
+                while let Some(chunk) = field.chunk().await.map_err(|e| {
+                    ServiceError::InvalidInput(format!("Failed to read file chunk: {}", e))
+                })? {
+                    total_size += chunk.len();
 
-        while let Some(mut field) = multipart
-            .next_field()
-            .await
-            .map_err(|e| ServiceError::InvalidInput(format!("Invalid multipart data: {}", e)))?
-        {
-            if let Some(field_name) = field.name() {
-                let field_name = field_name.to_string();
-                
-                if let Some(filename) = field.file_name() {
-                    let filename = filename.to_string();
-                    let now = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis();
-                    let temp_filename = format!("{}_{}", now, filename);
-                    let filepath = temp_dir.join(&temp_filename);
-                    
-                    // Use streaming approach for large files
-                    let mut buffer = Vec::new();
-                    let mut total_size = 0;
-                    
-                    // Read in smaller chunks to avoid memory issues
-                    while let Some(chunk) = field.chunk().await.map_err(|e| {
-                        ServiceError::InvalidInput(format!("Failed to read file chunk: {}", e))
-                    })? {
-                        total_size += chunk.len();
-                        
-                        // Check upload size limit early
-                        if total_size > MAX_UPLOAD_SIZE {
-                            return Err(ServiceError::InvalidInput(
-                                format!("File too large: {} bytes (max: {} bytes)", 
-                                       total_size, MAX_UPLOAD_SIZE)
-                            ));
-                        }
-                        
-                        buffer.extend_from_slice(&chunk);
+                    // Stop reading as soon as the limit is passed
+                    if total_size > max_upload_size {
+                        return Err(ServiceError::PayloadTooLarge(
+                            format!("File too large: {} (max: {} bytes)", filename, max_upload_size)
+                        ));
                     }
-                    
-                    // Write the entire buffer to file at once
-                    std::fs::write(&filepath, &buffer)
-                        .map_err(|e| ServiceError::IoError(e))?;
-                    
-                    println!("Uploaded {} file: {} ({:.2} MB)", field_name, filename, total_size as f64 / (1024.0 * 1024.0));
-                    
-                    // Assign to appropriate field based on field name
-                    match field_name.as_str() {
-                        "file" => binary_file = Some(filepath),
-                        "custom_decoder" => custom_decoder_file = Some(filepath),
-                        _ => {
-                            // Default to binary file if field name is unknown
-                            if binary_file.is_none() {
-                                binary_file = Some(filepath);
-                            }
-                        }
+
+                    file.write_all(&chunk).await.map_err(ServiceError::IoError)?;
+                }
+                file.flush().await.map_err(ServiceError::IoError)?;
+
+                if total_size == 0 {
+                    return Err(ServiceError::InvalidInput(format!("Uploaded file {} is empty", filename)));
+                }
+
+                println!("Uploaded {} file: {} ({:.2} MB)", field_name, filename, total_size as f64 / (1024.0 * 1024.0));
+
+                // Assign to appropriate field based on field name
+                match field_name.as_str() {
+                    "file" => binary_files.push((filename, filepath)),
+                    "custom_decoder" => custom_decoder_file = Some(filepath),
+                    // Default to binary file if field name is unknown
+                    _ if binary_files.is_empty() => binary_files.push((filename, filepath)),
+                    _ => {
+                        stored_files.pop();
+                        let _ = tokio::fs::remove_file(&filepath).await;
                     }
                 }
             }
+
+            if binary_files.is_empty() {
+                return Err(ServiceError::InvalidInput("No binary file found in upload".to_string()));
+            }
+            Ok(())
+        }.await;
+
+        if let Err(e) = stored {
+            for path in &stored_files {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+            return Err(e);
+        }
+        Ok(binary_files.into_iter()
+            .map(|(original_name, binary_file)| UploadedFiles {
+                original_name,
+                binary_file,
+                custom_decoder_file: custom_decoder_file.clone(),
+            })
+            .collect())
+    }
+
+    /// Hash the upload and the dictionary to identify a decode in the result cache
+    async fn cache_key(&self, input_file: &Path, dict_path: &Path, log_level: u8, include_summary: bool) -> Result<CacheKey, ServiceError> {
+        let input_file = input_file.to_path_buf();
+        let dict_path = dict_path.to_path_buf();
+        let (input_sha256, dictionary_sha256) = task::spawn_blocking(move || {
+            Ok::<_, std::io::Error>((sha256_file(&input_file)?, sha256_file(&dict_path)?))
+        }).await
+            .map_err(|_| ServiceError::Internal("Hashing task failed".to_string()))??;
+
+        Ok(CacheKey {
+            input_sha256,
+            dictionary_sha256,
+            log_level,
+            include_summary,
+        })
+    }
+
+    /// Find the dictionary file in downloads for a firmware version
+    pub fn dictionary_path(&self, firmware_version: &str) -> Result<PathBuf, ServiceError> {
+        // The version comes from the client, so it must not name a file outside downloads
+        if !is_valid_firmware_version(firmware_version) {
+            return Err(ServiceError::InvalidInput(format!("Invalid firmware version: {}", firmware_version)));
         }
+        let dict_filename = format!("{}.log", firmware_version);
+        let dict_path = self.config.downloads_dir().join(&dict_filename);
         
-        match binary_file {
-            Some(binary_path) => Ok(UploadedFiles {
-                binary_file: binary_path,
-                custom_decoder_file,
-            }),
-            None => Err(ServiceError::InvalidInput("No binary file found in upload".to_string())),
+        // Check if dictionary file exists
+        if !dict_path.exists() {
+            return Err(ServiceError::NotFound(
+                format!("Dictionary file not found: {}. Please refresh the files or provide a custom decoder file.", dict_filename)
+            ));
         }
+        Ok(dict_path)
     }
 
-    pub async fn run_decoder(&self, input_file: &PathBuf, firmware_version: &str, log_level: &str, _include_log_level: bool, custom_decoder_file: Option<&PathBuf>) -> Result<String, ServiceError> {
+    /// The dictionary a decode uses, with its filename for messages
+    fn decode_dictionary(&self, firmware_version: &str, custom_decoder_file: Option<&PathBuf>) -> Result<(PathBuf, String), ServiceError> {
         // Determine which dictionary file to use
         let dict_path = if let Some(custom_file) = custom_decoder_file {
             // Use the custom decoder file
             custom_file.clone()
         } else {
-            // Use the firmware version to find the corresponding dictionary file in downloads
-            let dict_filename = format!("{}.log", firmware_version);
-            let dict_path = self.config.downloads_dir().join(&dict_filename);
-            
-            // Check if dictionary file exists
-            if !dict_path.exists() {
-                return Err(ServiceError::NotFound(
-                    format!("Dictionary file not found: {}. Please refresh the files or provide a custom decoder file.", dict_filename)
-                ));
-            }
-            dict_path
+            self.dictionary_path(firmware_version)?
         };
         
         // Get the dictionary filename for logging
         let dict_filename = dict_path.file_name()
             .and_then(|name| name.to_str())
-            .unwrap_or("custom_decoder");
+            .unwrap_or("custom_decoder")
+            .to_string();
+        Ok((dict_path, dict_filename))
+    }
+
+    pub async fn run_decoder(&self, input_file: &Path, firmware_version: &str, log_level: &str, _include_log_level: bool, include_summary: bool, custom_decoder_file: Option<&PathBuf>) -> Result<DecodeOutput, ServiceError> {
+        self.run_decoder_with_timeout(input_file, firmware_version, log_level, include_summary, custom_decoder_file, PROCESSING_TIMEOUT).await
+    }
+
+    /// Decode each capture of a batch in turn, keyed by its original filename.
+    ///
+    /// The batch shares one `PROCESSING_TIMEOUT`: each capture may use an equal share of
+    /// what is left when it starts, so time a quick decode doesn't need goes to the
+    /// captures after it. A failed capture is reported in its entry and the batch goes on.
+    pub async fn run_batch_decoder(&self, uploads: &[UploadedFiles], firmware_version: &str, log_level: &str, include_summary: bool) -> Vec<(String, Result<DecodeOutput, ServiceError>)> {
+        let started = Instant::now();
+        let mut results = Vec::with_capacity(uploads.len());
+        for (index, upload) in uploads.iter().enumerate() {
+            let remaining = PROCESSING_TIMEOUT.saturating_sub(started.elapsed());
+            let time_limit = remaining / (uploads.len() - index) as u32;
+            let result = self.run_decoder_with_timeout(
+                &upload.binary_file,
+                firmware_version,
+                log_level,
+                include_summary,
+                upload.custom_decoder_file.as_ref(),
+                time_limit,
+            ).await;
+            results.push((upload.original_name.clone(), result));
+        }
+        results
+    }
+
+    async fn run_decoder_with_timeout(&self, input_file: &Path, firmware_version: &str, log_level: &str, include_summary: bool, custom_decoder_file: Option<&PathBuf>, time_limit: Duration) -> Result<DecodeOutput, ServiceError> {
+        let (dict_path, dict_filename) = self.decode_dictionary(firmware_version, custom_decoder_file)?;
         
         println!("Starting syslog parser library with dictionary: {} and log level {} (always including log levels in response)", dict_filename, log_level);
         
         // Parse log level
         let log_level_num: u8 = log_level.parse()
             .map_err(|_| ServiceError::InvalidInput("Invalid log level".to_string()))?;
-        
-        // Run decoder with timeout protection
-        let result = timeout(PROCESSING_TIMEOUT, async {
-            // Create syslog parser with dictionary
-            let parser = SyslogParser::new(&dict_path)
-                .map_err(|e| ServiceError::InvalidInput(format!("Failed to load dictionary: {}", e)))?;
-            
-            // Parse binary file (this now handles large files with streaming)
-            let parsed_logs = parser.parse_binary(input_file, log_level_num)
-                .map_err(|e| ServiceError::InvalidInput(format!("Failed to parse binary file: {}", e)))?;
-            
-            // Always format logs with log levels - frontend will control display
-            let formatted_logs = parser.format_logs_with_options(&parsed_logs, true);
-            
-            // Join all formatted logs with newlines for session parsing
-            let decoded_text = formatted_logs.join("\n");
-            
-            // Parse into sessions
-            let sessions = parse_log_sessions(&decoded_text);
-            
-            // Return sessions as JSON
-            let sessions_json = serde_json::to_string(&sessions)
-                .map_err(|e| ServiceError::InvalidInput(format!("Failed to serialize sessions: {}", e)))?;
-            
-            println!("Syslog parsing completed successfully, {} logs processed, {} sessions created", 
-                     formatted_logs.len(), sessions.len());
-            
-            Ok::<String, ServiceError>(sessions_json)
-        }).await;
-        
-        match result {
-            Ok(decoder_result) => decoder_result,
+
+        // Repeated decodes of the same upload are served from the result cache
+        let cache_key = self.cache_key(input_file, &dict_path, log_level_num, include_summary).await?;
+        if let Some(cached_result) = self.config.result_cache.get(&cache_key) {
+            println!("Serving cached decode for input {}", cache_key.input_sha256);
+            return Ok(cached_result);
+        }
+
+        // Run decoder with timeout protection, off the async executor since parsing is CPU-bound.
+        // Returning early, on timeout or when the request is dropped, cancels the decode.
+        let input_file = input_file.to_path_buf();
+        let max_total_args = self.config.max_total_args;
+        let decode_slots = self.config.decode_slots.clone();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _cancel_on_drop = CancelOnDrop(cancelled.clone());
+        let decode = async move {
+            // Wait for a decode slot so a few large files can't take over the blocking pool;
+            // the wait counts against the time limit
+            let decode_permit = decode_slots.acquire_owned().await
+                .map_err(|_| ServiceError::InvalidInput("Decoder is shutting down".to_string()))?;
+            task::spawn_blocking(move || {
+                let _decode_permit = decode_permit;
+
+                let parser = load_parser(&dict_path, &dict_filename, max_total_args)?;
+
+                decode_sessions(&parser, &input_file, log_level_num, include_summary, &cancelled)
+            }).await.map_err(|_| ServiceError::Internal("Decoder task failed".to_string()))?
+        };
+
+        match timeout(time_limit, decode).await {
+            Ok(Ok(output)) => {
+                self.config.result_cache.insert(cache_key, output.clone());
+                Ok(output)
+            }
+            Ok(Err(e)) => Err(e),
             Err(_) => Err(ServiceError::InvalidInput(
-                format!("Processing timed out after {} minutes. File may be too large or corrupted.", 
-                       PROCESSING_TIMEOUT.as_secs() / 60)
+                format!("Processing timed out after {}. File may be too large or corrupted.", 
+                       describe_time_limit(time_limit))
             ))
         }
     }
+
+    /// Check how many of the first `max_records` records of a capture resolve against the
+    /// dictionary, with a few sample lines, without decoding the whole capture
+    pub async fn run_preflight(&self, input_file: &Path, firmware_version: &str, custom_decoder_file: Option<&PathBuf>, max_records: usize) -> Result<PreflightReport, ServiceError> {
+        let (dict_path, dict_filename) = self.decode_dictionary(firmware_version, custom_decoder_file)?;
+        let decode_permit = self.config.decode_slots.clone().acquire_owned().await
+            .map_err(|_| ServiceError::InvalidInput("Decoder is shutting down".to_string()))?;
+        let input_file = input_file.to_path_buf();
+        let max_total_args = self.config.max_total_args;
+        task::spawn_blocking(move || {
+            let _decode_permit = decode_permit;
+            let parser = load_parser(&dict_path, &dict_filename, max_total_args)?;
+            parser.preflight(&input_file, max_records, PREFLIGHT_SAMPLE_LINES)
+                .map_err(|e| ServiceError::InvalidInput(format!("Failed to parse binary file: {}", e)))
+        }).await.map_err(|_| ServiceError::Internal("Decoder task failed".to_string()))?
+    }
+
+    /// Decode on the blocking pool, sending each session as an NDJSON line as soon as the
+    /// decode reaches the start of the next one.
+    ///
+    /// Dictionary and log level problems are returned before anything is sent. A decode
+    /// error part way through is sent as a final `{"status": "error", ...}` line, since the
+    /// response status is already out. Streamed decodes skip the result cache and the job
+    /// store, and stop early when the receiver is dropped. `upload_cleanup` is held until the
+    /// decode ends, since the response outlives the request handler.
+    pub async fn stream_decoder(&self, input_file: &Path, firmware_version: &str, log_level: &str, include_summary: bool, custom_decoder_file: Option<&PathBuf>, upload_cleanup: UploadCleanup) -> Result<mpsc::Receiver<Result<String, std::io::Error>>, ServiceError> {
+        self.stream_decoder_with_timeout(input_file, firmware_version, log_level, include_summary, custom_decoder_file, upload_cleanup, PROCESSING_TIMEOUT).await
+    }
+
+    /// `stream_decoder` that gives up once `time_limit` has passed, whether waiting for a
+    /// decode slot, decoding, or waiting for a client that stopped reading
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_decoder_with_timeout(&self, input_file: &Path, firmware_version: &str, log_level: &str, include_summary: bool, custom_decoder_file: Option<&PathBuf>, upload_cleanup: UploadCleanup, time_limit: Duration) -> Result<mpsc::Receiver<Result<String, std::io::Error>>, ServiceError> {
+        let deadline = Instant::now() + time_limit;
+        let timeout_message = format!("Processing timed out after {}. File may be too large or corrupted.", describe_time_limit(time_limit));
+        let (dict_path, dict_filename) = self.decode_dictionary(firmware_version, custom_decoder_file)?;
+        let log_level_num: u8 = log_level.parse()
+            .map_err(|_| ServiceError::InvalidInput("Invalid log level".to_string()))?;
+
+        let decode_permit = timeout(time_limit, self.config.decode_slots.clone().acquire_owned()).await
+            .map_err(|_| ServiceError::InvalidInput(timeout_message.clone()))?
+            .map_err(|_| ServiceError::InvalidInput("Decoder is shutting down".to_string()))?;
+        let max_total_args = self.config.max_total_args;
+        let parser = task::spawn_blocking(move || load_parser(&dict_path, &dict_filename, max_total_args)).await
+            .map_err(|_| ServiceError::Internal("Decoder task failed".to_string()))??;
+
+        println!("Streaming decode of {} at log level {}", input_file.display(), log_level_num);
+        let input_file = input_file.to_path_buf();
+        let (sender, receiver) = mpsc::channel(16);
+        let runtime = tokio::runtime::Handle::current();
+        let timeout_line = serde_json::json!({"status": "error", "message": timeout_message}).to_string();
+        task::spawn_blocking(move || {
+            let _decode_permit = decode_permit;
+            let _upload_cleanup = upload_cleanup;
+            // A client that stops reading fills the channel; wait for room only until the deadline
+            let send_line = |line: String| {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                runtime.block_on(sender.send_timeout(Ok(line + "\n"), remaining)).is_ok()
+            };
+            let send_session = |session| send_line(serde_json::to_string(&session).unwrap_or_default());
+
+            let format_options = FormatOptions {
+                include_log_level: true,
+                ..Default::default()
+            };
+            let mut splitter = SessionSplitter::new(SessionOptions::default());
+            let mut stats = DecodeStats::default();
+            let mut sessions_sent = 0;
+            let logs = match parser.parse_binary_iter(&input_file, log_level_num) {
+                Ok(logs) => logs,
+                Err(e) => {
+                    send_line(stream_error_line(&e));
+                    return;
+                }
+            };
+            for parsed_log in logs {
+                if sender.is_closed() {
+                    return; // Client went away
+                }
+                if Instant::now() >= deadline {
+                    let _ = sender.try_send(Ok(timeout_line + "\n"));
+                    return;
+                }
+                let parsed_log = match parsed_log {
+                    Ok(parsed_log) => parsed_log,
+                    Err(e) => {
+                        send_line(stream_error_line(&e));
+                        return;
+                    }
+                };
+                stats.add(&parsed_log);
+                if let Some(session) = splitter.push_line(&parser.format_log(&parsed_log, &format_options)) {
+                    if !send_session(session) {
+                        return; // Client went away
+                    }
+                    sessions_sent += 1;
+                }
+            }
+
+            if let Some(session) = splitter.finish() {
+                if !send_session(session) {
+                    return;
+                }
+                sessions_sent += 1;
+            }
+            // The summary follows the sessions on its own line rather than inside the last one
+            if include_summary {
+                send_line(serde_json::json!({"summary": parser.summary_line_from_stats(&stats)}).to_string());
+            }
+            println!("Streamed {} sessions from {} logs", sessions_sent, stats.entry_count);
+        });
+        Ok(receiver)
+    }
+}
+
+/// Deletes the temp files stored for one request's uploads when dropped, so each request
+/// removes its own files once nothing reads them any more and leaves other requests' alone
+pub struct UploadCleanup(Vec<PathBuf>);
+
+impl UploadCleanup {
+    pub fn new(uploads: &[UploadedFiles]) -> Self {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for upload in uploads {
+            // Captures of a batch share one custom dictionary
+            for path in std::iter::once(&upload.binary_file).chain(&upload.custom_decoder_file) {
+                if !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+        Self(paths)
+    }
+}
+
+impl Drop for UploadCleanup {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Cancels the decode it was made for when dropped, so a decode whose request timed out or
+/// went away stops at its next record instead of holding a blocking thread to the end
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Decode a capture into sessions, formatting logs as they decode so only the text is held,
+/// not every parsed log. Gives up at the next record once `cancelled` is set.
+fn decode_sessions(parser: &SyslogParser, input_file: &Path, log_level: u8, include_summary: bool, cancelled: &AtomicBool) -> Result<DecodeOutput, ServiceError> {
+    // Always format logs with log levels - frontend will control display
+    let format_options = FormatOptions {
+        include_log_level: true,
+        ..Default::default()
+    };
+
+    let parse_error = |e: anyhow::Error| ServiceError::InvalidInput(format!("Failed to parse binary file: {}", e));
+    let mut decoded_text = String::new();
+    let mut stats = DecodeStats::default();
+    let start = parser.counters();
+    for parsed_log in parser.parse_binary_iter(input_file, log_level).map_err(parse_error)? {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(ServiceError::Internal("Decode was cancelled".to_string()));
+        }
+        let parsed_log = parsed_log.map_err(parse_error)?;
+        if !decoded_text.is_empty() {
+            decoded_text.push('\n');
+        }
+        decoded_text.push_str(&parser.format_log(&parsed_log, &format_options));
+        stats.add(&parsed_log);
+    }
+
+    // Parse into sessions
+    let sessions = parse_log_sessions(&decoded_text);
+
+    // Return sessions as JSON
+    let sessions_json = serde_json::to_string(&sessions)
+        .map_err(|e| ServiceError::InvalidInput(format!("Failed to serialize sessions: {}", e)))?;
+
+    println!("Syslog parsing completed successfully, {} logs processed, {} sessions created",
+             stats.entry_count, sessions.len());
+
+    Ok(DecodeOutput {
+        sessions_json,
+        warnings: parser.warnings_since(start),
+        summary: include_summary.then(|| parser.summary_line_from_stats(&stats)),
+    })
+}
+
+/// A time limit in whole minutes, seconds when a batch share is under a minute, or
+/// milliseconds when it is under a second
+pub(crate) fn describe_time_limit(time_limit: Duration) -> String {
+    match time_limit.as_secs() {
+        0 => format!("{} milliseconds", time_limit.as_millis()),
+        secs if secs < 60 => format!("{} seconds", secs),
+        secs => format!("{} minutes", secs / 60),
+    }
+}
+
+/// A version names a dictionary directly in downloads: a single file stem with no path
+/// separators or `..`, as listed by `get_available_decoders`
+fn is_valid_firmware_version(version: &str) -> bool {
+    !version.is_empty()
+        && !version.contains(['/', '\\', '\0'])
+        && !version.contains("..")
+}
+
+/// Create the syslog parser for a decode, capping the total argument count
+fn load_parser(dict_path: &Path, dict_filename: &str, max_total_args: usize) -> Result<SyslogParser, ServiceError> {
+    let parser_config = ParserConfig {
+        max_total_args: Some(max_total_args),
+        warn_on_missing_arguments: true,
+        ..Default::default()
+    };
+    SyslogParser::with_config(dict_path, parser_config)
+        .map_err(|e| match e.downcast_ref::<DecodeError>() {
+            Some(DecodeError::EmptyDictionary { .. }) => ServiceError::InvalidInput(
+                format!("Dictionary {} is empty or corrupt: no log entries could be parsed", dict_filename)),
+            _ => ServiceError::InvalidInput(format!("Failed to load dictionary: {}", e)),
+        })
+}
+
+/// The last line of a stream whose decode failed part way through
+fn stream_error_line(error: &anyhow::Error) -> String {
+    serde_json::json!({
+        "status": "error",
+        "message": format!("Failed to parse binary file: {}", error),
+    }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::Arc;
+    use tempfile::{NamedTempFile, TempDir};
+    use crate::services::ResultCache;
+    use crate::test_support::{multipart_request, test_config};
+
+    fn write_test_dictionary() -> NamedTempFile {
+        let mut dict_file = NamedTempFile::new().unwrap();
+        write!(dict_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00").unwrap();
+        dict_file.flush().unwrap();
+        dict_file
+    }
+
+    fn write_test_binary(records: u32) -> NamedTempFile {
+        let mut binary_data = Vec::new();
+        for i in 0..records {
+            binary_data.extend_from_slice(&(i * 100).to_le_bytes()); // timestamp
+            binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes()); // 2 args, byte offset 0
+            binary_data.extend_from_slice(&i.to_le_bytes());
+            binary_data.extend_from_slice(&(i + 1).to_le_bytes());
+        }
+        let binary_file = NamedTempFile::new().unwrap();
+        std::fs::write(binary_file.path(), binary_data).unwrap();
+        binary_file
+    }
+
+    #[tokio::test]
+    async fn test_run_decoder_enforces_argument_budget() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(temp_dir.path());
+        config.max_total_args = 10;
+        let processor = FileProcessor::new(config);
+
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+
+        // 5 records x 2 args fits the budget
+        let binary_file = write_test_binary(5);
+        let result = processor.run_decoder(binary_file.path(), "", "5", true, false, Some(&dict_path)).await;
+        assert!(result.is_ok());
+
+        // 6 records x 2 args exceeds it
+        let binary_file = write_test_binary(6);
+        let result = processor.run_decoder(binary_file.path(), "", "5", true, false, Some(&dict_path)).await;
+        match result {
+            Err(ServiceError::InvalidInput(msg)) => assert!(msg.contains("Argument budget exceeded")),
+            _ => panic!("Expected the argument budget to be enforced"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waiting_for_a_decode_slot_counts_against_the_time_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(temp_dir.path());
+        let decode_slots = config.decode_slots.clone();
+        let processor = FileProcessor::new(config);
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+        let binary_file = write_test_binary(5);
+
+        // With the only slot taken the decode times out instead of waiting indefinitely
+        let _held_slot = decode_slots.acquire().await.unwrap();
+        let result = processor.run_decoder_with_timeout(binary_file.path(), "", "5", false, Some(&dict_path), Duration::from_millis(50)).await;
+        assert!(matches!(result, Err(ServiceError::InvalidInput(msg)) if msg.starts_with("Processing timed out after 50 milliseconds")));
+    }
+
+    #[tokio::test]
+    async fn test_batch_decoder_reports_each_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(temp_dir.path());
+        config.max_total_args = 10;
+        let processor = FileProcessor::new(config);
+
+        let dict_file = write_test_dictionary();
+        let binaries = [write_test_binary(5), write_test_binary(6), write_test_binary(3)];
+        let uploads: Vec<UploadedFiles> = ["run1.bin", "run2.bin", "run3.bin"].iter().zip(&binaries)
+            .map(|(name, binary_file)| UploadedFiles {
+                original_name: name.to_string(),
+                binary_file: binary_file.path().to_path_buf(),
+                custom_decoder_file: Some(dict_file.path().to_path_buf()),
+            })
+            .collect();
+
+        // The capture over the argument budget fails without stopping the ones after it
+        let results = processor.run_batch_decoder(&uploads, "", "5", false).await;
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["run1.bin", "run2.bin", "run3.bin"]);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(&results[1].1, Err(ServiceError::InvalidInput(msg)) if msg.contains("Argument budget exceeded")));
+        let sessions: Vec<serde_json::Value> = serde_json::from_str(&results[2].1.as_ref().unwrap().sessions_json).unwrap();
+        assert_eq!(sessions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_decoder_reports_empty_dictionary() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+
+        let dict_file = NamedTempFile::new().unwrap();
+        let dict_path = dict_file.path().to_path_buf();
+        let binary_file = write_test_binary(1);
+        let result = processor.run_decoder(binary_file.path(), "", "5", true, false, Some(&dict_path)).await;
+        match result {
+            Err(ServiceError::InvalidInput(msg)) => assert!(msg.contains("is empty or corrupt")),
+            _ => panic!("Expected an empty dictionary error"),
+        }
+    }
+
+    #[test]
+    fn test_cancelled_decode_stops() {
+        let dict_file = write_test_dictionary();
+        let binary_file = write_test_binary(1_000);
+        let parser = load_parser(dict_file.path(), "test.log", usize::MAX).unwrap();
+
+        let result = decode_sessions(&parser, binary_file.path(), 5, false, &AtomicBool::new(true));
+        assert!(matches!(result, Err(ServiceError::Internal(msg)) if msg == "Decode was cancelled"));
+        assert!(decode_sessions(&parser, binary_file.path(), 5, false, &AtomicBool::new(false)).is_ok());
+
+        // Leaving the request drops its guard, which cancels the decode
+        let cancelled = Arc::new(AtomicBool::new(false));
+        drop(CancelOnDrop(cancelled.clone()));
+        assert!(cancelled.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_stream_decoder_gives_up_on_a_stalled_client() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(temp_dir.path());
+        let decode_slots = config.decode_slots.clone();
+        let processor = FileProcessor::new(config);
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+
+        // More boot cycles than the channel holds
+        let mut binary_data = Vec::new();
+        for _ in 0..40 {
+            binary_data.extend(std::fs::read(write_test_binary(2).path()).unwrap());
+        }
+        let binary_file = NamedTempFile::new().unwrap();
+        std::fs::write(binary_file.path(), binary_data).unwrap();
+
+        // A receiver that is never read stops the decode at the time limit, freeing its slot
+        let stalled = processor.stream_decoder_with_timeout(binary_file.path(), "", "5", false, Some(&dict_path), UploadCleanup::new(&[]), Duration::from_millis(100)).await.unwrap();
+        let slot = timeout(Duration::from_secs(10), decode_slots.acquire()).await;
+        assert!(slot.is_ok(), "the stalled stream kept its decode slot");
+        drop(slot);
+        drop(stalled);
+
+        // A dropped receiver stops it too
+        let dropped = processor.stream_decoder(binary_file.path(), "", "5", false, Some(&dict_path), UploadCleanup::new(&[])).await.unwrap();
+        drop(dropped);
+        assert!(timeout(Duration::from_secs(10), decode_slots.acquire()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stream_decoder_sends_sessions_as_ndjson() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+
+        // Two boot cycles: timestamps restart at 0ms
+        let mut binary_data = std::fs::read(write_test_binary(3).path()).unwrap();
+        binary_data.extend(std::fs::read(write_test_binary(2).path()).unwrap());
+        let binary_file = NamedTempFile::new().unwrap();
+        std::fs::write(binary_file.path(), binary_data).unwrap();
+
+        let mut receiver = processor.stream_decoder(binary_file.path(), "", "5", false, Some(&dict_path), UploadCleanup::new(&[])).await.unwrap();
+        let mut lines = Vec::new();
+        while let Some(line) = receiver.recv().await {
+            lines.push(line.unwrap());
+        }
+        assert!(lines.iter().all(|line| line.ends_with('\n')));
+        let streamed: Vec<serde_json::Value> = lines.iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        // Same sessions as the buffered decode
+        let output = processor.run_decoder(binary_file.path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        let buffered: Vec<serde_json::Value> = serde_json::from_str(&output.sessions_json).unwrap();
+        assert_eq!(streamed.len(), 2);
+        assert_eq!(streamed, buffered);
+
+        // Problems found before decoding are returned instead of streamed
+        let result = processor.stream_decoder(binary_file.path(), "", "high", false, Some(&dict_path), UploadCleanup::new(&[])).await;
+        assert!(matches!(result, Err(ServiceError::InvalidInput(msg)) if msg == "Invalid log level"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+        let upload = |content: Vec<u8>| multipart_request(vec![("file", "capture.bin", content)]);
+
+        let result = processor.process_upload_with_limit(upload(vec![0; 64]).await, 16).await;
+        assert!(matches!(result, Err(ServiceError::PayloadTooLarge(msg)) if msg.contains("max: 16 bytes")));
+        // The partial upload is not left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        // Files from earlier fields are removed when a later one is rejected
+        let multipart = multipart_request(vec![
+            ("file", "run1.bin", vec![7; 16]),
+            ("file", "run2.bin", Vec::new()),
+        ]).await;
+        let result = processor.process_upload_with_limit(multipart, 16).await;
+        assert!(matches!(result, Err(ServiceError::InvalidInput(msg)) if msg == "Uploaded file run2.bin is empty"));
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        let uploads = processor.process_upload_with_limit(upload(vec![7; 16]).await, 16).await.unwrap();
+        assert_eq!(std::fs::read(&uploads[0].binary_file).unwrap(), vec![7; 16]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_cleanup_removes_only_its_own_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+        // A file another request is still decoding
+        std::fs::write(temp_dir.path().join("other_capture.bin"), [1, 2, 3]).unwrap();
+
+        let multipart = multipart_request(vec![
+            ("file", "run1.bin", vec![7; 16]),
+            ("file", "run2.bin", vec![8; 16]),
+            ("custom_decoder", "custom.log", b"0;1;init.c:45;SYS_INIT;System started\x00".to_vec()),
+        ]).await;
+        let uploads = processor.process_upload(multipart).await.unwrap();
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 4);
+
+        drop(UploadCleanup::new(&uploads));
+        let remaining: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(remaining, ["other_capture.bin"]);
+    }
+
+    #[test]
+    fn test_dictionary_path_rejects_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+        std::fs::write(temp_dir.path().join("9.17.3.0.log"), "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        assert_eq!(processor.dictionary_path("9.17.3.0").unwrap(), temp_dir.path().join("9.17.3.0.log"));
+
+        for version in ["../../etc/passwd", "..", "/etc/passwd", "nested/9.17.3.0", "..\\secrets", "9.17\0", ""] {
+            let result = processor.dictionary_path(version);
+            assert!(matches!(result, Err(ServiceError::InvalidInput(msg)) if msg.starts_with("Invalid firmware version")),
+                    "{:?} was accepted", version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_decoder_serves_repeats_from_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(temp_dir.path());
+        config.result_cache = Arc::new(ResultCache::new(2));
+        let processor = FileProcessor::new(config.clone());
+
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+        let inputs = [write_test_binary(1), write_test_binary(2), write_test_binary(3)];
+        let key = |input: &NamedTempFile| CacheKey {
+            input_sha256: sha256_file(input.path()).unwrap(),
+            dictionary_sha256: sha256_file(&dict_path).unwrap(),
+            log_level: 5,
+            include_summary: false,
+        };
+
+        let first = processor.run_decoder(inputs[0].path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+
+        // With no argument budget left a real decode fails, so success proves a cache hit
+        let mut exhausted_config = config.clone();
+        exhausted_config.max_total_args = 0;
+        let exhausted = FileProcessor::new(exhausted_config);
+        let repeat = exhausted.run_decoder(inputs[0].path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        assert_eq!(repeat, first);
+        assert!(exhausted.run_decoder(inputs[1].path(), "", "5", true, false, Some(&dict_path)).await.is_err());
+
+        // The third distinct input evicts the least recently used one
+        processor.run_decoder(inputs[1].path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        processor.run_decoder(inputs[0].path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        processor.run_decoder(inputs[2].path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        assert!(config.result_cache.get(&key(&inputs[0])).is_some());
+        assert!(config.result_cache.get(&key(&inputs[1])).is_none());
+        assert!(config.result_cache.get(&key(&inputs[2])).is_some());
+
+        // A dictionary replaced under the same version is not served the old result
+        let downloaded_dict = temp_dir.path().join("9.17.3.0.log");
+        std::fs::write(&downloaded_dict, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00").unwrap();
+        let before = processor.run_decoder(inputs[2].path(), "9.17.3.0", "5", true, false, None).await.unwrap();
+        std::fs::write(&downloaded_dict, "2;4;test.c:123;NEW_MODULE;Trigger no %d at %d\x00").unwrap();
+        let after = processor.run_decoder(inputs[2].path(), "9.17.3.0", "5", true, false, None).await.unwrap();
+        assert!(before.sessions_json.contains("TEST_MODULE"));
+        assert!(after.sessions_json.contains("NEW_MODULE"));
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_returned_beside_the_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = FileProcessor::new(test_config(temp_dir.path()));
+        let dict_file = write_test_dictionary();
+        let dict_path = dict_file.path().to_path_buf();
+        let binary_file = write_test_binary(3);
+
+        let output = processor.run_decoder(binary_file.path(), "", "5", true, true, Some(&dict_path)).await.unwrap();
+        assert_eq!(output.summary.as_deref(), Some("=== Summary: 3 entries, 0ms to 200ms, Info: 3 ==="));
+        assert!(!output.sessions_json.contains("Summary"));
+
+        let output = processor.run_decoder(binary_file.path(), "", "5", true, false, Some(&dict_path)).await.unwrap();
+        assert_eq!(output.summary, None);
+    }
 }
@@ -1,5 +1,9 @@
 pub mod decoder_service;
 pub mod file_service;
+pub mod result_cache;
+pub mod session_archive;
 
 pub use decoder_service::*;
 pub use file_service::*;
+pub use result_cache::*;
+pub use session_archive::*;
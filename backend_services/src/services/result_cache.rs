@@ -0,0 +1,67 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io,
+    path::Path,
+    sync::Mutex,
+};
+use sha2::{Digest, Sha256};
+
+use crate::types::DecodeOutput;
+
+/// Identifies a decode: the uploaded binary, the dictionary and the output options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub input_sha256: String,
+    /// Hash of the dictionary contents, so a refreshed or replaced dictionary is not served
+    /// results decoded with the old one
+    pub dictionary_sha256: String,
+    pub log_level: u8,
+    pub include_summary: bool,
+}
+
+/// Bounded least-recently-used cache of serialized decode results
+pub struct ResultCache {
+    capacity: usize,
+    // Most recently used entry at the back
+    entries: Mutex<VecDeque<(CacheKey, DecodeOutput)>>,
+}
+
+impl ResultCache {
+    /// A capacity of 0 disables caching
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<DecodeOutput> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries.iter().position(|(cached_key, _)| cached_key == key)?;
+        let entry = entries.remove(index)?;
+        let result = entry.1.clone();
+        entries.push_back(entry);
+        Some(result)
+    }
+
+    pub fn insert(&self, key: CacheKey, result: DecodeOutput) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|(cached_key, _)| *cached_key != key);
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back((key, result));
+    }
+}
+
+/// Hex-encoded SHA-256 of a file's contents
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
@@ -0,0 +1,100 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::Mutex,
+};
+use syslog_decoder::session::{LogSession, TimestampFormat};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+/// Sessions JSON of recent decodes, addressable by job id for later exports
+pub struct JobStore {
+    capacity: usize,
+    // Oldest job at the front
+    jobs: Mutex<VecDeque<(String, String)>>,
+}
+
+impl JobStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            jobs: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Keep a decode result and return its job id; the oldest job is dropped when full.
+    ///
+    /// A job id is all that is needed to fetch someone's decoded logs, so ids are 128 bits
+    /// from the OS random number generator rather than anything that could be enumerated.
+    pub fn insert(&self, sessions_json: String) -> String {
+        let mut id_bytes = [0u8; 16];
+        getrandom::getrandom(&mut id_bytes).expect("OS random number generator is unavailable");
+        let job_id: String = id_bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        let mut jobs = self.jobs.lock().unwrap();
+        while !jobs.is_empty() && jobs.len() >= self.capacity {
+            jobs.pop_front();
+        }
+        if self.capacity > 0 {
+            jobs.push_back((job_id.clone(), sessions_json));
+        }
+        job_id
+    }
+
+    pub fn get(&self, job_id: &str) -> Option<String> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter()
+            .find(|(id, _)| id == job_id)
+            .map(|(_, sessions_json)| sessions_json.clone())
+    }
+}
+
+/// File name for a session inside the archive, e.g. `session_002_Epoch_1756474625.log`
+pub fn session_file_name(session: &LogSession) -> String {
+    match &session.timestamp {
+        Some(timestamp) => {
+            let timestamp: String = timestamp.format(TimestampFormat::Epoch).chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+                .collect();
+            format!("session_{:03}_{}.log", session.id, timestamp)
+        }
+        None => format!("session_{:03}.log", session.id),
+    }
+}
+
+/// Write each session as its own `.log` file into a zip streamed to `writer`
+pub fn write_session_archive<W: Write>(sessions: &[LogSession], writer: W) -> zip::result::ZipResult<W> {
+    let mut zip = ZipWriter::new_stream(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for session in sessions {
+        zip.start_file(session_file_name(session), options)?;
+        zip.write_all(session.content.as_bytes())?;
+        zip.write_all(b"\n")?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// `Write` adapter that forwards each write as a chunk over a channel, for streaming bodies
+pub struct ChunkWriter {
+    sender: tokio::sync::mpsc::Sender<io::Result<Vec<u8>>>,
+}
+
+impl ChunkWriter {
+    pub fn new(sender: tokio::sync::mpsc::Sender<io::Result<Vec<u8>>>) -> Self {
+        Self { sender }
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender.blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Archive download was closed"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
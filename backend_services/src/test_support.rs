@@ -0,0 +1,46 @@
+use std::{path::Path, sync::Arc, time::Duration};
+use axum::{
+    body::Body,
+    extract::{FromRequest, Multipart},
+    http::{header, Request},
+};
+use tokio::sync::Semaphore;
+
+use crate::{config::Config, services::{JobStore, ResultCache}};
+
+/// A config keeping downloads and temp files in `dir`, with one decode slot and neither
+/// a result cache nor job history; tests override the fields they exercise
+pub fn test_config(dir: &Path) -> Config {
+    Config {
+        downloads_path: dir.to_string_lossy().to_string(),
+        temp_dir: dir.to_string_lossy().to_string(),
+        bind_address: "127.0.0.1:0".to_string(),
+        max_total_args: 1000,
+        decode_slots: Arc::new(Semaphore::new(1)),
+        result_cache: Arc::new(ResultCache::new(0)),
+        decode_jobs: Arc::new(JobStore::new(0)),
+        level_colors: Default::default(),
+        refresh_timeout: Duration::from_secs(60),
+        max_script_output: 4096,
+    }
+}
+
+/// A multipart upload with one part per `(field name, file name, contents)`
+pub async fn multipart_request(parts: Vec<(&str, &str, Vec<u8>)>) -> Multipart {
+    let boundary = "XBOUNDARY";
+    let mut body = Vec::new();
+    for (name, filename, content) in parts {
+        body.extend_from_slice(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+            boundary, name, filename).as_bytes());
+        body.extend_from_slice(&content);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    let request = Request::builder()
+        .method("POST")
+        .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(body))
+        .unwrap();
+    Multipart::from_request(request, &()).await.unwrap()
+}
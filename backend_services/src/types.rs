@@ -1,29 +1,109 @@
 use std::path::PathBuf;
+use syslog_decoder::DEFAULT_LOG_LEVEL;
+
+use crate::parser::session_parser::DEFAULT_SESSION_PAGE_SIZE;
 
 #[derive(serde::Deserialize)]
 pub struct DecoderQuery {
     pub version: String,
+    /// Defaults to the library's `DEFAULT_LOG_LEVEL`, like the frontend level picker
+    #[serde(default = "default_log_level_param")]
     pub log_level: String,
     #[serde(default)]
     pub include_log_level: bool,
     #[serde(default)]
     pub use_custom_decoder: bool,
+    /// Wrap the response as `{"sessions": [...], "summary": "..."}` with the decode's summary line
+    #[serde(default)]
+    pub include_summary: bool,
+    /// Wrap the response as `{"sessions": [...], "warnings": [...]}`
+    #[serde(default)]
+    pub include_warnings: bool,
+    /// Wrap the response as `{"sessions": [...], "level_colors": {...}}` with the configured colors
+    #[serde(default)]
+    pub include_level_colors: bool,
+    /// Stream sessions as NDJSON (`application/x-ndjson`, one session object per line, then
+    /// `{"summary": "..."}` when a summary was asked for) while decoding; uploads under `STREAM_MIN_UPLOAD_SIZE` still get the single JSON response
+    #[serde(default)]
+    pub stream: bool,
+    /// Return only this page of sessions, counting from 1 (default 1 when `page_size` is set)
+    #[serde(default)]
+    pub page: Option<usize>,
+    /// Sessions per page (default `DEFAULT_SESSION_PAGE_SIZE` when `page` is set); when either
+    /// is set the response is wrapped as `{"sessions": [...], "total_sessions": N,
+    /// "has_more": bool, "page": P, "page_size": S}` and never streamed
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+impl DecoderQuery {
+    /// The page number and size asked for, if the response should be paged
+    pub fn requested_page(&self) -> Option<(usize, usize)> {
+        if self.page.is_none() && self.page_size.is_none() {
+            return None;
+        }
+        Some((self.page.unwrap_or(1), self.page_size.unwrap_or(DEFAULT_SESSION_PAGE_SIZE)))
+    }
+}
+
+/// Page of a stored decode's sessions, both defaulting as for `DecoderQuery`
+#[derive(serde::Deserialize)]
+pub struct SessionPageQuery {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+}
+
+#[derive(serde::Deserialize)]
+pub struct PreflightQuery {
+    pub version: String,
+    #[serde(default)]
+    pub use_custom_decoder: bool,
+    /// Records checked from the start of the capture
+    #[serde(default = "default_preflight_records")]
+    pub records: usize,
+}
+
+#[derive(serde::Deserialize)]
+pub struct StreamQuery {
+    /// Most verbose level to stream
+    #[serde(default = "default_log_level")]
+    pub log_level: u8,
+}
+
+fn default_log_level() -> u8 {
+    DEFAULT_LOG_LEVEL
+}
+
+fn default_log_level_param() -> String {
+    DEFAULT_LOG_LEVEL.to_string()
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_page_size() -> usize {
+    DEFAULT_SESSION_PAGE_SIZE
+}
+
+fn default_preflight_records() -> usize {
+    1000
 }
 
 pub struct UploadedFiles {
+    /// Filename the client sent the capture under
+    pub original_name: String,
     pub binary_file: PathBuf,
     pub custom_decoder_file: Option<PathBuf>,
 }
 
-#[derive(serde::Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-}
-
-impl ErrorResponse {
-    pub fn new(error: impl Into<String>) -> Self {
-        Self {
-            error: error.into(),
-        }
-    }
+/// Result of a decode: the sessions as JSON plus problems worth showing the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeOutput {
+    pub sessions_json: String,
+    pub warnings: Vec<String>,
+    /// Summary line of the decode, when one was asked for; kept out of the session content
+    pub summary: Option<String>,
 }
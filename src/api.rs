@@ -1,6 +1,6 @@
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen::prelude::*;
-use crate::types::LogSession;
+use crate::types::DecodeResponse;
 
 pub async fn fetch_versions() -> Result<Vec<String>, JsValue> {
     let window = web_sys::window().ok_or("window not available")?;
@@ -15,7 +15,7 @@ pub async fn fetch_versions() -> Result<Vec<String>, JsValue> {
     Ok(versions)
 }
 
-pub async fn decode_log_file_with_options(file: web_sys::File, version: String, log_level: String, _include_log_level: bool, custom_decoder_file: Option<web_sys::File>) -> Result<Vec<LogSession>, JsValue> {
+pub async fn decode_log_file_with_options(file: web_sys::File, version: String, log_level: String, _include_log_level: bool, custom_decoder_file: Option<web_sys::File>) -> Result<DecodeResponse, JsValue> {
     let form = web_sys::FormData::new()?;
     form.append_with_blob("file", &file)?;
     
@@ -24,7 +24,7 @@ pub async fn decode_log_file_with_options(file: web_sys::File, version: String,
     if let Some(custom_file) = custom_decoder_file {
         form.append_with_blob("custom_decoder", &custom_file)?;
     }
-    let url = format!("/api/decode?version={}&log_level={}&include_log_level=true&use_custom_decoder={}", 
+    let url = format!("/api/decode?version={}&log_level={}&include_log_level=true&include_summary=true&include_level_colors=true&use_custom_decoder={}", 
                      version, log_level, use_custom);
     let opts = web_sys::RequestInit::new();
     opts.set_method("POST");
@@ -36,11 +36,11 @@ pub async fn decode_log_file_with_options(file: web_sys::File, version: String,
     let resp: web_sys::Response = resp_value.dyn_into()?;
     let json = JsFuture::from(resp.json()?).await?;
     
-    // Parse the JSON response as sessions
-    let sessions: Vec<LogSession> = serde_wasm_bindgen::from_value(json)
+    // Parse the JSON response as sessions plus level colors
+    let response: DecodeResponse = serde_wasm_bindgen::from_value(json)
         .map_err(|e| JsValue::from_str(&format!("Failed to parse sessions: {}", e)))?;
     
-    Ok(sessions)
+    Ok(response)
 }
 
 pub async fn refresh_azure_files() -> Result<String, JsValue> {
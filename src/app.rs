@@ -1,6 +1,8 @@
 use yew::prelude::*;
 use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::platform::spawn_local;
+use std::collections::HashMap;
+use syslog_decoder::{SyslogParser, DEFAULT_LOG_LEVEL, MAX_NAMED_LOG_LEVEL};
 
 use crate::types::LogSession;
 use crate::api::{fetch_versions, decode_log_file_with_options, refresh_azure_files};
@@ -16,16 +18,18 @@ pub enum ProcessingState {
 
 #[function_component(App)]
 pub fn app(_props: &()) -> Html {
-    let versions = use_state(|| Vec::<String>::new());
-    let selected_version = use_state(|| String::new());
-    let log_level = use_state(|| "4".to_string());
+    let versions = use_state(Vec::<String>::new);
+    let selected_version = use_state(String::new);
+    let log_level = use_state(|| DEFAULT_LOG_LEVEL.to_string());
     let show_log_levels = use_state(|| false);
-    let log_sessions = use_state(|| Vec::<LogSession>::new());
+    let log_sessions = use_state(Vec::<LogSession>::new);
+    let level_colors = use_state(HashMap::<String, String>::new);
+    let decode_summary = use_state(|| None::<String>);
     let file = use_state(|| None);
     let custom_decoder_file = use_state(|| None);
     let use_custom_decoder = use_state(|| false);
     let processing_state = use_state(|| ProcessingState::Idle);
-    let decode_progress_message = use_state(|| String::new()); // For decode operations
+    let decode_progress_message = use_state(String::new); // For decode operations
     let refreshing = use_state(|| false);
 
     // Fetch versions from backend on mount
@@ -36,7 +40,7 @@ pub fn app(_props: &()) -> Html {
             spawn_local(async move {
                 match fetch_versions().await {
                     Ok(v) => {
-                        if let Some(first) = v.get(0) {
+                        if let Some(first) = v.first() {
                             selected_version.set(first.clone());
                         }
                         versions.set(v);
@@ -118,7 +122,7 @@ pub fn app(_props: &()) -> Html {
                         // Refresh the versions list after successful Azure refresh
                         match fetch_versions().await {
                             Ok(v) => {
-                                if let Some(first) = v.get(0) {
+                                if let Some(first) = v.first() {
                                     selected_version.set(first.clone());
                                 }
                                 versions.set(v);
@@ -145,6 +149,8 @@ pub fn app(_props: &()) -> Html {
         let custom_decoder_file = custom_decoder_file.clone();
         let use_custom_decoder = use_custom_decoder.clone();
         let log_sessions = log_sessions.clone();
+        let level_colors = level_colors.clone();
+        let decode_summary = decode_summary.clone();
         let processing_state = processing_state.clone();
         let decode_progress_message = decode_progress_message.clone();
         Callback::from(move |_| {
@@ -154,6 +160,8 @@ pub fn app(_props: &()) -> Html {
             let custom_decoder_file_opt = (*custom_decoder_file).clone();
             let use_custom = *use_custom_decoder;
             let log_sessions = log_sessions.clone();
+            let level_colors = level_colors.clone();
+            let decode_summary = decode_summary.clone();
             let processing_state = processing_state.clone();
             let decode_progress_message = decode_progress_message.clone();
             
@@ -183,7 +191,10 @@ pub fn app(_props: &()) -> Html {
                     
                     let custom_decoder = if use_custom { custom_decoder_file_opt } else { None };
                     match decode_log_file_with_options(file, version, log_level, false, custom_decoder).await {
-                        Ok(sessions) => {
+                        Ok(response) => {
+                            let sessions = response.sessions;
+                            level_colors.set(response.level_colors);
+                            decode_summary.set(response.summary);
                             decode_progress_message.set("Processing completed successfully!".to_string());
                             
                             if sessions.is_empty() {
@@ -193,6 +204,7 @@ pub fn app(_props: &()) -> Html {
                                     id: 0,
                                     content: "No sessions found. The file may be invalid, corrupted, or the log level filter may be too restrictive.".to_string(),
                                     timestamp: None,
+                                    timestamp_display: None,
                                 }]);
                             } else {
                                 log_sessions.set(sessions.clone());
@@ -205,10 +217,12 @@ pub fn app(_props: &()) -> Html {
                             web_sys::console::log_1(&error_msg.clone().into());
                             processing_state.set(ProcessingState::Error(error_msg.clone()));
                             decode_progress_message.set(error_msg);
+                            decode_summary.set(None);
                             log_sessions.set(vec![LogSession {
                                 id: 0,
                                 content: format!("Error: {:?}", e),
                                 timestamp: None,
+                                timestamp_display: None,
                             }]);
                         }
                     }
@@ -255,12 +269,11 @@ pub fn app(_props: &()) -> Html {
                 <div style="display:flex; flex-direction:column; gap:0.5em;">
                     <label style="font-weight:bold; color:#555;">{ "Log Level:" }</label>
                     <select onchange={on_log_level_change} style="width:100%; padding:0.5em; border:1px solid #ccc; border-radius:4px;" value={(*log_level).clone()}>
-                        <option value="0">{ "0 - Critical" }</option>
-                        <option value="1">{ "1 - Error" }</option>
-                        <option value="2">{ "2 - Warning" }</option>
-                        <option value="3">{ "3 - Info" }</option>
-                        <option value="4" selected=true>{ "4 - Debug" }</option>
-                        <option value="5">{ "5 - Verbose" }</option>
+                        { for (0..=MAX_NAMED_LOG_LEVEL).map(|level| html! {
+                            <option value={level.to_string()} selected={level == DEFAULT_LOG_LEVEL}>
+                                { format!("{} - {}", level, SyslogParser::log_level_to_string(level)) }
+                            </option>
+                        }) }
                     </select>
                 </div>
                 
@@ -382,7 +395,7 @@ pub fn app(_props: &()) -> Html {
                 }}
             </div>
             <div style="flex:1; display:flex; flex-direction:column; padding:1em; gap:1em; overflow-y:auto;">
-                <EnhancedSessionView sessions={(*log_sessions).clone()} show_log_levels={*show_log_levels} />
+                <EnhancedSessionView sessions={(*log_sessions).clone()} show_log_levels={*show_log_levels} level_colors={(*level_colors).clone()} summary={(*decode_summary).clone()} />
             </div>
         </div>
     }
@@ -1,36 +1,26 @@
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, Element, HtmlAnchorElement, HtmlDocument, HtmlInputElement, HtmlTextAreaElement, Url};
+use yew::platform::{spawn_local, time::sleep};
+use std::time::Duration;
 use yew::prelude::*;
 use crate::types::LogSession;
-use std::collections::HashSet;
-
-fn format_epoch_to_readable(timestamp_str: &str) -> String {
-    // Remove "Epoch: " prefix if present
-    let clean_timestamp = if timestamp_str.starts_with("Epoch: ") {
-        &timestamp_str[7..] // Remove "Epoch: " (7 characters)
-    } else {
-        timestamp_str
-    };
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use syslog_decoder::{session::{SessionTimestamp, TimestampFormat}, SUMMARY_LINE_PREFIX};
+
+fn format_epoch_to_readable(timestamp: &SessionTimestamp) -> String {
+    // Use JavaScript Date for formatting (GMT adjusted)
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from(timestamp.epoch_ms() as f64));
     
-    // Try to parse the timestamp as epoch seconds
-    if let Ok(epoch_secs) = clean_timestamp.parse::<i64>() {
-        // Convert epoch seconds to JavaScript Date
-        let epoch_ms = epoch_secs * 1000; // Convert to milliseconds
-        
-        // Use JavaScript Date for formatting (GMT adjusted)
-        let date = js_sys::Date::new(&wasm_bindgen::JsValue::from(epoch_ms as f64));
-        
-        let day = date.get_utc_date();
-        let month = date.get_utc_month() + 1; // JavaScript months are 0-based
-        let year = date.get_utc_full_year() % 100; // Get last 2 digits of year
-        let hours = date.get_utc_hours();
-        let minutes = date.get_utc_minutes();
-        let seconds = date.get_utc_seconds();
-        
-        format!("Date: {:02}/{:02}/{:02} Time: {:02}:{:02}:{:02}", 
-               day, month, year, hours, minutes, seconds)
-    } else {
-        // If parsing fails, return the original timestamp
-        timestamp_str.to_string()
-    }
+    let day = date.get_utc_date();
+    let month = date.get_utc_month() + 1; // JavaScript months are 0-based
+    let year = date.get_utc_full_year() % 100; // Get last 2 digits of year
+    let hours = date.get_utc_hours();
+    let minutes = date.get_utc_minutes();
+    let seconds = date.get_utc_seconds();
+    
+    format!("Date: {:02}/{:02}/{:02} Time: {:02}:{:02}:{:02}", 
+           day, month, year, hours, minutes, seconds)
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -39,7 +29,19 @@ pub struct LogLevel {
     pub color: String,
 }
 
+/// Level names the decoder emits, recognized even without a configured color
+const KNOWN_LEVELS: [&str; 7] = ["CRITICAL", "FATALERROR", "ERROR", "WARNING", "INFO", "DEBUG", "VERBOSE"];
+
 impl LogLevel {
+    /// Level with the color configured for it on the backend, falling back to the built-in palette
+    pub fn with_colors(s: &str, level_colors: &HashMap<String, String>) -> Self {
+        let mut level = Self::from_string(s);
+        if let Some(color) = find_level_color(s, level_colors) {
+            level.color = color.clone();
+        }
+        level
+    }
+
     pub fn from_string(s: &str) -> Self {
         match s.to_uppercase().as_str() {
             "CRITICAL" => LogLevel { name: "Critical".to_string(), color: "#dc3545".to_string() },
@@ -54,16 +56,27 @@ impl LogLevel {
     }
 }
 
-fn parse_log_levels_from_content(content: &str) -> Vec<LogLevel> {
+/// Whether a bracketed field holds a level name: a built-in one or one configured on the backend
+fn is_level_name(field: &str, level_colors: &HashMap<String, String>) -> bool {
+    KNOWN_LEVELS.contains(&field.to_uppercase().as_str()) || find_level_color(field, level_colors).is_some()
+}
+
+/// Level names are matched case-insensitively, like the built-in ones
+fn find_level_color<'a>(level: &str, level_colors: &'a HashMap<String, String>) -> Option<&'a String> {
+    level_colors.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(level))
+        .map(|(_, color)| color)
+}
+
+fn parse_log_levels_from_content(content: &str, level_colors: &HashMap<String, String>) -> Vec<LogLevel> {
     let mut levels = HashSet::new();
     for line in content.lines() {
         if let Some(start) = line.find('[') {
             if let Some(end) = line[start..].find(']') {
                 let level_part = &line[start+1..start+end];
-                // Check if this looks like a log level
-                if ["CRITICAL", "FATALERROR", "ERROR", "WARNING", "INFO", "DEBUG", "VERBOSE"]
-                    .contains(&level_part.to_uppercase().as_str()) {
-                    levels.insert(LogLevel::from_string(level_part));
+                // Check if this looks like a log level (built-in or one with a configured color)
+                if is_level_name(level_part, level_colors) {
+                    levels.insert(LogLevel::with_colors(level_part, level_colors));
                 }
             }
         }
@@ -71,7 +84,7 @@ fn parse_log_levels_from_content(content: &str) -> Vec<LogLevel> {
     levels.into_iter().collect()
 }
 
-fn filter_content_by_log_levels(content: &str, enabled_levels: &HashSet<String>, show_log_levels: bool) -> String {
+fn filter_content_by_log_levels(content: &str, enabled_levels: &HashSet<String>, show_log_levels: bool, level_colors: &HashMap<String, String>) -> String {
     content.lines()
         .filter(|line| {
             if enabled_levels.is_empty() {
@@ -94,8 +107,7 @@ fn filter_content_by_log_levels(content: &str, enabled_levels: &HashSet<String>,
                 if let Some(start) = line.find('[') {
                     if let Some(end) = line[start..].find(']') {
                         let level_part = &line[start+1..start+end];
-                        if ["CRITICAL", "FATALERROR", "ERROR", "WARNING", "INFO", "DEBUG", "VERBOSE"]
-                            .contains(&level_part.to_uppercase().as_str()) {
+                        if is_level_name(level_part, level_colors) {
                             let before = &line[..start];
                             let after = &line[start+end+1..];
                             return format!("{}{}", before, after).trim().to_string();
@@ -109,10 +121,153 @@ fn filter_content_by_log_levels(content: &str, enabled_levels: &HashSet<String>,
         .join("\n")
 }
 
+/// Keep the lines containing `query`, ignoring case; an empty query keeps every line
+fn filter_content_by_search(content: &str, query: &str) -> String {
+    let query = query.trim().to_lowercase();
+    content.lines()
+        .filter(|line| query.is_empty() || line.to_lowercase().contains(&query))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Height of one rendered line in the session details view, in pixels
+const LINE_HEIGHT_PX: f64 = 18.0;
+/// Lines mounted above and below the visible ones so fast scrolling does not show gaps
+const OVERSCAN_LINES: usize = 30;
+/// Assumed height of the session details viewport until its first scroll event
+const DEFAULT_VIEWPORT_HEIGHT_PX: f64 = 1200.0;
+
+/// Filtered session text split into lines once, so the windowed view can index into it
+struct SessionLines {
+    text: String,
+    lines: Vec<Range<usize>>,
+}
+
+impl SessionLines {
+    fn new(text: String) -> Self {
+        let start_of = |line: &str| line.as_ptr() as usize - text.as_ptr() as usize;
+        let lines = text.lines().map(|line| start_of(line)..start_of(line) + line.len()).collect();
+        Self { text, lines }
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn line(&self, index: usize) -> &str {
+        &self.text[self.lines[index].clone()]
+    }
+}
+
+/// Lines to mount for a viewport scrolled to `scroll_top`, including the overscan
+fn visible_line_range(scroll_top: f64, viewport_height: f64, total_lines: usize) -> Range<usize> {
+    let visible_lines = (viewport_height.max(0.0) / LINE_HEIGHT_PX).ceil() as usize + 1;
+    // The browser clamps the scroll position when filtering shortens the content
+    let first_visible = ((scroll_top.max(0.0) / LINE_HEIGHT_PX) as usize).min(total_lines.saturating_sub(visible_lines));
+    let start = first_visible.saturating_sub(OVERSCAN_LINES).min(total_lines);
+    let end = (first_visible + visible_lines + OVERSCAN_LINES).min(total_lines);
+    start..end
+}
+
+/// File name for a downloaded session, e.g. `session_2_1756474625.log`
+fn session_download_name(index: usize, session: &LogSession) -> String {
+    match &session.timestamp {
+        Some(timestamp) => format!("session_{}_{}.log", index, timestamp.epoch_seconds()),
+        None => format!("session_{}.log", index),
+    }
+}
+
+/// All sessions in one text file, each under a header with its number and time
+fn export_all_sessions(sessions: &[LogSession], show_log_levels: bool, level_colors: &HashMap<String, String>) -> String {
+    sessions.iter()
+        .enumerate()
+        .map(|(index, session)| {
+            let header = match &session.timestamp {
+                Some(timestamp) => format!("=== Session {} - {} ===", index + 1, timestamp.format(TimestampFormat::Iso8601)),
+                None => format!("=== Session {} ===", index + 1),
+            };
+            format!("{}\n{}\n", header, filter_content_by_log_levels(&session.content, &HashSet::new(), show_log_levels, level_colors))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Have the browser save `content` as a text file through a temporary object URL
+fn download_text(filename: &str, content: &str) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type("text/plain;charset=utf-8");
+    let blob = Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&JsValue::from_str(content)), &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document to download from"))?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    // The browser may still be reading the blob after `click` returns, so the URL is released later
+    spawn_local(async move {
+        sleep(Duration::from_secs(60)).await;
+        let _ = Url::revoke_object_url(&url);
+    });
+    Ok(())
+}
+
+/// Download handler that logs failures to the console; the text is only built on click
+fn on_download(filename: String, content: impl Fn() -> String + 'static) -> Callback<MouseEvent> {
+    Callback::from(move |event: MouseEvent| {
+        // Cards open the session on click; downloading should not
+        event.stop_propagation();
+        if let Err(e) = download_text(&filename, &content()) {
+            web_sys::console::log_1(&format!("Download failed: {:?}", e).into());
+        }
+    })
+}
+
+/// Start writing `text` to the clipboard, or `None` when the Clipboard API is unavailable
+/// (e.g. outside a secure context)
+fn clipboard_write_text(text: &str) -> Option<js_sys::Promise> {
+    let navigator = web_sys::window()?.navigator();
+    let clipboard = js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard")).ok().filter(JsValue::is_object)?;
+    let write_text = js_sys::Reflect::get(&clipboard, &JsValue::from_str("writeText")).ok()?.dyn_into::<js_sys::Function>().ok()?;
+    write_text.call1(&clipboard, &JsValue::from_str(text)).ok()?.dyn_into::<js_sys::Promise>().ok()
+}
+
+/// Copy `text` through a temporary textarea and `execCommand`, for browsers without the Clipboard API
+fn copy_with_selection(text: &str) -> Result<bool, JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document"))?;
+    let body = document.body().ok_or_else(|| JsValue::from_str("No document body"))?;
+    let textarea: HtmlTextAreaElement = document.create_element("textarea")?.dyn_into()?;
+    textarea.set_value(text);
+    textarea.set_attribute("readonly", "")?;
+    textarea.set_attribute("style", "position: fixed; top: 0; left: 0; opacity: 0;")?;
+    body.append_child(&textarea)?;
+    textarea.select();
+    let copied = document.dyn_ref::<HtmlDocument>().map(|document| document.exec_command("copy"));
+    textarea.remove();
+    Ok(copied.transpose()?.unwrap_or(false))
+}
+
+/// Footer text for the decode's summary line, e.g. `Summary: 3 entries, 0ms to 200ms`
+fn format_decode_summary(summary_line: &str) -> String {
+    let summary = summary_line.strip_prefix(SUMMARY_LINE_PREFIX).unwrap_or(summary_line);
+    format!("Summary: {}", summary.trim_end_matches("===").trim())
+}
+
 #[derive(Properties, PartialEq)]
 pub struct EnhancedSessionViewProps {
     pub sessions: Vec<LogSession>,
     pub show_log_levels: bool,
+    /// Colors for level names sent by the backend; unset levels use the built-in palette
+    #[prop_or_default]
+    pub level_colors: HashMap<String, String>,
+    /// Summary line the backend returned beside the sessions, shown as a footer
+    #[prop_or_default]
+    pub summary: Option<String>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -126,7 +281,35 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
     let sessions = &props.sessions;
     let show_log_levels = props.show_log_levels;
     let selected_session = use_state(|| None::<LogSession>);
-    let enabled_log_levels = use_state(|| HashSet::<String>::new());
+    let enabled_log_levels = use_state(HashSet::<String>::new);
+    let search_query = use_state(String::new);
+    let copied = use_state(|| false);
+    let scroll_top = use_state(|| 0.0);
+    let viewport_height = use_state(|| DEFAULT_VIEWPORT_HEIGHT_PX);
+
+    // Levels and filtered lines of the open session, recomputed only when the session or
+    // filters change rather than on every scroll
+    let open_session_key = selected_session.as_ref().map(|session| (session.id, session.content.len()));
+    let available_levels = {
+        let selected_session = selected_session.clone();
+        use_memo((open_session_key, props.level_colors.clone()), move |(_, level_colors)| {
+            selected_session.as_ref()
+                .map(|session| parse_log_levels_from_content(&session.content, level_colors))
+                .unwrap_or_default()
+        })
+    };
+    let session_lines = {
+        let selected_session = selected_session.clone();
+        use_memo(
+            (open_session_key, (*enabled_log_levels).clone(), (*search_query).clone(), show_log_levels, props.level_colors.clone()),
+            move |(_, enabled_log_levels, search_query, show_log_levels, level_colors)| {
+                let content = selected_session.as_ref().map(|session| session.content.as_str()).unwrap_or_default();
+                // Search within the lines the level filter kept
+                let filtered_content = filter_content_by_log_levels(content, enabled_log_levels, *show_log_levels, level_colors);
+                SessionLines::new(filter_content_by_search(&filtered_content, search_query))
+            },
+        )
+    };
     
     if sessions.is_empty() {
         return html! {
@@ -148,7 +331,7 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
         }
     }
     
-    let categories = vec![
+    let categories = [
         SessionCategory {
             name: "Boot Cycles with Timestamp".to_string(),
             sessions: sessions_with_timestamp,
@@ -162,9 +345,15 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
     let on_session_click = {
         let selected_session = selected_session.clone();
         let enabled_log_levels = enabled_log_levels.clone();
+        let search_query = search_query.clone();
+        let copied = copied.clone();
+        let scroll_top = scroll_top.clone();
         Callback::from(move |session: LogSession| {
-            // Reset log level filter when opening a new session
+            // Reset log level filter, search, copy confirmation and scroll position when opening a new session
             enabled_log_levels.set(HashSet::new());
+            search_query.set(String::new());
+            copied.set(false);
+            scroll_top.set(0.0);
             selected_session.set(Some(session));
         })
     };
@@ -176,8 +365,30 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
         })
     };
 
+    let download_button_style = "
+        background: #4a5568; 
+        color: white; 
+        border: none; 
+        padding: 0.25em 0.75em; 
+        border-radius: 4px; 
+        cursor: pointer; 
+        font-size: 0.8em;
+    ";
+
     html! {
         <>
+            <div style="display: flex; justify-content: flex-end; margin-bottom: 1em;">
+                <button
+                    onclick={{
+                        let sessions = sessions.clone();
+                        let level_colors = props.level_colors.clone();
+                        on_download("decoded_sessions.log".to_string(), move || export_all_sessions(&sessions, show_log_levels, &level_colors))
+                    }}
+                    style={download_button_style}
+                >
+                    { "Export all" }
+                </button>
+            </div>
             <div style="display: grid; grid-template-columns: 1fr 1fr; gap: 2em; height: 100%;">
                 { for categories.iter().filter(|cat| !cat.sessions.is_empty()).map(|category| {
                     html! {
@@ -206,7 +417,7 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                                     };
                                     
                                     let preview_lines: Vec<&str> = session.content.lines().take(3).collect();
-                                    let preview_text = if preview_lines.len() > 0 {
+                                    let preview_text = if !preview_lines.is_empty() {
                                         let preview = preview_lines.join("\n");
                                         if session.content.lines().count() > 3 {
                                             format!("{}...", preview)
@@ -248,11 +459,23 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                                                 { if show_log_levels {
                                                     preview_text
                                                 } else {
-                                                    filter_content_by_log_levels(&preview_text, &HashSet::new(), false)
+                                                    filter_content_by_log_levels(&preview_text, &HashSet::new(), false, &props.level_colors)
                                                 }}
                                             </div>
-                                            <div style="margin-top: 0.5em; font-size: 0.75em; color: #888;">
+                                            <div style="margin-top: 0.5em; font-size: 0.75em; color: #888; display: flex; justify-content: space-between; align-items: center;">
                                                 { format!("{} lines", session.content.lines().count()) }
+                                                <button
+                                                    onclick={{
+                                                        let session = session_clone.clone();
+                                                        let level_colors = props.level_colors.clone();
+                                                        on_download(session_download_name(index + 1, &session), move || {
+                                                            filter_content_by_log_levels(&session.content, &HashSet::new(), show_log_levels, &level_colors)
+                                                        })
+                                                    }}
+                                                    style={download_button_style}
+                                                >
+                                                    { "Download" }
+                                                </button>
                                             </div>
                                         </div>
                                     }
@@ -263,6 +486,16 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                 }) }
             </div>
 
+            { if let Some(summary) = &props.summary {
+                html! {
+                    <div style="margin-top: 1em; padding: 0.5em 1em; border-top: 1px solid #ddd; color: #555; font-size: 0.9em; font-family: 'Courier New', monospace;">
+                        { format_decode_summary(summary) }
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+
             { if let Some(ref session) = *selected_session {
                 let session_title = if let Some(ref timestamp) = session.timestamp {
                     format!("Session Details - {}", format_epoch_to_readable(timestamp))
@@ -270,15 +503,52 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                     "Session Details".to_string()
                 };
 
-                // Get all available log levels from this session
-                let available_levels = parse_log_levels_from_content(&session.content);
-                
-                // Apply log level filtering and display preferences
-                let filtered_content = filter_content_by_log_levels(
-                    &session.content, 
-                    &*enabled_log_levels, 
-                    show_log_levels
-                );
+                let search_matches = if search_query.trim().is_empty() {
+                    None
+                } else {
+                    Some(session_lines.len())
+                };
+                let visible_lines = visible_line_range(*scroll_top, *viewport_height, session_lines.len());
+
+                let on_search_input = {
+                    let search_query = search_query.clone();
+                    Callback::from(move |event: InputEvent| {
+                        let target = event.target_unchecked_into::<HtmlInputElement>();
+                        search_query.set(target.value());
+                    })
+                };
+
+                let on_content_scroll = {
+                    let scroll_top = scroll_top.clone();
+                    let viewport_height = viewport_height.clone();
+                    Callback::from(move |event: Event| {
+                        let target = event.target_unchecked_into::<Element>();
+                        scroll_top.set(target.scroll_top() as f64);
+                        viewport_height.set(target.client_height() as f64);
+                    })
+                };
+
+                // Copy what the filters left, through a temporary textarea without the Clipboard API
+                let on_copy = {
+                    let copied = copied.clone();
+                    let session_lines = session_lines.clone();
+                    Callback::from(move |_: MouseEvent| {
+                        let session_lines = session_lines.clone();
+                        let copied = copied.clone();
+                        spawn_local(async move {
+                            let written = match clipboard_write_text(&session_lines.text) {
+                                Some(promise) => wasm_bindgen_futures::JsFuture::from(promise).await.is_ok(),
+                                None => false,
+                            };
+                            if !written && !copy_with_selection(&session_lines.text).unwrap_or(false) {
+                                return;
+                            }
+                            copied.set(true);
+                            sleep(Duration::from_secs(2)).await;
+                            copied.set(false);
+                        });
+                    })
+                };
 
                 html! {
                     <div 
@@ -318,26 +588,31 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                                 align-items: center;
                             ">
                                 <h3 style="margin: 0; font-size: 1.2em;">{ session_title }</h3>
-                                <button 
-                                    onclick={on_modal_close.clone()}
-                                    style="
-                                        background: none; 
-                                        border: none; 
-                                        color: white; 
-                                        font-size: 1.5em; 
-                                        cursor: pointer; 
-                                        padding: 0; 
-                                        width: 2em; 
-                                        height: 2em; 
-                                        display: flex; 
-                                        align-items: center; 
-                                        justify-content: center;
-                                        border-radius: 4px;
-                                    "
-                                    class="close-button"
-                                >
-                                    { "×" }
-                                </button>
+                                <div style="display: flex; gap: 0.75em; align-items: center;">
+                                    <button onclick={on_copy} style={download_button_style}>
+                                        { if *copied { "Copied!" } else { "Copy" } }
+                                    </button>
+                                    <button 
+                                        onclick={on_modal_close.clone()}
+                                        style="
+                                            background: none; 
+                                            border: none; 
+                                            color: white; 
+                                            font-size: 1.5em; 
+                                            cursor: pointer; 
+                                            padding: 0; 
+                                            width: 2em; 
+                                            height: 2em; 
+                                            display: flex; 
+                                            align-items: center; 
+                                            justify-content: center;
+                                            border-radius: 4px;
+                                        "
+                                        class="close-button"
+                                    >
+                                        { "×" }
+                                    </button>
+                                </div>
                             </div>
                             
                             { if !available_levels.is_empty() {
@@ -417,22 +692,61 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
                                 html! {}
                             }}
 
-                            <div style="flex: 1; overflow: hidden; display: flex; flex-direction: column;">
-                                <textarea 
-                                    readonly=true
-                                    value={filtered_content}
+                            <div style="
+                                border-bottom: 1px solid #dee2e6; 
+                                padding: 0.75em 1.5em;
+                                display: flex;
+                                gap: 1em;
+                                align-items: center;
+                            ">
+                                <input
+                                    type="search"
+                                    placeholder="Search this session"
+                                    value={(*search_query).clone()}
+                                    oninput={on_search_input}
                                     style="
                                         flex: 1; 
-                                        font-family: 'Courier New', monospace; 
-                                        font-size: 0.9em; 
-                                        padding: 1.5em; 
-                                        border: none; 
-                                        outline: none; 
-                                        resize: none; 
-                                        line-height: 1.4; 
-                                        background: #f8f9fa;
+                                        padding: 0.4em 0.75em; 
+                                        border: 1px solid #ced4da; 
+                                        border-radius: 4px; 
+                                        font-size: 0.9em;
                                     "
                                 />
+                                { if let Some(matches) = search_matches {
+                                    html! {
+                                        <span style="color: #495057; font-size: 0.9em; white-space: nowrap;">
+                                            { format!("{} matching line{}", matches, if matches != 1 { "s" } else { "" }) }
+                                        </span>
+                                    }
+                                } else {
+                                    html! {}
+                                }}
+                            </div>
+
+                            <div style="flex: 1; overflow: hidden; display: flex; flex-direction: column;">
+                                <div
+                                    onscroll={on_content_scroll}
+                                    style={format!("
+                                        flex: 1; 
+                                        overflow: auto; 
+                                        font-family: 'Courier New', monospace; 
+                                        font-size: 13px; 
+                                        line-height: {LINE_HEIGHT_PX}px; 
+                                        padding: 0 1.5em; 
+                                        background: #f8f9fa;
+                                    ")}
+                                >
+                                    // Only the visible slice is mounted, positioned inside a spacer as tall as every line
+                                    <div style={format!("position: relative; height: {}px;", session_lines.len() as f64 * LINE_HEIGHT_PX)}>
+                                        <div style={format!("position: absolute; top: {}px; left: 0;", visible_lines.start as f64 * LINE_HEIGHT_PX)}>
+                                            { for visible_lines.map(|index| html! {
+                                                <div key={index} style={format!("height: {LINE_HEIGHT_PX}px; white-space: pre;")}>
+                                                    { session_lines.line(index) }
+                                                </div>
+                                            }) }
+                                        </div>
+                                    </div>
+                                </div>
                             </div>
                         </div>
                     </div>
@@ -443,3 +757,79 @@ pub fn enhanced_session_view(props: &EnhancedSessionViewProps) -> Html {
         </>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_colors_are_applied() {
+        let content = "100ms\t[Error]\t[NET]\tLink down\n200ms\t[Trace]\t[NET]\tRetry\n300ms\t[Info]\t[NET]\tLink up";
+        let level_colors = HashMap::from([
+            ("error".to_string(), "#ff0000".to_string()),
+            ("Trace".to_string(), "#00ffff".to_string()),
+        ]);
+
+        let mut levels = parse_log_levels_from_content(content, &level_colors);
+        levels.sort_by(|a, b| a.name.cmp(&b.name));
+        let levels: Vec<(&str, &str)> = levels.iter().map(|level| (level.name.as_str(), level.color.as_str())).collect();
+        // Configured colors win, custom level names are recognized, others keep the default palette
+        assert_eq!(levels, [("Error", "#ff0000"), ("Info", "#198754"), ("Trace", "#00ffff")]);
+
+        assert!(parse_log_levels_from_content(content, &HashMap::new()).iter().all(|level| level.name != "Trace"));
+
+        // Hiding levels strips configured names as well as built-in ones
+        let hidden = filter_content_by_log_levels(content, &HashSet::new(), false, &level_colors);
+        assert!(!hidden.contains("[Trace]") && !hidden.contains("[Error]"));
+        assert!(hidden.contains("Retry"));
+    }
+
+    #[test]
+    fn test_session_downloads() {
+        let sessions = [
+            LogSession {
+                id: 0,
+                content: "100ms\t[Error]\t[NET]\tLink down".to_string(),
+                timestamp: Some(SessionTimestamp::Epoch { seconds: 1756474625 }),
+                timestamp_display: Some("Epoch: 1756474625".to_string()),
+            },
+            LogSession { id: 1, content: "200ms\t[Info]\t[APP]\tStarted".to_string(), timestamp: None, timestamp_display: None },
+        ];
+        assert_eq!(session_download_name(1, &sessions[0]), "session_1_1756474625.log");
+        assert_eq!(session_download_name(2, &sessions[1]), "session_2.log");
+
+        // Levels are left out of the export when they are hidden on screen
+        assert_eq!(export_all_sessions(&sessions, false, &HashMap::new()),
+                   "=== Session 1 - 2025-08-29T13:37:05.000Z ===\n100ms\t\t[NET]\tLink down\n\n=== Session 2 ===\n200ms\t\t[APP]\tStarted\n");
+        assert!(export_all_sessions(&sessions, true, &HashMap::new()).contains("100ms\t[Error]\t[NET]\tLink down"));
+    }
+
+    #[test]
+    fn test_search_combines_with_level_filter() {
+        let content = "100ms\t[Error]\t[NET]\tLink down\n200ms\t[Info]\t[NET]\tLink up\n300ms\t[Error]\t[APP]\tCrash";
+
+        assert_eq!(filter_content_by_search(content, "  "), content);
+        assert_eq!(filter_content_by_search(content, "LINK"), "100ms\t[Error]\t[NET]\tLink down\n200ms\t[Info]\t[NET]\tLink up");
+
+        // Only lines kept by both the level filter and the search are shown
+        let errors = filter_content_by_log_levels(content, &HashSet::from(["ERROR".to_string()]), true, &HashMap::new());
+        assert_eq!(filter_content_by_search(&errors, "link"), "100ms\t[Error]\t[NET]\tLink down");
+        assert_eq!(filter_content_by_search(&errors, "missing"), "");
+    }
+
+    #[test]
+    fn test_windowed_session_lines() {
+        let lines = SessionLines::new("first\nsecond\r\n\nlast".to_string());
+        assert_eq!((0..lines.len()).map(|index| lines.line(index)).collect::<Vec<_>>(), ["first", "second", "", "last"]);
+
+        // 200k lines mount only the viewport plus the overscan on each side
+        let total_lines = 200_000;
+        assert_eq!(visible_line_range(0.0, 180.0, total_lines), 0..11 + OVERSCAN_LINES);
+        let range = visible_line_range(100_000.0 * LINE_HEIGHT_PX, 180.0, total_lines);
+        assert_eq!(range, 100_000 - OVERSCAN_LINES..100_011 + OVERSCAN_LINES);
+
+        // A stale scroll position past the end still shows the last lines
+        assert_eq!(visible_line_range(1e9, 180.0, 50), 50 - 11 - OVERSCAN_LINES..50);
+        assert_eq!(visible_line_range(0.0, 180.0, 0), 0..0);
+    }
+}
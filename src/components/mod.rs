@@ -1,4 +1,3 @@
-pub mod session_view;
 pub mod enhanced_session_view;
 
 pub use enhanced_session_view::EnhancedSessionView;
@@ -20,3 +20,13 @@ pub fn run_app() {
 pub fn format_timestamp_ms_test(timestamp_ms: u64) -> String {
     parser::format_timestamp_ms(timestamp_ms)
 }
+
+// Export session parsing so sessions can be re-split client-side with the backend's algorithm
+#[wasm_bindgen]
+pub fn parse_log_sessions(log_content: &str) -> Result<JsValue, JsValue> {
+    // The browser has no console for the splitter's status lines
+    let options = syslog_decoder::session::SessionOptions { quiet: true, ..Default::default() };
+    let sessions = syslog_decoder::session::parse_log_sessions_with_options(log_content, &options);
+    serde_wasm_bindgen::to_value(&sessions)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize sessions: {}", e)))
+}
@@ -1,8 +1,24 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use syslog_decoder::session::SessionTimestamp;
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct LogSession {
     pub id: usize,
     pub content: String,
-    pub timestamp: Option<String>, // Human-readable timestamp
+    pub timestamp: Option<SessionTimestamp>, // Formatted for display by the session view
+    #[serde(default)]
+    pub timestamp_display: Option<String>, // Backend-formatted copy of `timestamp`
+}
+
+/// Body of `/api/decode` when extra fields are requested alongside the sessions
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct DecodeResponse {
+    pub sessions: Vec<LogSession>,
+    /// CSS color per log level name configured on the backend
+    #[serde(default)]
+    pub level_colors: HashMap<String, String>,
+    /// Summary line of the decode, sent beside the sessions rather than in them
+    #[serde(default)]
+    pub summary: Option<String>,
 }
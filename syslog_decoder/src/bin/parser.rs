@@ -1,45 +1,430 @@
-use syslog_decoder::SyslogParser;
+use syslog_decoder::{FormatOptions, LevelDirection, ParserConfig, SyslogParser};
 use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+/// Most verbose level kept when no log level is given
+const DEFAULT_MAX_LEVEL: u8 = 6;
+
+/// Exit code used when `--fail-on` finds entries at or above the threshold severity
+const FAIL_ON_EXIT_CODE: i32 = 3;
+
+/// Command line arguments for the syslog parser
+struct CliArgs {
+    dict_path: String,
+    binary_path: String,
+    /// Most verbose level to keep
+    log_level: u8,
+    /// Most severe level to keep; entries below it are dropped
+    min_level: u8,
+    include_log_level: bool,
+    include_offset: bool,
+    quiet: bool,
+    include_summary: bool,
+    include_module_counts: bool,
+    field_separator: Option<String>,
+    group_by_module: bool,
+    raw_on_mismatch: bool,
+    resync: bool,
+    rebase_timestamps: bool,
+    include_deltas: bool,
+    /// Emit `{"entries": N, "bytes": M}` progress lines to stderr
+    progress_json: bool,
+    fail_on: Option<u8>,
+    /// Write the decoded logs to this file instead of stdout
+    output: Option<String>,
+}
+
+impl CliArgs {
+    /// Parse positional arguments followed by optional flags
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut include_log_level = false;
+        let mut include_offset = false;
+        let mut quiet = false;
+        let mut include_summary = false;
+        let mut include_module_counts = false;
+        let mut field_separator = None;
+        let mut group_by_module = false;
+        let mut raw_on_mismatch = false;
+        let mut resync = false;
+        let mut rebase_timestamps = false;
+        let mut include_deltas = false;
+        let mut progress_json = false;
+        let mut fail_on = None;
+        let mut min_level = 0;
+        let mut max_level = None;
+        let mut output = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--include-log-level" => include_log_level = true,
+                "--include-offset" => include_offset = true,
+                "--quiet" => quiet = true,
+                "--summary" => include_summary = true,
+                "--module-counts" => include_module_counts = true,
+                "--group-by-module" => group_by_module = true,
+                "--raw-on-mismatch" => raw_on_mismatch = true,
+                "--resync" => resync = true,
+                "--rebase-timestamps" => rebase_timestamps = true,
+                "--deltas" => include_deltas = true,
+                "--separator" => {
+                    let value = iter.next().ok_or("--separator requires a value")?;
+                    field_separator = Some(value.clone());
+                }
+                "--progress" => {
+                    let value = iter.next().ok_or("--progress requires a format")?;
+                    if value != "json" {
+                        return Err(format!("Unsupported --progress format: {} (expected json)", value));
+                    }
+                    progress_json = true;
+                }
+                "--fail-on" => {
+                    let value = iter.next().ok_or("--fail-on requires a log level")?;
+                    fail_on = Some(value.parse().map_err(|_| format!("Invalid --fail-on level: {}", value))?);
+                }
+                "--min-level" => {
+                    let value = iter.next().ok_or("--min-level requires a log level")?;
+                    min_level = value.parse().map_err(|_| format!("Invalid --min-level: {}", value))?;
+                }
+                "--output" | "-o" => {
+                    let value = iter.next().ok_or_else(|| format!("{} requires a path", arg))?;
+                    output = Some(value.clone());
+                }
+                "--max-level" | "-l" => {
+                    let value = iter.next().ok_or_else(|| format!("{} requires a log level", arg))?;
+                    max_level = Some(value.parse().map_err(|_| format!("Invalid {}: {}", arg, value))?);
+                }
+                flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+                value => positional.push(value.to_string()),
+            }
+        }
+
+        // The maximum level is the third positional argument, or given with --max-level
+        let log_level = match (positional.len(), max_level) {
+            (3, None) => positional[2].parse()
+                .map_err(|_| format!("Invalid log level: {}", positional[2]))?,
+            (3, Some(_)) => return Err("Log level given both as an argument and with --max-level".to_string()),
+            (2, max_level) => max_level.unwrap_or(DEFAULT_MAX_LEVEL),
+            _ => return Err("Expected <dictionary.log> <binary.bin> [log_level]".to_string()),
+        };
+        if min_level > log_level {
+            return Err(format!("--min-level {} is above the maximum level {}", min_level, log_level));
+        }
+
+        Ok(Self {
+            dict_path: positional[0].clone(),
+            binary_path: positional[1].clone(),
+            log_level,
+            min_level,
+            include_log_level,
+            include_offset,
+            quiet,
+            include_summary,
+            include_module_counts,
+            field_separator,
+            group_by_module,
+            raw_on_mismatch,
+            resync,
+            rebase_timestamps,
+            include_deltas,
+            progress_json,
+            fail_on,
+            output,
+        })
+    }
+}
+
+/// A dictionary or binary argument, downloaded first when given as an HTTP(S) URL
+enum InputFile<'a> {
+    Local(&'a str),
+    Downloaded(NamedTempFile),
+}
+
+impl<'a> InputFile<'a> {
+    fn resolve(arg: &'a str) -> Result<Self, String> {
+        if !(arg.starts_with("http://") || arg.starts_with("https://")) {
+            return Ok(InputFile::Local(arg));
+        }
+
+        let mut response = reqwest::blocking::get(arg)
+            .map_err(|e| format!("Failed to download {}: {}", arg, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to download {}: HTTP {}", arg, response.status()));
+        }
+
+        let mut temp_file = NamedTempFile::new()
+            .map_err(|e| format!("Failed to create temp file for {}: {}", arg, e))?;
+        response.copy_to(&mut temp_file)
+            .map_err(|e| format!("Failed to download {}: {}", arg, e))?;
+        Ok(InputFile::Downloaded(temp_file))
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            InputFile::Local(path) => Path::new(path),
+            InputFile::Downloaded(temp_file) => temp_file.path(),
+        }
+    }
+}
+
+/// Exit code used by `check` when the capture has format violations
+const NONCONFORMANT_EXIT_CODE: i32 = 4;
+
+/// Print the conformance report of a capture; returns whether it conforms
+fn run_check(args: &[String]) -> Result<bool, Box<dyn std::error::Error>> {
+    let [dict_arg, binary_arg] = args else {
+        return Err("Expected check <dictionary.log> <binary.bin>".into());
+    };
+    let dict_file = InputFile::resolve(dict_arg)?;
+    let binary_file = InputFile::resolve(binary_arg)?;
+
+    let config = ParserConfig {
+        quiet: true,
+        ..Default::default()
+    };
+    let parser = SyslogParser::with_config(dict_file.path(), config)?;
+    let report = parser.check_conformance(binary_file.path())?;
+
+    println!("Records: {}", report.records);
+    println!("Unmapped offsets: {}", report.unmapped_offsets);
+    println!("Argument count mismatches: {}", report.arg_count_mismatches);
+    println!("Timestamp regressions: {}", report.timestamp_regressions);
+    println!("Trailing bytes: {}", report.trailing_bytes);
+    if report.is_conformant() {
+        println!("Capture conforms to the dictionary and record format");
+    } else {
+        println!("Capture has {} format violations", report.violations());
+    }
+    Ok(report.is_conformant())
+}
+
+/// Arguments for the `gen` subcommand
+struct GenArgs {
+    dict_path: String,
+    output_path: String,
+    count: usize,
+    seed: u64,
+}
+
+impl GenArgs {
+    /// Parse `<dictionary.log> <output.bin> [--count N] [--seed S]`
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut count = 1000;
+        let mut seed = 1;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--count" => {
+                    let value = iter.next().ok_or("--count requires a value")?;
+                    count = value.parse().map_err(|_| format!("Invalid record count: {}", value))?;
+                }
+                "--seed" => {
+                    let value = iter.next().ok_or("--seed requires a value")?;
+                    seed = value.parse().map_err(|_| format!("Invalid seed: {}", value))?;
+                }
+                flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+                value => positional.push(value.to_string()),
+            }
+        }
+
+        if positional.len() != 2 {
+            return Err("Expected gen <dictionary.log> <output.bin>".to_string());
+        }
+
+        Ok(Self {
+            dict_path: positional[0].clone(),
+            output_path: positional[1].clone(),
+            count,
+            seed,
+        })
+    }
+}
+
+/// Write a synthetic binary capture for the given dictionary
+fn run_gen(gen_args: GenArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ParserConfig {
+        quiet: true,
+        ..Default::default()
+    };
+    // An empty dictionary is rejected here with DecodeError::EmptyDictionary
+    let parser = SyslogParser::with_config(&gen_args.dict_path, config)?;
+
+    let binary_data = parser.generate_synthetic_binary(gen_args.count, gen_args.seed);
+    std::fs::write(&gen_args.output_path, &binary_data)?;
+
+    println!("Generated {} records ({} bytes) in {}", gen_args.count, binary_data.len(), gen_args.output_path);
+    Ok(())
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <dictionary.log|URL> <binary.bin|URL> [log_level] [--min-level <level>] [--max-level <level>] [--include-log-level] [--include-offset] [--quiet] [--summary] [--module-counts] [--separator <sep>] [--group-by-module] [--raw-on-mismatch] [--resync] [--rebase-timestamps] [--deltas] [--progress json] [--fail-on <level>] [--output <path>]", program);
+    eprintln!("Example: {} Quara_fw_9.17.3.0.log syslog_9_17_3_0_F344.bin 5", program);
+    eprintln!("Example: {} Quara_fw_9.17.3.0.log syslog_9_17_3_0_F344.bin 5 --include-log-level", program);
+    eprintln!("  Dictionary and binary may be http(s):// URLs; they are downloaded to a temp file first");
+    eprintln!("  --min-level <level> Drop entries more severe than <level> (default 0)");
+    eprintln!("  --max-level <level> Same as the log_level argument, the most verbose level kept (default {}); -l is an alias", DEFAULT_MAX_LEVEL);
+    eprintln!("  --include-offset    Append the dictionary offset {{offset=0x..}} to each line");
+    eprintln!("  --quiet             Only print decoded logs (no banner, progress or warnings)");
+    eprintln!("  --summary           Append a summary line (entries, time span, level counts)");
+    eprintln!("  --module-counts     Append a line with the number of entries per module");
+    eprintln!("  --separator <sep>   Separate output fields with <sep> instead of tabs");
+    eprintln!("  --group-by-module   Print one section per module with its entries in timestamp order");
+    eprintln!("  --raw-on-mismatch   Append the raw argument words when a record's argument count disagrees with the dictionary");
+    eprintln!("  --resync            Skip over corrupt bytes to the next valid record instead of decoding them");
+    eprintln!("  --rebase-timestamps Print times relative to the first entry of each boot cycle");
+    eprintln!("  --deltas            Print the time since the previous line as (+Nms) after each timestamp");
+    eprintln!("  --progress json     Print {{\"entries\": N, \"bytes\": M}} progress lines to stderr while decoding");
+    eprintln!("  --fail-on <level>   Exit with code {} if any decoded entry is at or above this severity", FAIL_ON_EXIT_CODE);
+    eprintln!("  --output <path>     Write the decoded logs to <path> instead of stdout; -o is an alias");
+    eprintln!("Usage: {} check <dictionary.log|URL> <binary.bin|URL>", program);
+    eprintln!("  Count format violations without decoding; exits with code {} if any are found", NONCONFORMANT_EXIT_CODE);
+    eprintln!("Usage: {} gen <dictionary.log> <output.bin> [--count N] [--seed S]", program);
+    eprintln!("  Generate a synthetic binary with N valid records (default 1000) for testing");
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 4 || args.len() > 5 {
-        eprintln!("Usage: {} <dictionary.log> <binary.bin> <log_level> [--include-log-level]", args[0]);
-        eprintln!("Example: {} Quara_fw_9.17.3.0.log syslog_9_17_3_0_F344.bin 5", args[0]);
-        eprintln!("Example: {} Quara_fw_9.17.3.0.log syslog_9_17_3_0_F344.bin 5 --include-log-level", args[0]);
-        std::process::exit(1);
-    }
-    
-    let dict_path = &args[1];
-    let binary_path = &args[2]; 
-    let log_level: u8 = args[3].parse()?;
-    let include_log_level = args.len() == 5 && args[4] == "--include-log-level";
-    
-    println!("Syslog Parser v0.1.0");
-    println!("Dictionary: {}", dict_path);
-    println!("Binary: {}", binary_path);
-    println!("Log level: {}", log_level);
-    if include_log_level {
-        println!("Output format: timestamp [log_level] [module] message");
-    } else {
-        println!("Output format: timestamp [module] message");
+
+    if args.get(1).map(String::as_str) == Some("gen") {
+        return match GenArgs::parse(&args[2..]) {
+            Ok(gen_args) => run_gen(gen_args),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                print_usage(&args[0]);
+                std::process::exit(1);
+            }
+        };
     }
-    println!("---");
-    
+
+    if args.get(1).map(String::as_str) == Some("check") {
+        return match run_check(&args[2..]) {
+            Ok(true) => Ok(()),
+            Ok(false) => std::process::exit(NONCONFORMANT_EXIT_CODE),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                print_usage(&args[0]);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    let cli = match CliArgs::parse(&args[1..]) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            print_usage(&args[0]);
+            std::process::exit(1);
+        }
+    };
+
+    if !cli.quiet {
+        println!("Syslog Parser v0.1.0");
+        println!("Dictionary: {}", cli.dict_path);
+        println!("Binary: {}", cli.binary_path);
+        println!("Log level: {}", cli.log_level);
+        if cli.min_level > 0 {
+            println!("Minimum log level: {}", cli.min_level);
+        }
+        if cli.include_log_level {
+            println!("Output format: timestamp [log_level] [module] message");
+        } else {
+            println!("Output format: timestamp [module] message");
+        }
+        println!("---");
+    }
+
+    let (dict_file, binary_file) = match InputFile::resolve(&cli.dict_path)
+        .and_then(|dict_file| Ok((dict_file, InputFile::resolve(&cli.binary_path)?)))
+    {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Create parser
-    let parser = SyslogParser::new(dict_path)?;
-    println!("Loaded {} dictionary entries", parser.dictionary_size());
-    
+    let config = ParserConfig {
+        quiet: cli.quiet,
+        raw_on_mismatch: cli.raw_on_mismatch,
+        resync_on_corruption: cli.resync,
+        ..Default::default()
+    };
+    let mut parser = SyslogParser::with_config(dict_file.path(), config)?;
+    if cli.progress_json {
+        parser.set_progress_callback(|progress| {
+            eprintln!("{{\"entries\": {}, \"bytes\": {}}}", progress.entries, progress.bytes);
+        });
+    }
+    if !cli.quiet {
+        println!("Loaded {} dictionary entries", parser.dictionary_size());
+    }
+
     // Parse binary file
-    let parsed_logs = parser.parse_binary(binary_path, log_level)?;
-    println!("Parsed {} log entries", parsed_logs.len());
-    
+    let mut parsed_logs = parser.parse_binary(binary_file.path(), cli.log_level)?;
+    // Gap markers are kept like in the maximum level filter, so data loss is never hidden
+    parsed_logs.retain(|log| log.log_level >= cli.min_level || log.is_gap_marker());
+    if !cli.quiet {
+        println!("Parsed {} log entries", parsed_logs.len());
+        if parser.skipped_bytes() > 0 {
+            println!("Skipped {} corrupt bytes", parser.skipped_bytes());
+        }
+    }
+
     // Format and output logs
-    let formatted_logs = parser.format_logs_with_options(&parsed_logs, include_log_level);
-    for log in formatted_logs {
-        println!("{}", log);
+    let format_options = FormatOptions {
+        include_log_level: cli.include_log_level,
+        include_offset: cli.include_offset,
+        include_summary: cli.include_summary,
+        include_module_counts: cli.include_module_counts,
+        field_separator: cli.field_separator.clone(),
+        group_by_module: cli.group_by_module,
+        rebase_timestamps: cli.rebase_timestamps,
+        include_deltas: cli.include_deltas,
+        ..Default::default()
+    };
+    let formatted_logs = parser.format_logs_with(&parsed_logs, &format_options);
+    match &cli.output {
+        Some(output_path) => {
+            let file = File::create(output_path)
+                .map_err(|e| format!("Failed to create output file {}: {}", output_path, e))?;
+            let mut writer = BufWriter::new(file);
+            let line_count = formatted_logs.len();
+            for log in formatted_logs {
+                writeln!(writer, "{}", log)?;
+            }
+            writer.flush()?;
+            if !cli.quiet {
+                println!("Wrote {} lines to {}", line_count, output_path);
+            }
+        }
+        None => {
+            for log in formatted_logs {
+                println!("{}", log);
+            }
+        }
     }
-    
+
+    // Let CI pipelines fail on captures containing severe entries
+    if let Some(fail_on) = cli.fail_on {
+        let failures = parsed_logs.iter()
+            .filter(|log| !log.is_gap_marker()) // gap markers are not device failures
+            .filter(|log| LevelDirection::default().passes_filter(log.log_level, fail_on))
+            .count();
+        if failures > 0 {
+            if !cli.quiet {
+                eprintln!("Found {} entries at or above log level {}", failures, fail_on);
+            }
+            drop((dict_file, binary_file)); // exit skips destructors, remove downloads first
+            std::process::exit(FAIL_ON_EXIT_CODE);
+        }
+    }
+
     Ok(())
 }
@@ -25,7 +25,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Entry 1: Boot message with 2 args (timestamp=0, log_id=0, args=[5])
     binary_data.extend_from_slice(&0u32.to_le_bytes()); // timestamp
-    let log_id_1 = (2u32 << 28) | 0u32; // 2 args, offset 0
+    let log_id_1 = 2u32 << 28; // 2 args, offset 0
     binary_data.extend_from_slice(&log_id_1.to_le_bytes());
     binary_data.extend_from_slice(&5u32.to_le_bytes()); // arg1: 5 modules
     binary_data.extend_from_slice(&0u32.to_le_bytes()); // arg2: unused (should show <missing>)
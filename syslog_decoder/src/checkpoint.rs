@@ -0,0 +1,296 @@
+//! Resumable decoding of large captures.
+//!
+//! `SyslogParser::parse_binary_resumable` records its progress in a sidecar file next to
+//! the capture (`<capture>.checkpoint`) after every chunk. If the decode is interrupted,
+//! the next call with the same capture continues from the last checkpoint instead of
+//! byte 0, and the sidecar is removed once the capture has been decoded to the end.
+//!
+//! Logs are handed to the caller's sink before the checkpoint is written, so a crash
+//! between the two repeats at most one chunk of logs on resume.
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+
+use crate::{ParsedLog, SyslogParser, TimestampEncoding, RESYNC_CONFIRM_RECORDS};
+
+/// Progress of a resumable decode, as persisted in the sidecar file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeCheckpoint {
+    /// Size of the capture the checkpoint was taken on; a different size starts over
+    pub file_len: u64,
+    /// Offset of the first record not yet decoded
+    pub byte_offset: u64,
+    /// Records decoded before `byte_offset`, including ones filtered out by level
+    pub records_decoded: usize,
+    /// Absolute raw timestamp of the last decoded record, for delta-encoded captures
+    pub previous_timestamp: Option<u32>,
+    /// Raw timestamp and counter wraps of the last decoded record
+    pub previous_wrap: Option<(u32, u32)>,
+}
+
+impl DecodeCheckpoint {
+    /// Sidecar file used for a capture, e.g. `capture.bin.checkpoint`
+    pub fn sidecar_path<P: AsRef<Path>>(binary_path: P) -> PathBuf {
+        let mut path = binary_path.as_ref().as_os_str().to_owned();
+        path.push(".checkpoint");
+        PathBuf::from(path)
+    }
+
+    /// Read a checkpoint, `None` when the sidecar does not exist
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read checkpoint: {}", path.as_ref().display())),
+        };
+
+        let mut checkpoint = Self::default();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Malformed checkpoint line: {}", line))?;
+            let invalid = || anyhow::anyhow!("Invalid checkpoint value: {}", line);
+            match key {
+                "file_len" => checkpoint.file_len = value.parse().map_err(|_| invalid())?,
+                "byte_offset" => checkpoint.byte_offset = value.parse().map_err(|_| invalid())?,
+                "records_decoded" => checkpoint.records_decoded = value.parse().map_err(|_| invalid())?,
+                "previous_timestamp" => checkpoint.previous_timestamp = Some(value.parse().map_err(|_| invalid())?),
+                "previous_wrap" => {
+                    let (timestamp, wraps) = value.split_once(',').ok_or_else(invalid)?;
+                    checkpoint.previous_wrap = Some((timestamp.parse().map_err(|_| invalid())?,
+                                                     wraps.parse().map_err(|_| invalid())?));
+                }
+                _ => return Err(anyhow::anyhow!("Unknown checkpoint field: {}", key)),
+            }
+        }
+        Ok(Some(checkpoint))
+    }
+
+    /// Write the checkpoint through a temp file so a crash never leaves a partial sidecar
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut contents = format!("file_len={}\nbyte_offset={}\nrecords_decoded={}\n",
+                                   self.file_len, self.byte_offset, self.records_decoded);
+        if let Some(previous_timestamp) = self.previous_timestamp {
+            contents.push_str(&format!("previous_timestamp={}\n", previous_timestamp));
+        }
+        if let Some((timestamp, wraps)) = self.previous_wrap {
+            contents.push_str(&format!("previous_wrap={},{}\n", timestamp, wraps));
+        }
+
+        let path = path.as_ref();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        fs::write(&temp_path, contents)
+            .with_context(|| format!("Failed to write checkpoint: {}", path.display()))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write checkpoint: {}", path.display()))
+    }
+}
+
+impl SyslogParser {
+    /// Decode a capture in chunks of `chunk_size` bytes, passing each chunk's logs to `sink`
+    /// and checkpointing after it; resumes from an existing checkpoint for the same capture.
+    ///
+    /// An error from `sink` or the parser stops the decode with the checkpoint left in place.
+    /// Continuation records are not supported, as a record held for a possible continuation
+    /// has no byte offset to resume from.
+    pub fn parse_binary_resumable<P: AsRef<Path>>(&self, binary_path: P, min_log_level: u8, chunk_size: usize,
+                                                  mut sink: impl FnMut(Vec<ParsedLog>) -> Result<()>) -> Result<DecodeCheckpoint> {
+        if self.config.continuation_offset.is_some() {
+            return Err(anyhow::anyhow!("Resumable decoding does not support continuation records"));
+        }
+
+        let binary_path = binary_path.as_ref();
+        let sidecar_path = DecodeCheckpoint::sidecar_path(binary_path);
+        let mut file = File::open(binary_path)
+            .with_context(|| format!("Failed to open binary file: {}", binary_path.display()))?;
+        let file_len = file.metadata()
+            .with_context(|| format!("Failed to get file metadata: {}", binary_path.display()))?
+            .len();
+
+        let mut checkpoint = match DecodeCheckpoint::load(&sidecar_path)? {
+            Some(checkpoint) if checkpoint.file_len == file_len && checkpoint.byte_offset <= file_len => {
+                status!(self.config, "Resuming decode at byte {} after {} records",
+                        checkpoint.byte_offset, checkpoint.records_decoded);
+                checkpoint
+            }
+            Some(_) => {
+                warning!(self.config, "Warning: Checkpoint {} is for a different capture, starting over",
+                         sidecar_path.display());
+                DecodeCheckpoint { file_len, ..Default::default() }
+            }
+            None => DecodeCheckpoint { file_len, ..Default::default() },
+        };
+
+        if checkpoint.byte_offset > 0 {
+            checkpoint.byte_offset += self.resync_at_checkpoint(&mut file, checkpoint.byte_offset)? as u64;
+        }
+        file.seek(SeekFrom::Start(checkpoint.byte_offset))
+            .with_context(|| format!("Failed to seek to byte {}", checkpoint.byte_offset))?;
+
+        let mut decoder = self.stream_decoder(min_log_level);
+        decoder.previous_timestamp = checkpoint.previous_timestamp;
+        decoder.previous_wrap = checkpoint.previous_wrap;
+        let chunk_start = checkpoint.byte_offset;
+        let mut bytes_fed = 0u64;
+        let mut buffer = vec![0u8; chunk_size.max(self.max_record_size())];
+
+        loop {
+            let bytes_read = file.read(&mut buffer)
+                .with_context(|| "Failed to read from binary file")?;
+            if bytes_read == 0 {
+                break;
+            }
+            bytes_fed += bytes_read as u64;
+
+            let before = decoder.records_decoded;
+            let logs = decoder.push(&buffer[..bytes_read])?;
+            sink(logs)?;
+
+            checkpoint.byte_offset = chunk_start + bytes_fed - decoder.pending_bytes() as u64;
+            checkpoint.records_decoded += decoder.records_decoded - before;
+            checkpoint.previous_timestamp = decoder.previous_timestamp;
+            checkpoint.previous_wrap = decoder.previous_wrap;
+            checkpoint.save(&sidecar_path)?;
+        }
+
+        let before = decoder.records_decoded;
+        sink(decoder.finish()?)?;
+        checkpoint.byte_offset = file_len;
+        checkpoint.records_decoded += decoder.records_decoded - before;
+        match fs::remove_file(&sidecar_path) {
+            // Nothing was checkpointed for an empty capture
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove checkpoint: {}", sidecar_path.display()))
+            }
+            _ => Ok(checkpoint),
+        }
+    }
+
+    /// Bytes to skip from a checkpoint offset to reach a record boundary; 0 unless the
+    /// capture was modified since the checkpoint was taken
+    fn resync_at_checkpoint(&self, file: &mut File, byte_offset: u64) -> Result<usize> {
+        // Raw deltas are not ordered, so the timestamp check of the resync would reject them
+        if self.config.timestamp_encoding == TimestampEncoding::Delta {
+            return Ok(0);
+        }
+
+        let mut probe = Vec::new();
+        file.seek(SeekFrom::Start(byte_offset))
+            .with_context(|| format!("Failed to seek to byte {}", byte_offset))?;
+        file.by_ref().take((RESYNC_CONFIRM_RECORDS * self.max_record_size()) as u64).read_to_end(&mut probe)
+            .with_context(|| "Failed to read from binary file")?;
+        if probe.is_empty() {
+            return Ok(0);
+        }
+
+        match self.find_record_boundary(&probe, probe.len()) {
+            Some(0) => Ok(0),
+            Some(skip) => {
+                warning!(self.config, "Warning: Checkpoint at byte {} is not a record boundary, resyncing {} bytes later",
+                         byte_offset, skip);
+                Ok(skip)
+            }
+            None => Err(anyhow::anyhow!("No record boundary found after checkpoint at byte {}", byte_offset)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserConfig;
+
+    #[test]
+    fn test_resume_after_interrupted_decode() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        fs::write(&dict_path, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let config = ParserConfig { quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(&dict_path, config).unwrap();
+
+        let binary_path = temp_dir.path().join("capture.bin");
+        fs::write(&binary_path, parser.generate_synthetic_binary(200, 7)).unwrap();
+        let expected = parser.parse_binary(&binary_path, 5).unwrap();
+
+        // Crash in the sink while handling the third chunk
+        let mut decoded = Vec::new();
+        let mut chunks = 0;
+        let result = parser.parse_binary_resumable(&binary_path, 5, 256, |logs| {
+            chunks += 1;
+            if chunks == 3 {
+                return Err(anyhow::anyhow!("client disconnected"));
+            }
+            decoded.extend(logs);
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        let sidecar_path = DecodeCheckpoint::sidecar_path(&binary_path);
+        let checkpoint = DecodeCheckpoint::load(&sidecar_path).unwrap().unwrap();
+        assert_eq!(checkpoint.records_decoded, decoded.len());
+        assert!(checkpoint.byte_offset > 0 && checkpoint.byte_offset <= 512);
+
+        let finished = parser.parse_binary_resumable(&binary_path, 5, 256, |logs| {
+            decoded.extend(logs);
+            Ok(())
+        }).unwrap();
+        assert_eq!(decoded, expected);
+        assert_eq!(finished.records_decoded, 200);
+        assert_eq!(finished.byte_offset, fs::metadata(&binary_path).unwrap().len());
+        assert!(!sidecar_path.exists());
+    }
+
+    #[test]
+    fn test_resume_across_counter_wrap() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        fs::write(&dict_path, "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let config = ParserConfig { quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(&dict_path, config).unwrap();
+
+        // The counter wraps within the first chunk
+        let mut binary_data = Vec::new();
+        for record in 0..100u32 {
+            binary_data.extend_from_slice(&4294967000u32.wrapping_add(record * 100).to_le_bytes());
+            binary_data.extend_from_slice(&0u32.to_le_bytes());
+        }
+        let binary_path = temp_dir.path().join("capture.bin");
+        fs::write(&binary_path, binary_data).unwrap();
+        let expected = parser.parse_binary(&binary_path, 5).unwrap();
+
+        let mut decoded = Vec::new();
+        let mut chunks = 0;
+        let result = parser.parse_binary_resumable(&binary_path, 5, 256, |logs| {
+            chunks += 1;
+            if chunks == 2 {
+                return Err(anyhow::anyhow!("client disconnected"));
+            }
+            decoded.extend(logs);
+            Ok(())
+        });
+        assert!(result.is_err());
+        let checkpoint = DecodeCheckpoint::load(DecodeCheckpoint::sidecar_path(&binary_path)).unwrap().unwrap();
+        assert_eq!(checkpoint.previous_wrap.map(|(_, wraps)| wraps), Some(1));
+
+        parser.parse_binary_resumable(&binary_path, 5, 256, |logs| {
+            decoded.extend(logs);
+            Ok(())
+        }).unwrap();
+        let monotonic: Vec<u64> = decoded.iter().map(|log| log.timestamp_monotonic_ms).collect();
+        assert_eq!(monotonic, expected.iter().map(|log| log.timestamp_monotonic_ms).collect::<Vec<_>>());
+        assert_eq!(monotonic[99], (1 << 32) + 9604);
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("capture.bin.checkpoint");
+        assert_eq!(DecodeCheckpoint::load(&path).unwrap(), None);
+
+        let checkpoint = DecodeCheckpoint { file_len: 4096, byte_offset: 1024, records_decoded: 80, previous_timestamp: Some(1500),
+                                             previous_wrap: Some((4294967000, 2)) };
+        checkpoint.save(&path).unwrap();
+        assert_eq!(DecodeCheckpoint::load(&path).unwrap(), Some(checkpoint));
+    }
+}
@@ -0,0 +1,222 @@
+//! Format conformance checks for binary captures.
+//!
+//! `SyslogParser::check_conformance` walks every record of a capture and counts format
+//! violations without formatting any log text, so QA can tell whether a capture and
+//! dictionary belong together before trusting a decode. `SyslogParser::preflight` is the
+//! quick version: it only looks at the first chunk, to catch a wrong firmware version
+//! before a long decode starts.
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::{BinaryLogEntry, FormatOptions, SyslogParser, CHUNK_SIZE};
+
+/// Violation counts over a whole capture
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConformanceReport {
+    /// Records read, after continuations are attached to their record
+    pub records: usize,
+    /// Records whose offset matches no dictionary entry
+    pub unmapped_offsets: usize,
+    /// Records whose argument count differs from their dictionary entry
+    pub arg_count_mismatches: usize,
+    /// Backward timestamp jumps within a session; a jump to 0ms starts a new session
+    pub timestamp_regressions: usize,
+    /// Bytes of an incomplete record at the end of the capture
+    pub trailing_bytes: usize,
+}
+
+impl ConformanceReport {
+    pub fn violations(&self) -> usize {
+        self.unmapped_offsets + self.arg_count_mismatches + self.timestamp_regressions + usize::from(self.trailing_bytes > 0)
+    }
+
+    pub fn is_conformant(&self) -> bool {
+        self.violations() == 0
+    }
+}
+
+/// How well the start of a capture matches the dictionary
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PreflightReport {
+    /// Records read from the start of the capture
+    pub records: usize,
+    /// Records whose offset matches a dictionary entry (or the gap marker)
+    pub matched_offsets: usize,
+    /// Formatted lines of the first matching records
+    pub sample_lines: Vec<String>,
+}
+
+impl PreflightReport {
+    /// Fraction of records whose offset resolved; a low rate suggests the wrong dictionary
+    pub fn match_rate(&self) -> f64 {
+        if self.records == 0 {
+            return 0.0;
+        }
+        self.matched_offsets as f64 / self.records as f64
+    }
+}
+
+impl SyslogParser {
+    /// Resolve the offsets of the first `max_records` records against the dictionary,
+    /// reading only the first chunk of the capture, and format up to `sample_count` of
+    /// the matching ones
+    pub fn preflight<P: AsRef<Path>>(&self, binary_path: P, max_records: usize, sample_count: usize) -> Result<PreflightReport> {
+        let file = File::open(&binary_path)
+            .with_context(|| format!("Failed to open binary file: {}", binary_path.as_ref().display()))?;
+        let mut chunk_data = Vec::new();
+        file.take(CHUNK_SIZE as u64).read_to_end(&mut chunk_data)
+            .with_context(|| "Failed to read from binary file")?;
+
+        let (mut entries, _) = self.parse_chunk(&chunk_data)?;
+        self.resolve_timestamps(&mut entries, &mut None);
+        let format_options = FormatOptions { include_log_level: true, ..Default::default() };
+
+        let mut report = PreflightReport::default();
+        for entry in self.attach_all_continuations(entries).iter().take(max_records) {
+            report.records += 1;
+            let is_gap_marker = self.config.gap_marker_offset == Some(entry.log_id);
+            if !is_gap_marker && !self.dictionary.contains_key(&entry.log_id) {
+                continue;
+            }
+            report.matched_offsets += 1;
+            if report.sample_lines.len() < sample_count {
+                let log = match self.dictionary.get(&entry.log_id) {
+                    Some(log_entry) if !is_gap_marker => self.decode_entry(entry, log_entry),
+                    _ => self.dropped_logs_entry(entry),
+                };
+                report.sample_lines.push(self.format_log(&log, &format_options));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Check every record of a capture against the dictionary and the record format
+    pub fn check_conformance<P: AsRef<Path>>(&self, binary_path: P) -> Result<ConformanceReport> {
+        let mut file = File::open(&binary_path)
+            .with_context(|| format!("Failed to open binary file: {}", binary_path.as_ref().display()))?;
+        let file_len = file.metadata()
+            .with_context(|| format!("Failed to get file metadata: {}", binary_path.as_ref().display()))?
+            .len();
+
+        let mut report = ConformanceReport::default();
+        let mut buffer = vec![0u8; (file_len as usize).clamp(1, CHUNK_SIZE)];
+        let mut remainder = Vec::new();
+        let mut pending_entry = None;
+        let mut previous_timestamp = None;
+        let mut last_timestamp_ms = None;
+
+        loop {
+            let bytes_read = file.read(&mut buffer)
+                .with_context(|| "Failed to read from binary file")?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            let mut chunk_data = remainder;
+            chunk_data.extend_from_slice(&buffer[..bytes_read]);
+            let (mut entries, remaining_bytes) = self.parse_chunk(&chunk_data)?;
+            self.resolve_timestamps(&mut entries, &mut previous_timestamp);
+            for entry in self.attach_continuations(entries, &mut pending_entry) {
+                self.check_record(&entry, &mut last_timestamp_ms, &mut report);
+            }
+            remainder = remaining_bytes;
+        }
+
+        if let Some(entry) = pending_entry {
+            self.check_record(&entry, &mut last_timestamp_ms, &mut report);
+        }
+        report.trailing_bytes = remainder.len();
+        Ok(report)
+    }
+
+    fn check_record(&self, entry: &BinaryLogEntry, last_timestamp_ms: &mut Option<u64>, report: &mut ConformanceReport) {
+        report.records += 1;
+
+        if self.config.gap_marker_offset != Some(entry.log_id) {
+            match self.get_entry_by_byte_offset(entry.log_id) {
+                None => report.unmapped_offsets += 1,
+                Some(log_entry) if log_entry.num_args as usize != entry.arguments.len() => report.arg_count_mismatches += 1,
+                Some(_) => {}
+            }
+        }
+
+        let timestamp_ms = self.config.timestamp_unit.to_millis(entry.timestamp_ms);
+        if matches!(*last_timestamp_ms, Some(last) if timestamp_ms < last && timestamp_ms != 0) {
+            report.timestamp_regressions += 1;
+        }
+        *last_timestamp_ms = Some(timestamp_ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserConfig;
+
+    fn write_records(path: &Path, records: &[(u32, u32, &[u32])]) {
+        let mut binary_data = Vec::new();
+        for (timestamp, log_id, arguments) in records {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&((arguments.len() as u32) << 28 | log_id).to_le_bytes());
+            for argument in *arguments {
+                binary_data.extend_from_slice(&argument.to_le_bytes());
+            }
+        }
+        std::fs::write(path, binary_data).unwrap();
+    }
+
+    #[test]
+    fn test_conformance_report() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        std::fs::write(&dict_path, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let config = ParserConfig { quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(&dict_path, config).unwrap();
+        let binary_path = temp_dir.path().join("capture.bin");
+
+        // Two boot cycles, each with increasing timestamps
+        write_records(&binary_path, &[(0, 47, &[]), (100, 0, &[1, 2]), (0, 47, &[]), (50, 0, &[3, 4])]);
+        let report = parser.check_conformance(&binary_path).unwrap();
+        assert_eq!(report, ConformanceReport { records: 4, ..Default::default() });
+        assert!(report.is_conformant());
+
+        // Unmapped offset, wrong argument count, a backward jump and a cut-off record
+        write_records(&binary_path, &[(0, 47, &[]), (500, 9, &[]), (400, 0, &[1]), (600, 47, &[])]);
+        let mut binary_data = std::fs::read(&binary_path).unwrap();
+        binary_data.extend_from_slice(&700u32.to_le_bytes());
+        std::fs::write(&binary_path, binary_data).unwrap();
+        let report = parser.check_conformance(&binary_path).unwrap();
+        assert_eq!(report, ConformanceReport {
+            records: 4,
+            unmapped_offsets: 1,
+            arg_count_mismatches: 1,
+            timestamp_regressions: 1,
+            trailing_bytes: 4,
+        });
+        assert_eq!(report.violations(), 4);
+    }
+
+    #[test]
+    fn test_preflight_match_rate() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        std::fs::write(&dict_path, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let parser = SyslogParser::with_config(&dict_path, ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let binary_path = temp_dir.path().join("capture.bin");
+
+        // Three of the first four records resolve; the fifth is past the limit
+        write_records(&binary_path, &[(0, 47, &[]), (100, 0, &[1, 2]), (200, 9, &[]), (300, 47, &[]), (400, 9, &[])]);
+        let report = parser.preflight(&binary_path, 4, 2).unwrap();
+        assert_eq!(report.records, 4);
+        assert_eq!(report.matched_offsets, 3);
+        assert_eq!(report.match_rate(), 0.75);
+        assert_eq!(report.sample_lines.len(), 2);
+        assert!(report.sample_lines[1].ends_with("[Info]\t[TEST_MODULE]\tTrigger no 1 at 2"));
+
+        std::fs::write(&binary_path, []).unwrap();
+        assert_eq!(parser.preflight(&binary_path, 4, 2).unwrap().match_rate(), 0.0);
+    }
+}
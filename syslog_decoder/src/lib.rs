@@ -1,147 +1,931 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::fs::{self, File};
-use std::io::{Read, BufReader};
-use std::path::Path;
+use std::io::{Read, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use anyhow::{Result, Context};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 // Resource optimization constants for large file handling
 const CHUNK_SIZE: usize = 16 * 1024 * 1024;  // 16MB chunks for binary reading
-const MAX_ENTRIES_PER_BATCH: usize = 10000;  // Process entries in batches 
 const PROGRESS_REPORT_INTERVAL: usize = 100000; // Report progress every 100k entries
 const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024 * 1024; // 2GB file size limit
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF]; // Byte order mark some editors prepend to dictionaries
+const MAX_VARINT_LEN: usize = 5; // A u32 LEB128 varint takes at most 5 bytes
+const MAX_RECORD_SIZE: usize = 8 + 15 * MAX_VARINT_LEN; // Header plus the maximum of 15 arguments
+const MAX_STRING_ARG_LEN: usize = 256; // Longest inline `%s` argument; longer lengths are treated as corrupt
+const MAX_STRING_RECORD_SIZE: usize = 8 + 15 * (MAX_VARINT_LEN + MAX_STRING_ARG_LEN); // Same with 15 string arguments
+const RESYNC_CONFIRM_RECORDS: usize = 4; // Consecutive plausible records needed to accept a resync point
+const WRONG_VERSION_UNMAPPED_RATIO: f64 = 0.5; // Share of unmapped records that suggests the wrong dictionary
+const REBOOT_UPTIME_MS: u64 = 60_000; // A backward timestamp jump landing below this uptime may be a reboot
+
+// Individual placeholders of a message: flags, width, then the conversion in group 3 (numeric),
+// 4 (`%s`), 5 (`%n`) or 6 (`%ls`/`%lc`). `%%` is matched so it is never read as a conversion
+const PLACEHOLDER_PATTERN: &str = r"%%|%([-0]*)(\d*)(?:l{0,2}([udxfge])|([s])|l{0,2}(n)|l([sc]))";
+
+/// Module name reported for records flagging dropped logs
+pub const DROPPED_LOGS_MODULE: &str = "DROPPED";
+
+/// Default cap for printf field widths (e.g. `%08d`) taken from dictionary templates
+pub const DEFAULT_MAX_FORMAT_WIDTH: usize = 256;
+
+/// Prefix of the optional summary line appended after the decoded logs
+pub const SUMMARY_LINE_PREFIX: &str = "=== Summary:";
+
+/// Prefix of the optional line with entry counts per module, just before the summary line
+pub const MODULE_COUNTS_LINE_PREFIX: &str = "=== Modules:";
+
+/// Log level reported for records kept via `ParserConfig::unknown_entries`; renders as `Unknown`
+pub const UNKNOWN_LOG_LEVEL: u8 = u8::MAX;
+
+/// Version of the `DecodeResult` JSON schema, bumped whenever a field is renamed or removed
+pub const DECODE_RESULT_VERSION: u32 = 1;
+
+/// Highest log level number with a name (Verbose in descending numbering)
+pub const MAX_NAMED_LOG_LEVEL: u8 = 6;
+
+/// Most verbose level decoded when the caller doesn't choose one (Info in descending
+/// numbering); shared by the backend query defaults and the frontend level picker
+pub const DEFAULT_LOG_LEVEL: u8 = 4;
+const _: () = assert!(DEFAULT_LOG_LEVEL <= MAX_NAMED_LOG_LEVEL, "default log level must have a name");
+
+/// Print a status line to stdout unless the parser is configured to be quiet
+macro_rules! status {
+    ($config:expr, $($arg:tt)*) => {
+        if !$config.quiet {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Print a warning to stderr unless the parser is configured to be quiet
+macro_rules! warning {
+    ($config:expr, $($arg:tt)*) => {
+        if !$config.quiet {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub mod checkpoint;
+pub mod conformance;
+pub mod session;
+pub mod writer;
+
+/// Decoding errors callers may want to tell apart from generic I/O or parse failures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The dictionary file was read but no entries could be parsed from it
+    EmptyDictionary { path: String },
+    /// The capture ends part way through a record and `TruncationPolicy::Error` is set
+    TruncatedInput { trailing_bytes: usize },
+    /// A record's offset is not the start of a dictionary entry and `strict_dictionary` is set
+    UnalignedOffset { log_offset: u32, timestamp_raw: u32 },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::EmptyDictionary { path } => {
+                write!(f, "Dictionary file {} contains no entries (empty or corrupt)", path)
+            }
+            DecodeError::TruncatedInput { trailing_bytes } => {
+                write!(f, "Capture ends with {} bytes of an incomplete record (file may be truncated)", trailing_bytes)
+            }
+            DecodeError::UnalignedOffset { log_offset, timestamp_raw } => {
+                write!(f, "Record at timestamp {} references offset 0x{:X}, which is not the start of a dictionary entry", 
+                       timestamp_raw, log_offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
 
 /// Represents a log entry from the dictionary
 #[derive(Debug, Clone)]
 pub struct LogEntry {
+    pub num_args: u8,
     pub log_level: u8,
+    pub source_file: String,
+    /// Line number from `source_file:line_number`, `None` when absent or not numeric
+    pub source_line: Option<u32>,
     pub module_name: String,
     pub log_message: String,
 }
 
+impl ParsedLog {
+    /// Whether this log reports dropped logs rather than a decoded record
+    pub fn is_gap_marker(&self) -> bool {
+        self.module_name == DROPPED_LOGS_MODULE
+    }
+}
+
+impl LogEntry {
+    /// Get the source location as written in the dictionary (`file.c:123`)
+    pub fn source_location(&self) -> String {
+        match self.source_line {
+            Some(line) => format!("{}:{}", self.source_file, line),
+            None => self.source_file.clone(),
+        }
+    }
+
+    /// Render the entry in the dictionary line format (without the NULL terminator)
+    /// Format: num_args;log_level;source_file:line_number;module_name;log_message
+    pub fn to_dictionary_line(&self) -> String {
+        format!("{};{};{};{};{}", 
+               self.num_args, 
+               self.log_level, 
+               self.source_location(), 
+               self.module_name, 
+               self.log_message)
+    }
+}
+
 /// Represents a parsed log from binary file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedLog {
     pub timestamp_formatted: String,
+    /// Timestamp in milliseconds, already scaled from the capture's timestamp unit
+    pub timestamp_ms: u64,
+    /// Timestamp as stored in the record, in the capture's timestamp unit
+    #[serde(default)]
+    pub timestamp_raw: u32,
+    /// Uptime in milliseconds that keeps growing when the 32-bit counter wraps (every ~49.7
+    /// days in milliseconds); like `timestamp_ms` it restarts when the device reboots to 0
+    #[serde(default)]
+    pub timestamp_monotonic_ms: u64,
     pub log_level: u8,
     pub module_name: String,
     pub formatted_message: String,
+    /// Argument words as stored in the record, so callers can reinterpret their bits
+    /// (signed, float, hex) without parsing `formatted_message`; inline `%s` strings read as 0
+    #[serde(default)]
+    pub raw_args: Vec<u32>,
+    /// Number of logs the firmware reported as dropped, set only for gap marker records that
+    /// carry a count; see `is_gap_marker` to recognize markers without one
+    pub dropped_logs: Option<u32>,
+    /// Dictionary byte offset (log_id) the entry was decoded from
+    pub log_offset: u32,
+    /// Core that wrote the record, when `ParserConfig::core_id_field` is set
+    #[serde(default)]
+    pub core: Option<u8>,
+}
+
+/// Options for `SyslogParser::decode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Most verbose level to keep
+    pub min_log_level: u8,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self { min_log_level: DEFAULT_LOG_LEVEL }
+    }
+}
+
+/// Everything a decode produces, for embedding crates that deserialize it into their own types.
+///
+/// The JSON schema is stable within a `version` (see `DECODE_RESULT_VERSION`); new fields
+/// may be added without a version bump, so consumers should ignore unknown fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodeResult {
+    pub version: u32,
+    pub entries: Vec<ParsedLog>,
+    pub stats: DecodeStats,
+    /// Same messages as `SyslogParser::warnings` after the decode
+    pub warnings: Vec<String>,
+}
+
+/// Counts over a set of decoded logs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodeStats {
+    pub entry_count: usize,
+    pub first_timestamp_ms: Option<u64>,
+    pub last_timestamp_ms: Option<u64>,
+    /// Entries per log level number; gap markers are counted in `dropped_logs` instead
+    pub level_counts: BTreeMap<u8, usize>,
+    /// Entries per module name; gap markers are not counted
+    #[serde(default)]
+    pub module_counts: BTreeMap<String, usize>,
+    /// Total logs the firmware reported as dropped
+    pub dropped_logs: u64,
+    /// Placeholders printed as `<missing>`; counted by the parser while decoding, so only
+    /// `SyslogParser::decode` fills it in
+    #[serde(default)]
+    pub missing_arguments: usize,
+    /// Records with arguments left over after every placeholder was filled, which usually
+    /// means a slightly different dictionary version; like `missing_arguments` only
+    /// `SyslogParser::decode` fills it in
+    #[serde(default)]
+    pub extra_arguments: usize,
+}
+
+impl DecodeStats {
+    pub fn from_logs(logs: &[ParsedLog]) -> Self {
+        let mut stats = Self::default();
+        for log in logs {
+            stats.add(log);
+        }
+        stats
+    }
+
+    /// Count one more log, e.g. while consuming `SyslogParser::parse_binary_iter`
+    pub fn add(&mut self, log: &ParsedLog) {
+        self.entry_count += 1;
+        self.first_timestamp_ms = Some(self.first_timestamp_ms.map_or(log.timestamp_ms, |first| first.min(log.timestamp_ms)));
+        self.last_timestamp_ms = Some(self.last_timestamp_ms.map_or(log.timestamp_ms, |last| last.max(log.timestamp_ms)));
+        if log.is_gap_marker() {
+            // A marker without a count still means loss, but adds nothing to the known total
+            self.dropped_logs += log.dropped_logs.unwrap_or(0) as u64;
+        } else {
+            *self.level_counts.entry(log.log_level).or_insert(0) += 1;
+            // Look up before inserting so the module name is only cloned once per module
+            match self.module_counts.get_mut(&log.module_name) {
+                Some(count) => *count += 1,
+                None => {
+                    self.module_counts.insert(log.module_name.clone(), 1);
+                }
+            }
+        }
+    }
+}
+
+/// Options controlling how parsed logs are rendered as text
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// Include the log level name (e.g. `[Error]`) before the module
+    pub include_log_level: bool,
+    /// Append the dictionary offset as `{offset=0x2F}` to help debug wrong decodes
+    pub include_offset: bool,
+    /// Append a final line summarizing entry count, time span and level counts
+    pub include_summary: bool,
+    /// Append a line with the number of entries per module, before any summary line
+    pub include_module_counts: bool,
+    /// Separator between fields; `None` keeps the tab layout with a padded timestamp
+    pub field_separator: Option<String>,
+    /// How the level and module fields are delimited
+    pub bracket_style: BracketStyle,
+    /// Emit one section per module, headed by the module name, with entries in timestamp order
+    pub group_by_module: bool,
+    /// Order of the lines produced by `format_logs_unique`
+    pub dedup_order: DedupOrder,
+    /// Print timestamps relative to the first entry of each boot cycle (see `rebase_timestamps`)
+    pub rebase_timestamps: bool,
+    /// Emit one section per core, headed by `=== core N (M entries) ===`, so interleaved
+    /// streams of multi-core firmware read in their own order (see `partition_by_core`)
+    pub separate_cores: bool,
+    /// Add the time since the previous line as `(+Nms)` after the timestamp; the first
+    /// line of a run or section shows `(+0ms)` and a reboot shows a negative delta
+    pub include_deltas: bool,
+}
+
+/// Delimiters around the level and module fields of a formatted log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BracketStyle {
+    /// `[Info]` and `[MODULE]`
+    #[default]
+    Square,
+    /// Bare `Info` and `MODULE`
+    Plain,
+}
+
+/// Direction in which log level numbers grow in severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelDirection {
+    /// 0 is the most severe level (Critical) and higher numbers are more verbose
+    #[default]
+    Descending,
+    /// 0 is the most verbose level and higher numbers are more severe
+    Ascending,
+}
+
+impl LevelDirection {
+    /// Check whether a level is at least as severe as the minimum level to show
+    pub fn passes_filter(self, log_level: u8, min_log_level: u8) -> bool {
+        match self {
+            LevelDirection::Descending => log_level <= min_log_level,
+            LevelDirection::Ascending => log_level >= min_log_level,
+        }
+    }
+
+    /// Map a level in this numbering to the default (descending) numbering
+    pub fn to_descending(self, log_level: u8) -> Option<u8> {
+        match self {
+            LevelDirection::Descending => Some(log_level),
+            LevelDirection::Ascending => MAX_NAMED_LOG_LEVEL.checked_sub(log_level),
+        }
+    }
+
+    /// Level number of the most severe level in this numbering
+    pub fn most_severe(self) -> u8 {
+        match self {
+            LevelDirection::Descending => 0,
+            LevelDirection::Ascending => MAX_NAMED_LOG_LEVEL,
+        }
+    }
+}
+
+/// How record arguments are encoded in the binary capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArgEncoding {
+    /// Each argument is a fixed 4-byte little-endian word
+    #[default]
+    Fixed32,
+    /// Each argument is an unsigned LEB128 varint of 1 to 5 bytes
+    Varint,
+}
+
+/// What to do when a capture ends part way through a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncationPolicy {
+    /// Decode the complete records and say nothing
+    Ignore,
+    /// Decode the complete records and print a warning
+    #[default]
+    Warn,
+    /// Fail the decode with `DecodeError::TruncatedInput`
+    Error,
+}
+
+/// How records are framed in the binary capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordLayout {
+    /// The header is followed by exactly `num_args` arguments
+    #[default]
+    Variable,
+    /// Every record is the header plus `arg_slots` 32-bit slots; only the first
+    /// `num_args` slots are meaningful and the rest are zero padding
+    Fixed { arg_slots: usize },
+}
+
+/// Byte order of the 32-bit words (timestamp, log_id and arguments) in binary records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    #[default]
+    Little,
+    /// Captures from big-endian firmware targets
+    Big,
+}
+
+impl ByteOrder {
+    /// Read a 32-bit word stored in this byte order
+    pub fn read_u32(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            ByteOrder::Little => u32::from_le_bytes(bytes),
+            ByteOrder::Big => u32::from_be_bytes(bytes),
+        }
+    }
+
+    /// Encode a 32-bit word in this byte order
+    pub fn write_u32(self, value: u32) -> [u8; 4] {
+        match self {
+            ByteOrder::Little => value.to_le_bytes(),
+            ByteOrder::Big => value.to_be_bytes(),
+        }
+    }
+}
+
+/// Unit of the raw timestamp field in binary records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampUnit {
+    #[default]
+    Milliseconds,
+    Microseconds,
+    /// Raw hardware ticks of a clock running at `hz`
+    Ticks { hz: u32 },
+}
+
+impl TimestampUnit {
+    /// Convert a raw timestamp in this unit to whole milliseconds
+    pub fn to_millis(self, raw: u32) -> u64 {
+        self.to_millis_u64(raw as u64)
+    }
+
+    /// Convert a raw timestamp that may exceed 32 bits, e.g. after counter wraps
+    pub fn to_millis_u64(self, raw: u64) -> u64 {
+        match self {
+            TimestampUnit::Milliseconds => raw,
+            TimestampUnit::Microseconds => raw / 1000,
+            TimestampUnit::Ticks { hz } => raw.saturating_mul(1000) / hz.max(1) as u64,
+        }
+    }
+}
+
+/// Reserved bits of the 28-bit offset field that carry the id of the core that wrote a record,
+/// on multi-core firmware where several cores share one log buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreIdField {
+    /// Lowest bit of the core id within the offset field
+    pub shift: u32,
+    /// Number of bits in the core id, at most 8
+    pub width: u32,
+}
+
+impl CoreIdField {
+    /// Split a record's offset field into the dictionary offset and the core id
+    pub fn split(self, log_offset: u32) -> (u32, u8) {
+        let mask = ((1u32 << self.width.min(8)) - 1).checked_shl(self.shift).unwrap_or(0) & 0x0FFFFFFF;
+        (log_offset & !mask, (log_offset & mask).checked_shr(self.shift).unwrap_or(0) as u8)
+    }
+}
+
+/// How the timestamp field of each record is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampEncoding {
+    /// Every record carries its own timestamp
+    #[default]
+    Absolute,
+    /// The first record carries an absolute timestamp, every later one the time since the record before it
+    Delta,
+}
+
+/// Order of deduplicated logs from `format_logs_unique`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupOrder {
+    /// Each log at the position of its first occurrence, so the output still reads chronologically
+    #[default]
+    FirstSeen,
+    /// Most frequent logs first, ties in order of first occurrence
+    Summary,
+}
+
+/// What makes two logs the same for `format_logs_unique`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UniqueBy {
+    /// Same module and formatted message (arguments included)
+    #[default]
+    Message,
+    /// Same dictionary entry, whatever the arguments
+    Offset,
 }
 
 /// Binary log entry structure
 #[derive(Debug)]
 struct BinaryLogEntry {
     timestamp_ms: u32,
+    // Times the timestamp counter wrapped before this record
+    wraps: u32,
     log_id: u32,
-    arguments: Vec<u32>,
+    arguments: Vec<Arg>,
+    core: Option<u8>,
+    // Packed argument count when the record was framed with the dictionary's instead
+    declared_num_args: Option<u8>,
+}
+
+/// One argument of a binary record
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Arg {
+    U32(u32),
+    /// A `%s` argument, stored inline as a length-prefixed byte run
+    Str(String),
+}
+
+impl Arg {
+    /// The argument as a 32-bit word; strings have none and read as 0
+    fn word(&self) -> u32 {
+        match self {
+            Arg::U32(word) => *word,
+            Arg::Str(_) => 0,
+        }
+    }
+}
+
+/// How a placeholder of a dictionary message consumes the argument stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgSlot {
+    /// One argument word (`%d`, `%u`, `%x`, `%lc`)
+    Word,
+    /// A length followed by that many bytes (`%s`)
+    Str,
+    /// Argument words up to the one holding a NUL UTF-16 unit (`%ls`)
+    WideStr,
+}
+
+/// Configuration options for the syslog parser
+#[derive(Debug, Clone)]
+pub struct ParserConfig {
+    /// Largest field width honored in placeholders like `%08d`; wider requests
+    /// are clamped to this value so a corrupt template can't force huge allocations
+    pub max_format_width: usize,
+    /// Suppress all status and warning output (for library/service use)
+    pub quiet: bool,
+    /// Reserved log offset the firmware writes when it drops logs on buffer overrun;
+    /// the record's first argument carries the number of dropped logs
+    pub gap_marker_offset: Option<u32>,
+    /// Reserved log offset of continuation records: their arguments are appended to the
+    /// preceding record, for messages with more arguments than the 4-bit count can declare
+    pub continuation_offset: Option<u32>,
+    /// Abort decoding once the capture declares more than this many arguments in
+    /// total, protecting services from corrupt files with 15 args on every record
+    pub max_total_args: Option<usize>,
+    /// Whether the dictionary numbers levels by descending (default) or ascending severity;
+    /// flips the level filter and the level names
+    pub level_direction: LevelDirection,
+    /// Argument encoding declared by the capture's format descriptor
+    pub arg_encoding: ArgEncoding,
+    /// Byte order of the 32-bit words; varint arguments are unaffected
+    pub byte_order: ByteOrder,
+    /// Record framing; fixed-size records always use 32-bit slots regardless of `arg_encoding`
+    pub record_layout: RecordLayout,
+    /// How an incomplete trailing record is reported
+    pub truncation_policy: TruncationPolicy,
+    /// Unit of the record timestamps; decoded times are always reported in milliseconds
+    pub timestamp_unit: TimestampUnit,
+    /// Whether record timestamps are absolute or deltas from the previous record
+    pub timestamp_encoding: TimestampEncoding,
+    /// Fail the decode at the first record whose offset is not the start of a dictionary
+    /// entry (e.g. one landing mid-entry) instead of dropping it, since that usually means
+    /// the capture or dictionary is corrupt; `resync_on_corruption` takes precedence
+    pub strict_dictionary: bool,
+    /// Keep records whose offset matches no dictionary entry, labelled with these
+    /// placeholders, instead of dropping them
+    pub unknown_entries: Option<UnknownEntryLabels>,
+    /// When a record's argument count disagrees with its dictionary entry, append the raw
+    /// argument words to the decoded message so the bad decode can be diagnosed
+    pub raw_on_mismatch: bool,
+    /// Report `<missing>` substitutions in `SyslogParser::warnings`
+    pub warn_on_missing_arguments: bool,
+    /// Where the core id is stored on multi-core firmware; the bits are cleared from the
+    /// offset before the dictionary lookup and reported in `ParsedLog::core`
+    pub core_id_field: Option<CoreIdField>,
+    /// Treat a record whose offset matches no dictionary entry as corruption: skip one byte
+    /// and retry framing there instead of decoding it, counting the bytes skipped. At the end
+    /// of a capture, from `parse_binary` or `StreamDecoder::finish`, a record whose arguments
+    /// would run past the end is skipped the same way
+    pub resync_on_corruption: bool,
+    /// Frame a record with its dictionary entry's `num_args` instead of the 4-bit count
+    /// packed in the log_id when the two disagree, provided the entry's placeholders agree
+    /// with `num_args`; keeps the arguments of later records in step despite version skew
+    pub prefer_dictionary_arg_count: bool,
+    /// Subtracted from each record's offset before the dictionary lookup, for encoders whose
+    /// offsets point somewhere other than the entry start (e.g. 1 when they point one byte
+    /// past it). This format stores the entry start itself, so the default is 0. Reserved
+    /// offsets like `gap_marker_offset` are compared after the adjustment
+    pub offset_adjustment: i64,
+}
+
+/// Module and message reported for a record whose offset matches no dictionary entry.
+/// `{offset}` in either label is replaced with the record's offset, e.g. `0x0000002f`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEntryLabels {
+    pub module: String,
+    pub message: String,
+}
+
+impl Default for UnknownEntryLabels {
+    fn default() -> Self {
+        Self {
+            module: "UNKNOWN".to_string(),
+            message: "Unknown log format [offset: {offset}]".to_string(),
+        }
+    }
+}
+
+impl UnknownEntryLabels {
+    /// Fill in `{offset}` for a record
+    fn render(&self, log_id: u32) -> (String, String) {
+        let offset = format!("0x{:08x}", log_id);
+        (self.module.replace("{offset}", &offset), self.message.replace("{offset}", &offset))
+    }
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            max_format_width: DEFAULT_MAX_FORMAT_WIDTH,
+            quiet: false,
+            gap_marker_offset: None,
+            continuation_offset: None,
+            max_total_args: None,
+            level_direction: LevelDirection::default(),
+            arg_encoding: ArgEncoding::default(),
+            byte_order: ByteOrder::default(),
+            record_layout: RecordLayout::default(),
+            truncation_policy: TruncationPolicy::default(),
+            timestamp_unit: TimestampUnit::default(),
+            timestamp_encoding: TimestampEncoding::default(),
+            strict_dictionary: false,
+            unknown_entries: None,
+            raw_on_mismatch: false,
+            warn_on_missing_arguments: false,
+            core_id_field: None,
+            resync_on_corruption: false,
+            prefer_dictionary_arg_count: false,
+            offset_adjustment: 0,
+        }
+    }
 }
 
 /// Syslog parser library with optimized parsing
 pub struct SyslogParser {
+    // Parsed entries keyed by the byte offset where they start in the dictionary file;
+    // the raw file contents are dropped once parsed
     dictionary: HashMap<u32, LogEntry>,
-    // Store raw dictionary content for byte-offset lookups
-    raw_dictionary: Vec<u8>,
+    config: ParserConfig,
+    // Lines that failed to parse while loading the dictionary
+    dictionary_issues: Vec<ValidationIssue>,
+    // Records decoded so far, and how many of them matched no dictionary entry
+    records_seen: AtomicUsize,
+    unmapped_records: AtomicUsize,
+    // Placeholders rendered as `<missing>` because the record ran out of arguments
+    missing_arguments: AtomicUsize,
+    // Records with more arguments than their message has placeholders
+    extra_arguments: AtomicUsize,
+    // Bytes skipped to find the next record when `resync_on_corruption` is set
+    skipped_bytes: AtomicUsize,
+    // Records framed with the dictionary's argument count instead of their packed count,
+    // and the offsets already warned about so each is reported once
+    corrected_arg_counts: AtomicUsize,
+    corrected_arg_offsets: Mutex<HashSet<u32>>,
+    progress_callback: Option<ProgressCallback>,
+    // Argument slots of the entries whose message has a `%s` argument, by offset, so
+    // records are read without re-parsing their template; other entries take only words
+    string_arg_slots: HashMap<u32, Vec<ArgSlot>>,
+    // Offsets of the entries whose placeholders agree with their `num_args`, the ones
+    // `ParserConfig::prefer_dictionary_arg_count` trusts over a record's packed count
+    consistent_arg_counts: HashSet<u32>,
+}
+
+/// The parser's running decode counters at one point in time, so the warnings of a
+/// single decode can be told apart from earlier decodes with the same parser
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeCounters {
+    pub records_seen: usize,
+    pub unmapped_records: usize,
+    pub missing_arguments: usize,
+    pub extra_arguments: usize,
+    pub skipped_bytes: usize,
+    pub corrected_arg_counts: usize,
+}
+
+impl DecodeCounters {
+    /// What was counted between `start` and this snapshot
+    pub fn since(self, start: DecodeCounters) -> DecodeCounters {
+        DecodeCounters {
+            records_seen: self.records_seen - start.records_seen,
+            unmapped_records: self.unmapped_records - start.unmapped_records,
+            missing_arguments: self.missing_arguments - start.missing_arguments,
+            extra_arguments: self.extra_arguments - start.extra_arguments,
+            skipped_bytes: self.skipped_bytes - start.skipped_bytes,
+            corrected_arg_counts: self.corrected_arg_counts - start.corrected_arg_counts,
+        }
+    }
 }
 
+/// Progress of a `parse_binary` call, reported after each chunk of the capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeProgress {
+    /// Records decoded so far, including ones filtered out by level
+    pub entries: usize,
+    /// Bytes of the capture read so far
+    pub bytes: u64,
+}
+
+type ProgressCallback = Box<dyn Fn(DecodeProgress) + Send + Sync>;
+
 impl SyslogParser {
     /// Create a new parser with dictionary file
     pub fn new<P: AsRef<Path>>(dictionary_path: P) -> Result<Self> {
-        let (dictionary, raw_dictionary) = Self::load_dictionary(dictionary_path)?;
-        
-        Ok(Self { 
-            dictionary,
-            raw_dictionary,
-        })
+        Self::with_config(dictionary_path, ParserConfig::default())
     }
 
-    /// Load dictionary from .log file (optimized with byte offset support)
-    fn load_dictionary<P: AsRef<Path>>(path: P) -> Result<(HashMap<u32, LogEntry>, Vec<u8>)> {
-        let contents = fs::read(&path)
-            .with_context(|| format!("Failed to read dictionary file: {}", path.as_ref().display()))?;
-        
-        let mut dictionary = HashMap::new();
+    /// Create a new parser for captures written in the given byte order
+    pub fn with_byte_order<P: AsRef<Path>>(dictionary_path: P, byte_order: ByteOrder) -> Result<Self> {
+        Self::with_config(dictionary_path, ParserConfig { byte_order, ..Default::default() })
+    }
 
-        // Split by NULL character (0x00) and track byte positions
-        let mut start_pos = 0;
-        for end_pos in contents.iter().enumerate().filter_map(|(i, &b)| if b == 0x00 { Some(i) } else { None }) {
-            if start_pos < end_pos {
-                let entry_bytes = &contents[start_pos..end_pos];
-                let line = String::from_utf8_lossy(entry_bytes);
-                let trimmed = line.trim();
-                
-                if !trimmed.is_empty() {
-                    match Self::parse_dictionary_line(trimmed) {
-                        Ok(entry) => {
-                            dictionary.insert(start_pos as u32, entry);
-                        }
-                        Err(e) => {
-                            eprintln!("Warning: Failed to parse dictionary line at byte {}: {} ({})", 
-                                     start_pos, trimmed, e);
-                        }
-                    }
-                }
+    /// Create a new parser with dictionary file and custom configuration
+    pub fn with_config<P: AsRef<Path>>(dictionary_path: P, config: ParserConfig) -> Result<Self> {
+        let (dictionary, dictionary_issues) = Self::load_dictionary(&[dictionary_path], &config)?;
+        Ok(Self::from_dictionary(dictionary, dictionary_issues, config))
+    }
+
+    /// Create a parser from a dictionary split across several files, e.g. one per component.
+    ///
+    /// The files are laid out back to back in the given order, as if concatenated: each file's
+    /// offsets are shifted by the total size of the files before it (without any UTF-8 BOM), so
+    /// the files occupy disjoint offset ranges and a log_id resolves to the file it points into.
+    pub fn from_paths(paths: &[PathBuf]) -> Result<Self> {
+        let config = ParserConfig::default();
+        let (dictionary, dictionary_issues) = Self::load_dictionary(paths, &config)?;
+        Ok(Self::from_dictionary(dictionary, dictionary_issues, config))
+    }
+
+    fn from_dictionary(dictionary: HashMap<u32, LogEntry>, dictionary_issues: Vec<ValidationIssue>, config: ParserConfig) -> Self {
+        let mut string_arg_slots = HashMap::new();
+        let mut consistent_arg_counts = HashSet::new();
+        for (&offset, log_entry) in &dictionary {
+            let slots = argument_slots(&log_entry.log_message);
+            if slot_count_mismatch(&slots, log_entry.num_args).is_none() {
+                consistent_arg_counts.insert(offset);
             }
-            
-            start_pos = end_pos + 1; // Skip the NULL character
+            if slots.contains(&ArgSlot::Str) {
+                string_arg_slots.insert(offset, slots);
+            }
+        }
+        
+        Self { 
+            dictionary,
+            config,
+            dictionary_issues,
+            records_seen: AtomicUsize::new(0),
+            unmapped_records: AtomicUsize::new(0),
+            missing_arguments: AtomicUsize::new(0),
+            extra_arguments: AtomicUsize::new(0),
+            skipped_bytes: AtomicUsize::new(0),
+            corrected_arg_counts: AtomicUsize::new(0),
+            corrected_arg_offsets: Mutex::new(HashSet::new()),
+            progress_callback: None,
+            string_arg_slots,
+            consistent_arg_counts,
         }
+    }
 
-        // Handle the last entry if file doesn't end with NULL
-        if start_pos < contents.len() {
-            let entry_bytes = &contents[start_pos..];
-            let line = String::from_utf8_lossy(entry_bytes);
-            let trimmed = line.trim();
-            
-            if !trimmed.is_empty() {
-                match Self::parse_dictionary_line(trimmed) {
-                    Ok(entry) => {
-                        dictionary.insert(start_pos as u32, entry);
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse dictionary line at byte {}: {} ({})", 
-                                 start_pos, trimmed, e);
-                    }
-                }
-            }
+    /// Call `callback` with the decode progress as `parse_binary` works through a capture
+    pub fn set_progress_callback(&mut self, callback: impl Fn(DecodeProgress) + Send + Sync + 'static) {
+        self.progress_callback = Some(Box::new(callback));
+    }
+
+    fn report_progress(&self, entries: usize, bytes: u64) {
+        if let Some(callback) = &self.progress_callback {
+            callback(DecodeProgress { entries, bytes });
         }
+    }
 
-        println!("Loaded {} dictionary entries from {}", 
-                 dictionary.len(), path.as_ref().display());
-        Ok((dictionary, contents))
+    /// Problems worth showing to a user who can't see stderr: dictionary lines that failed
+    /// to parse, records whose offset matched no entry, and a hint when most records are
+    /// unmapped, which usually means the dictionary is for a different firmware version.
+    ///
+    /// Counts cover every decode since the parser was created; see `warnings_since` for one decode
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings_since(DecodeCounters::default())
     }
 
-    /// Get dictionary entry by byte offset from raw dictionary content
-    fn get_entry_by_byte_offset(&self, byte_offset: u32) -> Option<LogEntry> {
-        let offset = byte_offset as usize;
-        if offset >= self.raw_dictionary.len() {
-            return None;
+    /// Current values of the counters behind `warnings`, to pass to `warnings_since` later
+    pub fn counters(&self) -> DecodeCounters {
+        DecodeCounters {
+            records_seen: self.records_seen.load(Ordering::Relaxed),
+            unmapped_records: self.unmapped_records.load(Ordering::Relaxed),
+            missing_arguments: self.missing_arguments(),
+            extra_arguments: self.extra_arguments(),
+            skipped_bytes: self.skipped_bytes(),
+            corrected_arg_counts: self.corrected_arg_counts(),
+        }
+    }
+
+    /// Like `warnings`, with the counts limited to what was decoded after the `start` snapshot
+    pub fn warnings_since(&self, start: DecodeCounters) -> Vec<String> {
+        let mut warnings: Vec<String> = self.dictionary_issues.iter()
+            .map(|issue| format!("Dictionary line at byte {}: {}", issue.byte_offset, issue.message))
+            .collect();
+
+        let counters = self.counters().since(start);
+        if counters.unmapped_records > 0 {
+            warnings.push(format!("{} of {} records reference offsets missing from the dictionary", 
+                                  counters.unmapped_records, counters.records_seen));
+            if counters.unmapped_records as f64 > counters.records_seen as f64 * WRONG_VERSION_UNMAPPED_RATIO {
+                warnings.push("Most records do not match this dictionary; the capture may be from a different firmware version".to_string());
+            }
         }
 
-        // Find the end of this entry (next NULL character or end of file)
-        let mut end_pos = offset;
-        while end_pos < self.raw_dictionary.len() && self.raw_dictionary[end_pos] != 0x00 {
-            end_pos += 1;
+        if counters.skipped_bytes > 0 {
+            warnings.push(format!("Skipped {} corrupt bytes to resync on valid records", counters.skipped_bytes));
         }
 
-        if end_pos == offset {
-            return None; // Empty entry
+        if counters.corrected_arg_counts > 0 {
+            warnings.push(format!("{} records declared a different argument count than the dictionary and were read with the dictionary's", 
+                                  counters.corrected_arg_counts));
         }
 
-        let entry_bytes = &self.raw_dictionary[offset..end_pos];
-        let line = String::from_utf8_lossy(entry_bytes);
-        let trimmed = line.trim();
+        if self.config.warn_on_missing_arguments && counters.missing_arguments > 0 {
+            warnings.push(format!("{} placeholders had no argument and were printed as <missing>; the dictionary may not match the capture", 
+                                  counters.missing_arguments));
+        }
 
-        if trimmed.is_empty() {
-            return None;
+        if counters.extra_arguments > 0 {
+            warnings.push(format!("{} records had more arguments than placeholders; the dictionary may be from a different firmware version", 
+                                  counters.extra_arguments));
+        }
+        warnings
+    }
+
+    /// Check the loaded dictionary for lines that were dropped while loading and entries whose
+    /// placeholders disagree with their `num_args`, before decoding a capture against it
+    pub fn validate(&self) -> DictionaryReport {
+        let count = |kind| self.dictionary_issues.iter().filter(|issue| issue.kind == kind).count();
+        DictionaryReport {
+            total_entries: self.dictionary.len(),
+            malformed_lines: count(ValidationIssueKind::MalformedLine),
+            duplicate_offsets: count(ValidationIssueKind::DuplicateOffset),
+            arg_count_mismatches: self.dictionary.values().filter(|entry| arg_count_mismatch(entry).is_some()).count(),
         }
+    }
 
-        match Self::parse_dictionary_line(trimmed) {
-            Ok(entry) => Some(entry),
-            Err(e) => {
-                eprintln!("Warning: Failed to parse dictionary entry at byte offset {}: {} ({})", 
-                         byte_offset, trimmed, e);
-                None
+    /// Bytes skipped so far to resync after corrupt records, see `ParserConfig::resync_on_corruption`
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Records framed with the dictionary's argument count so far, see
+    /// `ParserConfig::prefer_dictionary_arg_count`
+    pub fn corrected_arg_counts(&self) -> usize {
+        self.corrected_arg_counts.load(Ordering::Relaxed)
+    }
+
+    /// Placeholders printed as `<missing>` so far because a record had too few arguments
+    pub fn missing_arguments(&self) -> usize {
+        self.missing_arguments.load(Ordering::Relaxed)
+    }
+
+    /// Records so far whose arguments outnumbered the placeholders of their message
+    pub fn extra_arguments(&self) -> usize {
+        self.extra_arguments.load(Ordering::Relaxed)
+    }
+
+    /// Update parser configuration
+    pub fn set_config(&mut self, config: ParserConfig) {
+        self.config = config;
+    }
+
+    /// Load dictionary from .log files (optimized with byte offset support); the offsets of
+    /// each file continue from the end of the file before it
+    fn load_dictionary<P: AsRef<Path>>(paths: &[P], config: &ParserConfig) -> Result<(HashMap<u32, LogEntry>, Vec<ValidationIssue>)> {
+        let mut dictionary = HashMap::new();
+        let mut issues = Vec::new();
+        let mut base_offset = 0u32;
+        let placeholder_pattern = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+        for path in paths {
+            let raw_contents = fs::read(path)
+                .with_context(|| format!("Failed to read dictionary file: {}", path.as_ref().display()))?;
+
+            // Editors may prepend a UTF-8 BOM that the firmware's offsets don't account for,
+            // so strip it and measure offsets from the first real byte
+            let contents = match raw_contents.strip_prefix(UTF8_BOM) {
+                Some(contents) => {
+                    warning!(config, "Warning: Stripped UTF-8 BOM from dictionary {}", path.as_ref().display());
+                    contents
+                }
+                None => &raw_contents[..],
+            };
+
+            for (start_pos, line) in split_dictionary(contents) {
+                let start_pos = base_offset + start_pos;
+                match Self::parse_dictionary_line(&line) {
+                    Ok(entry) => {
+                        // printf's %n writes through a pointer; it has no meaning in a decoded log
+                        if uses_percent_n(&placeholder_pattern, &entry.log_message) {
+                            warning!(config, "Warning: Dictionary entry at byte {} uses %n, which is left unformatted: {}", 
+                                     start_pos, entry.log_message);
+                        }
+                        if dictionary.insert(start_pos, entry).is_some() {
+                            issues.push(ValidationIssue {
+                                byte_offset: start_pos,
+                                kind: ValidationIssueKind::DuplicateOffset,
+                                message: "Entry replaces an earlier entry at the same offset".to_string(),
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        warning!(config, "Warning: Failed to parse dictionary line at byte {}: {} ({})", 
+                                 start_pos, line, e);
+                        issues.push(ValidationIssue {
+                            byte_offset: start_pos,
+                            kind: ValidationIssueKind::MalformedLine,
+                            message: format!("Failed to parse line '{}': {}", line, e),
+                        });
+                    }
+                }
             }
+            base_offset = u32::try_from(contents.len()).ok()
+                .and_then(|len| base_offset.checked_add(len))
+                .with_context(|| "Dictionary files exceed the 32-bit offset range")?;
+        }
+
+        let paths = paths.iter().map(|path| path.as_ref().display().to_string()).collect::<Vec<_>>().join(", ");
+        if dictionary.is_empty() {
+            return Err(DecodeError::EmptyDictionary { path: paths }.into());
         }
+
+        status!(config, "Loaded {} dictionary entries from {}", 
+                 dictionary.len(), paths);
+        Ok((dictionary, issues))
+    }
+
+    /// Get dictionary entry by the byte offset where it starts in the dictionary file
+    fn get_entry_by_byte_offset(&self, byte_offset: u32) -> Option<&LogEntry> {
+        self.dictionary.get(&byte_offset)
+    }
+
+    /// Reject a record whose offset is not a known entry when `strict_dictionary` is set
+    fn check_strict_offset(&self, entry: &BinaryLogEntry) -> Result<()> {
+        if self.config.strict_dictionary && !self.is_known_offset(entry.log_id) {
+            return Err(DecodeError::UnalignedOffset { log_offset: entry.log_id, timestamp_raw: entry.timestamp_ms }.into());
+        }
+        Ok(())
     }
 
     /// Parse a single dictionary line (optimized)
@@ -149,8 +933,11 @@ impl SyslogParser {
     fn parse_dictionary_line(line: &str) -> Result<LogEntry> {
         let mut parts = line.splitn(5, ';'); // More efficient - stops after 5 parts
         
-        // Skip num_args (parts[0])
-        parts.next().context("Missing num_args field")?;
+        let num_args = parts.next()
+            .context("Missing num_args field")?
+            .trim()
+            .parse::<u8>()
+            .context("Failed to parse num_args")?;
 
         let log_level = parts.next()
             .context("Missing log_level field")?
@@ -158,8 +945,9 @@ impl SyslogParser {
             .parse::<u8>()
             .context("Failed to parse log level")?;
 
-        // Skip source file and line number (parts[2])
-        parts.next().context("Missing source_file field")?;
+        let (source_file, source_line) = Self::parse_source_location(
+            parts.next().context("Missing source_file field")?.trim()
+        );
         
         let module_name = parts.next()
             .context("Missing module_name field")?
@@ -172,12 +960,77 @@ impl SyslogParser {
             .to_string();
 
         Ok(LogEntry {
+            num_args,
             log_level,
+            source_file,
+            source_line,
             module_name,
             log_message,
         })
     }
 
+    /// Split a `source_file:line_number` field into its parts.
+    /// Entries without a colon or with a non-numeric line keep the whole field as the file.
+    fn parse_source_location(field: &str) -> (String, Option<u32>) {
+        match field.rsplit_once(':') {
+            Some((file, line)) => match line.trim().parse::<u32>() {
+                Ok(line) => (file.trim().to_string(), Some(line)),
+                Err(_) => (field.to_string(), None),
+            },
+            None => (field.to_string(), None),
+        }
+    }
+
+    /// Generate a synthetic binary capture referencing this dictionary.
+    ///
+    /// Each record uses a valid dictionary offset, carries as many arguments as the
+    /// entry declares (capped at the 4-bit nibble maximum of 15) and has a strictly
+    /// increasing timestamp. Words are written in the configured byte order, and
+    /// the same seed always produces the same capture.
+    pub fn generate_synthetic_binary(&self, record_count: usize, seed: u64) -> Vec<u8> {
+        let mut offsets: Vec<u32> = self.dictionary.keys().copied().collect();
+        offsets.sort_unstable();
+        if offsets.is_empty() {
+            return Vec::new();
+        }
+
+        let mut rng = XorShift64::new(seed);
+        let mut binary_data = Vec::with_capacity(record_count * 16);
+        let mut timestamp_ms: u32 = 0;
+        let byte_order = self.config.byte_order;
+
+        for _ in 0..record_count {
+            let offset = offsets[(rng.next_u32() as usize) % offsets.len()];
+            let num_args = self.dictionary[&offset].num_args.min(0xF) as u32;
+            let slots = self.string_arg_slots.get(&offset).map_or(&[][..], Vec::as_slice);
+            timestamp_ms = timestamp_ms.saturating_add(1 + rng.next_u32() % 1000);
+
+            binary_data.extend_from_slice(&byte_order.write_u32(timestamp_ms));
+            binary_data.extend_from_slice(&byte_order.write_u32((num_args << 28) | (offset & 0x0FFFFFFF)));
+            for slot in 0..num_args as usize {
+                if slots.get(slot) == Some(&ArgSlot::Str) {
+                    // Inline string padded to a word, as `read_string_argument` expects
+                    let text = format!("str{}", rng.next_u32() % 1000);
+                    binary_data.extend_from_slice(&byte_order.write_u32(text.len() as u32));
+                    binary_data.extend_from_slice(text.as_bytes());
+                    binary_data.resize(binary_data.len().next_multiple_of(4), 0);
+                } else {
+                    binary_data.extend_from_slice(&byte_order.write_u32(rng.next_u32()));
+                }
+            }
+        }
+
+        binary_data
+    }
+
+    /// Serialize the loaded dictionary back into the NULL-separated format
+    pub fn export_dictionary(&self) -> Result<Vec<u8>> {
+        let entries: Vec<(u32, LogEntry)> = self.dictionary.iter()
+            .map(|(offset, entry)| (*offset, entry.clone()))
+            .collect();
+        serialize_dictionary(&entries)
+    }
+
     /// Parse binary log file and return formatted logs (optimized for large files)
     pub fn parse_binary<P: AsRef<Path>>(&self, binary_path: P, min_log_level: u8) -> Result<Vec<ParsedLog>> {
         // Check file size first
@@ -189,108 +1042,373 @@ impl SyslogParser {
                                      metadata.len(), MAX_FILE_SIZE));
         }
 
-        println!("Parsing binary file: {} ({:.2} MB)", 
+        status!(self.config, "Parsing binary file: {} ({:.2} MB)", 
                  binary_path.as_ref().display(), 
                  metadata.len() as f64 / (1024.0 * 1024.0));
 
-        // Use streaming reader for large files, regular reader for small files
-        if metadata.len() > CHUNK_SIZE as u64 {
-            self.parse_binary_streaming(binary_path, min_log_level)
-        } else {
-            self.parse_binary_legacy(binary_path, min_log_level)
-        }
-    }
-
-    /// Legacy method for small files (loads entire file into memory)
-    fn parse_binary_legacy<P: AsRef<Path>>(&self, binary_path: P, min_log_level: u8) -> Result<Vec<ParsedLog>> {
-        let binary_entries = self.read_binary_file_legacy(binary_path)?;
-        
-        let mut parsed_logs = Vec::with_capacity(binary_entries.len().min(MAX_ENTRIES_PER_BATCH));
-
-        for entry in binary_entries {
-            if let Some(parsed_log) = self.process_binary_entry(&entry, min_log_level) {
-                parsed_logs.push(parsed_log);
-            }
-        }
-
-        println!("Parsed {} logs from binary file (min level: {})", 
+        let parsed_logs = self.parse_binary_iter(binary_path, min_log_level)?.collect::<Result<Vec<_>>>()?;
+        status!(self.config, "Parsed {} logs from binary file (min level: {})", 
                  parsed_logs.len(), min_log_level);
         Ok(parsed_logs)
     }
 
-    /// Streaming method for large files (processes in chunks)
-    fn parse_binary_streaming<P: AsRef<Path>>(&self, binary_path: P, min_log_level: u8) -> Result<Vec<ParsedLog>> {
+    /// Decode a binary file lazily, one chunk of the file in memory at a time.
+    ///
+    /// Logs are yielded as their chunk is decoded. A read error, an exceeded argument budget
+    /// or a truncated record under `TruncationPolicy::Error` is yielded as the last item.
+    pub fn parse_binary_iter<P: AsRef<Path>>(&self, binary_path: P, min_log_level: u8) -> Result<impl Iterator<Item = Result<ParsedLog>> + '_> {
         let file = File::open(&binary_path)
             .with_context(|| format!("Failed to open binary file: {}", binary_path.as_ref().display()))?;
-        
-        let mut reader = BufReader::new(file);
-        let mut parsed_logs = Vec::new();
-        let mut buffer = vec![0u8; CHUNK_SIZE];
-        let mut remainder = Vec::new();
-        let mut total_entries = 0;
-        let mut batch_count = 0;
+        let file_len = file.metadata()
+            .with_context(|| format!("Failed to get file metadata: {}", binary_path.as_ref().display()))?
+            .len();
 
-        loop {
-            // Read chunk from file
-            let bytes_read = reader.read(&mut buffer)
-                .with_context(|| "Failed to read from binary file")?;
-            
-            if bytes_read == 0 {
-                break; // End of file
-            }
+        Ok(BinaryLogIter {
+            parser: self,
+            min_log_level,
+            reader: BufReader::new(file),
+            buffer: vec![0u8; (file_len as usize).clamp(1, CHUNK_SIZE)],
+            remainder: Vec::new(),
+            entries: std::collections::VecDeque::new(),
+            pending_entry: None,
+            previous_timestamp: None,
+            previous_wrap: None,
+            total_entries: 0,
+            total_args: 0,
+            total_bytes: 0,
+            finished: false,
+        })
+    }
 
-            // Combine remainder from previous chunk with new data
-            let mut chunk_data = remainder;
-            chunk_data.extend_from_slice(&buffer[..bytes_read]);
+    /// Decode a binary file into a single serializable result with the logs, their stats and warnings
+    pub fn decode<P: AsRef<Path>>(&self, binary_path: P, options: &DecodeOptions) -> Result<DecodeResult> {
+        let start = self.counters();
+        let entries = self.parse_binary(binary_path, options.min_log_level)?;
+        let counters = self.counters().since(start);
+        Ok(DecodeResult {
+            version: DECODE_RESULT_VERSION,
+            stats: DecodeStats {
+                missing_arguments: counters.missing_arguments,
+                extra_arguments: counters.extra_arguments,
+                ..DecodeStats::from_logs(&entries)
+            },
+            entries,
+            warnings: self.warnings_since(start),
+        })
+    }
 
-            // Process entries from this chunk
-            let (entries, remaining_bytes) = self.parse_chunk(&chunk_data)?;
-            
-            // Process entries in batches to manage memory
-            for batch in entries.chunks(MAX_ENTRIES_PER_BATCH) {
-                for entry in batch {
-                    if let Some(parsed_log) = self.process_binary_entry(entry, min_log_level) {
-                        parsed_logs.push(parsed_log);
-                    }
-                    total_entries += 1;
+    /// Create an incremental decoder for captures that arrive in pieces, e.g. a live device stream
+    pub fn stream_decoder(&self, min_log_level: u8) -> StreamDecoder<'_> {
+        StreamDecoder {
+            parser: self,
+            min_log_level,
+            remainder: Vec::new(),
+            pending_entry: None,
+            previous_timestamp: None,
+            previous_wrap: None,
+            records_decoded: 0,
+            total_args: 0,
+        }
+    }
 
-                    // Report progress periodically
-                    if total_entries % PROGRESS_REPORT_INTERVAL == 0 {
-                        println!("Processed {} entries...", total_entries);
-                    }
-                }
-                
-                batch_count += 1;
-                // Hint that batch processing is complete for memory management
-                if batch_count % 10 == 0 {
-                    // Allow garbage collector to reclaim memory from processed batches
-                    println!("Processed {} batches, {} entries total", batch_count, total_entries);
-                }
+    /// Decode only the records that start within the byte range `start..end` of a binary file
+    ///
+    /// `start` does not need to fall on a record boundary: the parser resyncs to the first
+    /// position where several consecutive records reference valid dictionary entries with
+    /// non-decreasing timestamps. The record straddling `end` is still decoded in full.
+    /// Delta-encoded captures can only be decoded from the start, as earlier deltas are unknown.
+    pub fn parse_binary_range_bytes<P: AsRef<Path>>(&self, binary_path: P, start: u64, end: u64, min_log_level: u8) -> Result<Vec<ParsedLog>> {
+        if start > end {
+            return Err(anyhow::anyhow!("Invalid byte range: start {} is after end {}", start, end));
+        }
+
+        let mut file = File::open(&binary_path)
+            .with_context(|| format!("Failed to open binary file: {}", binary_path.as_ref().display()))?;
+        let file_len = file.metadata()
+            .with_context(|| format!("Failed to get file metadata: {}", binary_path.as_ref().display()))?
+            .len();
+        let end = end.min(file_len);
+        if start >= end {
+            return Ok(Vec::new());
+        }
+
+        // Fixed-width records are made of 32-bit words, so candidate boundaries are word aligned
+        let alignment = self.record_alignment() as u64;
+        let aligned_start = start.div_ceil(alignment) * alignment;
+        let read_len = (end.saturating_sub(aligned_start) as usize + self.max_record_size())
+            .min((file_len - aligned_start.min(file_len)) as usize);
+        file.seek(SeekFrom::Start(aligned_start))
+            .with_context(|| format!("Failed to seek to byte {}", aligned_start))?;
+        let mut data = vec![0u8; read_len];
+        file.read_exact(&mut data)
+            .with_context(|| "Failed to read from binary file")?;
+
+        let range_len = (end - aligned_start.min(end)) as usize;
+        let delta_encoded = self.config.timestamp_encoding == TimestampEncoding::Delta;
+        if delta_encoded && start > 0 {
+            return Err(anyhow::anyhow!("Delta-encoded timestamps can only be decoded from byte 0, not {}", start));
+        }
+        // Raw deltas are not ordered, so the timestamp check of the resync would reject them
+        let boundary = if delta_encoded { Some(0) } else { self.find_record_boundary(&data, range_len) };
+        let mut offset = match boundary {
+            Some(offset) => offset,
+            None => {
+                warning!(self.config, "Warning: No record boundary found between bytes {} and {}", start, end);
+                return Ok(Vec::new());
             }
+        };
+        if offset > 0 {
+            status!(self.config, "Resynced to record boundary at byte {}", aligned_start + offset as u64);
+        }
 
-            // Save incomplete data for next iteration
-            remainder = remaining_bytes;
+        let mut entries = Vec::new();
+        let mut total_args = 0;
+        while offset < range_len {
+            let Some((entry, record_len)) = self.read_record(&data, offset) else {
+                // Only possible when the file ends inside this record
+                self.report_truncation(data.len() - offset)?;
+                break;
+            };
+            self.check_strict_offset(&entry)?;
+            total_args += entry.arguments.len();
+            self.check_arg_budget(total_args)?;
 
-            // If we're at end of file but have remaining bytes, it's incomplete data
-            if bytes_read < CHUNK_SIZE && !remainder.is_empty() {
-                println!("Warning: {} incomplete bytes at end of file", remainder.len());
+            entries.push(entry);
+            offset += record_len;
+        }
+        // A continuation of the last record in range starts right at `end`
+        while let Some((entry, record_len)) = self.read_record(&data, offset) {
+            if self.config.continuation_offset != Some(entry.log_id) {
                 break;
             }
+            entries.push(entry);
+            offset += record_len;
         }
 
-        println!("Streaming parse completed: {} logs from {} total entries (min level: {})", 
-                 parsed_logs.len(), total_entries, min_log_level);
+        self.resolve_timestamps(&mut entries, &mut None);
+        let parsed_logs: Vec<ParsedLog> = self.attach_all_continuations(entries).iter()
+            .filter_map(|entry| self.process_binary_entry(entry, min_log_level))
+            .collect();
+
+        status!(self.config, "Parsed {} logs from bytes {}..{} (min level: {})", 
+                 parsed_logs.len(), start, end, min_log_level);
         Ok(parsed_logs)
     }
 
+    /// Alignment of record boundaries for the configured argument encoding
+    fn record_alignment(&self) -> usize {
+        match (self.config.record_layout, self.config.arg_encoding) {
+            (RecordLayout::Fixed { .. }, _) | (_, ArgEncoding::Fixed32) => 4,
+            (RecordLayout::Variable, ArgEncoding::Varint) => 1,
+        }
+    }
+
+    /// Largest possible record in the configured layout
+    fn max_record_size(&self) -> usize {
+        match self.config.record_layout {
+            RecordLayout::Variable if !self.string_arg_slots.is_empty() => MAX_STRING_RECORD_SIZE,
+            RecordLayout::Variable => MAX_RECORD_SIZE,
+            RecordLayout::Fixed { arg_slots } => 8 + 4 * arg_slots,
+        }
+    }
+
+    /// Find the first aligned offset below `limit` that starts a run of plausible records
+    fn find_record_boundary(&self, data: &[u8], limit: usize) -> Option<usize> {
+        (0..limit).step_by(self.record_alignment()).find(|&candidate| {
+            let mut offset = candidate;
+            let mut last_timestamp = 0;
+            for _ in 0..RESYNC_CONFIRM_RECORDS {
+                let Some((entry, record_len)) = self.read_record(data, offset) else {
+                    // Running out of data after at least one good record is accepted
+                    return offset > candidate;
+                };
+                if !self.is_known_offset(entry.log_id) || entry.timestamp_ms < last_timestamp {
+                    return false;
+                }
+                last_timestamp = entry.timestamp_ms;
+                offset += record_len;
+            }
+            true
+        })
+    }
+
+    /// Whether an offset is a dictionary entry or one of the configured marker records
+    fn is_known_offset(&self, log_offset: u32) -> bool {
+        self.config.gap_marker_offset == Some(log_offset)
+            || self.config.continuation_offset == Some(log_offset)
+            || self.dictionary.contains_key(&log_offset)
+    }
+
+    /// Read one complete record at `offset`, returning it with its length in bytes
+    fn read_record(&self, data: &[u8], offset: usize) -> Option<(BinaryLogEntry, usize)> {
+        let timestamp_ms = self.read_word(data, offset)?;
+        let log_id_raw = self.read_word(data, offset + 4)?;
+        let (packed_num_args, log_offset, core) = self.split_log_id(log_id_raw);
+        let num_args = self.framing_arg_count(packed_num_args, log_offset);
+        let (arguments, end) = self.read_arguments(data, offset + 8, num_args as usize, log_offset)?;
+
+        Some((BinaryLogEntry {
+            timestamp_ms,
+            wraps: 0,
+            log_id: log_offset,
+            arguments,
+            core,
+            declared_num_args: (num_args != packed_num_args).then_some(packed_num_args),
+        }, end - offset))
+    }
+
+    /// Split a raw log_id into its argument count (top 4 bits), dictionary offset
+    /// (low 28 bits) and, if configured, the core id stored in the offset bits
+    fn split_log_id(&self, log_id_raw: u32) -> (u8, u32, Option<u8>) {
+        let num_args = ((log_id_raw >> 28) & 0xF) as u8;
+        let log_offset = log_id_raw & 0x0FFFFFFF;
+        let (log_offset, core) = match self.config.core_id_field {
+            Some(field) => {
+                let (log_offset, core) = field.split(log_offset);
+                (log_offset, Some(core))
+            }
+            None => (log_offset, None),
+        };
+        // An offset adjusted out of range matches no entry
+        let log_offset = u32::try_from(log_offset as i64 - self.config.offset_adjustment).unwrap_or(u32::MAX);
+        (num_args, log_offset, core)
+    }
+
+    /// Number of arguments to read for a record: the packed count, or the dictionary's when
+    /// `prefer_dictionary_arg_count` is set and the entry's placeholders back it up.
+    ///
+    /// Resync probes frame records too, so corrections are only counted once a record is
+    /// decoded, see `note_corrected_arg_count`.
+    fn framing_arg_count(&self, packed_num_args: u8, log_offset: u32) -> u8 {
+        if !self.config.prefer_dictionary_arg_count {
+            return packed_num_args;
+        }
+        match self.dictionary.get(&log_offset) {
+            Some(entry) if entry.num_args != packed_num_args && self.consistent_arg_counts.contains(&log_offset) => entry.num_args,
+            _ => packed_num_args,
+        }
+    }
+
+    /// Count a decoded record that was framed with its entry's argument count, warning the
+    /// first time each offset is corrected
+    fn note_corrected_arg_count(&self, log_offset: u32, declared_num_args: u8, log_entry: &LogEntry) {
+        self.corrected_arg_counts.fetch_add(1, Ordering::Relaxed);
+        if self.corrected_arg_offsets.lock().unwrap().insert(log_offset) {
+            warning!(self.config, "Warning: Records at offset 0x{:X} declare {} arguments, reading the dictionary's {}",
+                     log_offset, declared_num_args, log_entry.num_args);
+        }
+    }
+
+    /// Read a 32-bit word in the configured byte order at `offset`
+    fn read_word(&self, data: &[u8], offset: usize) -> Option<u32> {
+        data.get(offset..offset + 4)
+            .map(|bytes| self.config.byte_order.read_u32([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read the arguments following a record header at `offset`, returning them with the
+    /// offset where the record ends, or `None` if the record is incomplete.
+    ///
+    /// The placeholders of the dictionary entry at `log_offset` say which arguments are
+    /// inline strings; records matching no entry are read as plain words.
+    fn read_arguments(&self, data: &[u8], offset: usize, num_args: usize, log_offset: u32) -> Option<(Vec<Arg>, usize)> {
+        match self.config.record_layout {
+            RecordLayout::Variable => {
+                let mut slots = self.string_arg_slots.get(&log_offset).into_iter().flatten();
+                let mut arguments = Vec::with_capacity(num_args);
+                let mut end = offset;
+                while arguments.len() < num_args {
+                    match slots.next() {
+                        Some(ArgSlot::Str) => {
+                            let (arg, arg_end) = self.read_string_argument(data, end)?;
+                            arguments.push(arg);
+                            end = arg_end;
+                        }
+                        Some(ArgSlot::WideStr) => loop {
+                            let (word, arg_len) = self.read_argument(data, end)?;
+                            arguments.push(Arg::U32(word));
+                            end += arg_len;
+                            let has_nul = word as u16 == 0 || (word >> 16) as u16 == 0;
+                            if has_nul || arguments.len() == num_args {
+                                break;
+                            }
+                        },
+                        Some(ArgSlot::Word) | None => {
+                            let (word, arg_len) = self.read_argument(data, end)?;
+                            arguments.push(Arg::U32(word));
+                            end += arg_len;
+                        }
+                    }
+                }
+                Some((arguments, end))
+            }
+            RecordLayout::Fixed { arg_slots } => {
+                let end = offset + 4 * arg_slots;
+                data.get(offset..end)?;
+                let arguments = (0..num_args.min(arg_slots))
+                    .filter_map(|slot| self.read_word(data, offset + 4 * slot))
+                    .map(Arg::U32)
+                    .collect();
+                Some((arguments, end))
+            }
+        }
+    }
+
+    /// Read an inline string: its length as one argument in the configured encoding, then
+    /// the bytes, padded to a 4-byte boundary with 32-bit arguments so records stay aligned.
+    /// A length over `MAX_STRING_ARG_LEN` means the record is corrupt, so only the length is consumed.
+    fn read_string_argument(&self, data: &[u8], offset: usize) -> Option<(Arg, usize)> {
+        let (len, len_bytes) = self.read_argument(data, offset)?;
+        let start = offset + len_bytes;
+        if len as usize > MAX_STRING_ARG_LEN {
+            return Some((Arg::Str(format!("<invalid string length {}>", len)), start));
+        }
+
+        let bytes = data.get(start..start + len as usize)?;
+        let end = match self.config.arg_encoding {
+            ArgEncoding::Fixed32 => (start + bytes.len()).next_multiple_of(4),
+            ArgEncoding::Varint => start + bytes.len(),
+        };
+        data.get(start..end)?;
+        Some((Arg::Str(String::from_utf8_lossy(bytes).into_owned()), end))
+    }
+
+    /// Read one argument in the configured encoding, returning it with its length in bytes
+    fn read_argument(&self, data: &[u8], offset: usize) -> Option<(u32, usize)> {
+        match self.config.arg_encoding {
+            ArgEncoding::Fixed32 => self.read_word(data, offset).map(|arg| (arg, 4)),
+            ArgEncoding::Varint => Self::read_varint(data, offset),
+        }
+    }
+
+    /// Read an unsigned LEB128 varint; bits beyond 32 in a fifth byte are ignored
+    fn read_varint(data: &[u8], offset: usize) -> Option<(u32, usize)> {
+        let mut value: u32 = 0;
+        for (i, &byte) in data.get(offset..)?.iter().take(MAX_VARINT_LEN).enumerate() {
+            value |= ((byte & 0x7F) as u32) << (7 * i);
+            if byte & 0x80 == 0 || i + 1 == MAX_VARINT_LEN {
+                return Some((value, i + 1));
+            }
+        }
+        None
+    }
+
     /// Parse binary entries from a chunk of data, returning entries and any remaining bytes
     fn parse_chunk(&self, data: &[u8]) -> Result<(Vec<BinaryLogEntry>, Vec<u8>)> {
+        self.parse_records(data, false)
+    }
+
+    /// Parse binary entries from `data`; `at_end` says no more data follows, so with
+    /// `resync_on_corruption` a record whose arguments would run past the end is corruption
+    /// rather than a record to complete from the next chunk
+    fn parse_records(&self, data: &[u8], at_end: bool) -> Result<(Vec<BinaryLogEntry>, Vec<u8>)> {
         let mut entries = Vec::new();
         let mut offset = 0;
 
         while offset + 8 <= data.len() {
+            let record_start = offset;
+
             // Read timestamp (32-bit)
-            let timestamp_ms = u32::from_le_bytes([
+            let timestamp_ms = self.config.byte_order.read_u32([
                 data[offset],
                 data[offset + 1], 
                 data[offset + 2],
@@ -299,7 +1417,7 @@ impl SyslogParser {
             offset += 4;
 
             // Read log_id (32-bit)
-            let log_id_raw = u32::from_le_bytes([
+            let log_id_raw = self.config.byte_order.read_u32([
                 data[offset],
                 data[offset + 1],
                 data[offset + 2], 
@@ -308,35 +1426,45 @@ impl SyslogParser {
             offset += 4;
 
             // Extract number of arguments and log offset
-            let num_args = ((log_id_raw >> 28) & 0xF) as u8;
-            let log_offset = log_id_raw & 0x0FFFFFFF;
-
-            // Check if we have enough data for all arguments
-            let args_size = num_args as usize * 4;
-            if offset + args_size > data.len() {
-                // Not enough data for arguments - return remaining data
-                let remaining = data[offset - 8..].to_vec(); // Include current entry header
-                return Ok((entries, remaining));
-            }
-
-            // Read arguments
-            let mut arguments = Vec::with_capacity(num_args as usize);
-            for _ in 0..num_args {
-                let arg = u32::from_le_bytes([
-                    data[offset],
-                    data[offset + 1],
-                    data[offset + 2],
-                    data[offset + 3],
-                ]);
-                arguments.push(arg);
-                offset += 4;
-            }
-
-            entries.push(BinaryLogEntry {
+            let (packed_num_args, log_offset, core) = self.split_log_id(log_id_raw);
+            let num_args = self.framing_arg_count(packed_num_args, log_offset);
+
+            // An unknown offset is taken as corruption: retry framing one byte further on
+            if self.config.resync_on_corruption && !self.is_known_offset(log_offset) {
+                self.skipped_bytes.fetch_add(1, Ordering::Relaxed);
+                offset = record_start + 1;
+                continue;
+            }
+
+            // Read arguments; varint arguments are variable length, so the record may
+            // only turn out to be incomplete part way through
+            let arguments = match self.read_arguments(data, offset, num_args as usize, log_offset) {
+                Some((arguments, end)) => {
+                    offset = end;
+                    arguments
+                }
+                None if at_end && self.config.resync_on_corruption => {
+                    // The capture ends before the arguments the header declares
+                    self.skipped_bytes.fetch_add(1, Ordering::Relaxed);
+                    offset = record_start + 1;
+                    continue;
+                }
+                None => {
+                    // Not enough data for arguments - return remaining data including the header
+                    return Ok((entries, data[record_start..].to_vec()));
+                }
+            };
+
+            let entry = BinaryLogEntry {
                 timestamp_ms,
+                wraps: 0,
                 log_id: log_offset,
                 arguments,
-            });
+                core,
+                declared_num_args: (num_args != packed_num_args).then_some(packed_num_args),
+            };
+            self.check_strict_offset(&entry)?;
+            entries.push(entry);
         }
 
         // Return any remaining bytes that couldn't form a complete entry
@@ -349,118 +1477,304 @@ impl SyslogParser {
         Ok((entries, remaining))
     }
 
-    /// Read and parse binary file structure (legacy method for small files)
-    fn read_binary_file_legacy<P: AsRef<Path>>(&self, path: P) -> Result<Vec<BinaryLogEntry>> {
-        let contents = fs::read(&path)
-            .with_context(|| format!("Failed to read binary file: {}", path.as_ref().display()))?;
-
-        // Pre-allocate vector with estimated capacity (each entry is min 8 bytes)
-        let mut entries = Vec::with_capacity(contents.len() / 8);
-        let mut offset = 0;
+    /// Append the arguments of continuation records to the record before them.
+    ///
+    /// The last record is held in `pending` rather than returned, since a continuation for
+    /// it may arrive with the next chunk; callers flush it once the input ends.
+    fn attach_continuations(&self, entries: Vec<BinaryLogEntry>, pending: &mut Option<BinaryLogEntry>) -> Vec<BinaryLogEntry> {
+        let Some(continuation_offset) = self.config.continuation_offset else {
+            return entries;
+        };
 
-        while offset + 8 <= contents.len() {
-            // Read timestamp (32-bit)
-            let timestamp_ms = u32::from_le_bytes([
-                contents[offset],
-                contents[offset + 1], 
-                contents[offset + 2],
-                contents[offset + 3],
-            ]);
-            offset += 4;
+        let mut attached = Vec::with_capacity(entries.len());
+        for entry in entries {
+            if entry.log_id == continuation_offset {
+                match pending.as_mut() {
+                    Some(previous) => previous.arguments.extend(entry.arguments),
+                    None => warning!(self.config, "Warning: Dropping continuation record at {}ms with no record before it", 
+                                     entry.timestamp_ms),
+                }
+            } else if let Some(previous) = pending.replace(entry) {
+                attached.push(previous);
+            }
+        }
+        attached
+    }
 
-            // Read log_id (32-bit)
-            let log_id_raw = u32::from_le_bytes([
-                contents[offset],
-                contents[offset + 1],
-                contents[offset + 2], 
-                contents[offset + 3],
-            ]);
-            offset += 4;
+    /// Turn delta timestamps into absolute ones, continuing from the last record in `previous`.
+    ///
+    /// Deltas with the top bit set would be negative as signed values and are treated as
+    /// corrupt: they are reported and the record keeps the previous timestamp.
+    fn resolve_timestamps(&self, entries: &mut [BinaryLogEntry], previous: &mut Option<u32>) {
+        if self.config.timestamp_encoding == TimestampEncoding::Absolute {
+            return;
+        }
 
-            // Extract number of arguments (first 4 bits) and log offset (remaining 28 bits)
-            let num_args = ((log_id_raw >> 28) & 0xF) as u8;
-            let log_offset = log_id_raw & 0x0FFFFFFF;
-
-            // Read arguments if any
-            let mut arguments = Vec::new();
-            for _ in 0..num_args {
-                if offset + 4 <= contents.len() {
-                    let arg = u32::from_le_bytes([
-                        contents[offset],
-                        contents[offset + 1],
-                        contents[offset + 2],
-                        contents[offset + 3],
-                    ]);
-                    arguments.push(arg);
-                    offset += 4;
-                } else {
-                    break; // Incomplete data
+        for entry in entries {
+            let delta = entry.timestamp_ms;
+            let timestamp = match *previous {
+                None => delta, // first record is absolute
+                Some(previous) if (delta as i32) < 0 => {
+                    warning!(self.config, "Warning: Negative timestamp delta {} after {}, keeping previous timestamp", 
+                             delta as i32, previous);
+                    previous
                 }
-            }
+                Some(previous) => previous.checked_add(delta).unwrap_or_else(|| {
+                    warning!(self.config, "Warning: Timestamp delta {} after {} overflows, clamping", delta, previous);
+                    u32::MAX
+                }),
+            };
+            entry.timestamp_ms = timestamp;
+            *previous = Some(timestamp);
+        }
+    }
 
-            entries.push(BinaryLogEntry {
-                timestamp_ms,
-                log_id: log_offset,
-                arguments,
-            });
+    /// Count timestamp counter wraps, continuing from the last record's raw timestamp and
+    /// wrap count in `previous`. A backward jump of more than half the counter range is a
+    /// wrap. A shorter jump that lands within `REBOOT_UPTIME_MS` of 0, and closer to 0 than
+    /// to the previous record, is a reboot and restarts the count; any other backward jump
+    /// is a glitch and keeps it.
+    fn unwrap_timestamps(&self, entries: &mut [BinaryLogEntry], previous: &mut Option<(u32, u32)>) {
+        for entry in entries {
+            entry.wraps = match *previous {
+                Some((last, wraps)) if last.saturating_sub(entry.timestamp_ms) > u32::MAX / 2 => wraps + 1,
+                Some((last, _)) if entry.timestamp_ms < last && entry.timestamp_ms < last - entry.timestamp_ms
+                    && self.config.timestamp_unit.to_millis(entry.timestamp_ms) < REBOOT_UPTIME_MS => 0,
+                Some((_, wraps)) => wraps,
+                None => 0,
+            };
+            *previous = Some((entry.timestamp_ms, entry.wraps));
         }
+    }
+
+    /// Uptime of a record in milliseconds including counter wraps
+    fn monotonic_millis(&self, entry: &BinaryLogEntry) -> u64 {
+        self.config.timestamp_unit.to_millis_u64(((entry.wraps as u64) << 32) | entry.timestamp_ms as u64)
+    }
+
+    /// Attach continuations within a complete set of records
+    fn attach_all_continuations(&self, entries: Vec<BinaryLogEntry>) -> Vec<BinaryLogEntry> {
+        let mut pending = None;
+        let mut attached = self.attach_continuations(entries, &mut pending);
+        attached.extend(pending);
+        attached
+    }
 
-        println!("Read {} binary log entries from {}", 
-                 entries.len(), path.as_ref().display());
+    /// Handle the bytes left over at the end of a capture. With `resync_on_corruption` they
+    /// are resynced into any records they still hold and the bytes too short for a record
+    /// header are skipped; otherwise they are an incomplete record, reported per the
+    /// truncation policy
+    fn finish_remainder(&self, remainder: Vec<u8>) -> Result<Vec<BinaryLogEntry>> {
+        if !self.config.resync_on_corruption {
+            self.report_truncation(remainder.len())?;
+            return Ok(Vec::new());
+        }
+        let (entries, remaining_bytes) = self.parse_records(&remainder, true)?;
+        self.skipped_bytes.fetch_add(remaining_bytes.len(), Ordering::Relaxed);
         Ok(entries)
     }
 
+    /// Report `trailing_bytes` of an incomplete record at the end of a capture per the truncation policy
+    fn report_truncation(&self, trailing_bytes: usize) -> Result<()> {
+        if trailing_bytes == 0 {
+            return Ok(());
+        }
+        match self.config.truncation_policy {
+            TruncationPolicy::Ignore => Ok(()),
+            TruncationPolicy::Warn => {
+                warning!(self.config, "Warning: {} incomplete bytes at end of file", trailing_bytes);
+                Ok(())
+            }
+            TruncationPolicy::Error => Err(DecodeError::TruncatedInput { trailing_bytes }.into()),
+        }
+    }
+
+    /// Fail once the total number of arguments exceeds the configured budget
+    fn check_arg_budget(&self, total_args: usize) -> Result<()> {
+        match self.config.max_total_args {
+            Some(max_total_args) if total_args > max_total_args => Err(anyhow::anyhow!(
+                "Argument budget exceeded: capture declares more than {} arguments in total (file may be corrupt)", 
+                max_total_args)),
+            _ => Ok(()),
+        }
+    }
+
     /// Process a single binary entry and create formatted log (updated for byte offset)
     fn process_binary_entry(&self, entry: &BinaryLogEntry, min_log_level: u8) -> Option<ParsedLog> {
+        // Gap markers are always reported so data loss is never filtered out
+        if self.config.gap_marker_offset == Some(entry.log_id) {
+            return Some(self.dropped_logs_entry(entry));
+        }
+
         // Use byte offset directly instead of modulo mapping
-        let log_entry = self.get_entry_by_byte_offset(entry.log_id)?;
+        self.records_seen.fetch_add(1, Ordering::Relaxed);
+        let log_entry = match self.get_entry_by_byte_offset(entry.log_id) {
+            Some(log_entry) => log_entry,
+            None => {
+                self.unmapped_records.fetch_add(1, Ordering::Relaxed);
+                return self.unknown_entry(entry);
+            }
+        };
+
+        if let Some(declared_num_args) = entry.declared_num_args {
+            self.note_corrected_arg_count(entry.log_id, declared_num_args, log_entry);
+        }
 
         // Filter by log level
-        if log_entry.log_level > min_log_level {
+        if !self.config.level_direction.passes_filter(log_entry.log_level, min_log_level) {
             return None;
         }
 
+        Some(self.decode_entry(entry, log_entry))
+    }
+
+    /// Format a record resolved to its dictionary entry
+    fn decode_entry(&self, entry: &BinaryLogEntry, log_entry: &LogEntry) -> ParsedLog {
         // Format timestamp
-        let timestamp_formatted = Self::format_timestamp(entry.timestamp_ms);
+        let timestamp_ms = self.config.timestamp_unit.to_millis(entry.timestamp_ms);
 
         // Format message with arguments
-        let formatted_message = self.format_message(&log_entry.log_message, &entry.arguments);
+        let mut formatted_message = self.format_message(&log_entry.log_message, &entry.arguments);
+        if self.config.raw_on_mismatch && entry.arguments.len() != log_entry.num_args as usize {
+            formatted_message.push_str(&Self::raw_mismatch_suffix(entry, log_entry));
+        }
 
-        Some(ParsedLog {
-            timestamp_formatted,
+        ParsedLog {
+            timestamp_formatted: Self::format_timestamp(timestamp_ms),
+            timestamp_ms,
+            timestamp_raw: entry.timestamp_ms,
+            timestamp_monotonic_ms: self.monotonic_millis(entry),
             log_level: log_entry.log_level,
             module_name: log_entry.module_name.clone(),
             formatted_message,
+            raw_args: entry.arguments.iter().map(Arg::word).collect(),
+            dropped_logs: None,
+            log_offset: entry.log_id,
+            core: entry.core,
+        }
+    }
+
+    /// Decode only the record starting at `byte_offset` of a capture, whatever its level.
+    ///
+    /// Fails when the offset cannot be the start of a record: misaligned, too close to the
+    /// end, or with an offset field matching no dictionary entry. In delta-encoded captures
+    /// the timestamp is the record's raw delta.
+    pub fn decode_record_at(&self, data: &[u8], byte_offset: usize) -> Result<ParsedLog> {
+        if byte_offset >= data.len() {
+            return Err(anyhow::anyhow!("Byte {} is past the end of the capture ({} bytes)", byte_offset, data.len()));
+        }
+        if byte_offset.next_multiple_of(self.record_alignment()) != byte_offset {
+            return Err(anyhow::anyhow!("Byte {} is not a record boundary: records are {}-byte aligned", 
+                                     byte_offset, self.record_alignment()));
+        }
+        let (entry, _) = self.read_record(data, byte_offset)
+            .ok_or_else(|| anyhow::anyhow!("Incomplete record at byte {}", byte_offset))?;
+
+        if self.config.gap_marker_offset == Some(entry.log_id) {
+            return Ok(self.dropped_logs_entry(&entry));
+        }
+        if self.config.continuation_offset == Some(entry.log_id) {
+            return Err(anyhow::anyhow!("Record at byte {} is a continuation of the record before it", byte_offset));
+        }
+        match self.get_entry_by_byte_offset(entry.log_id) {
+            Some(log_entry) => Ok(self.decode_entry(&entry, log_entry)),
+            None => Err(anyhow::anyhow!("Byte {} is not a record boundary: offset 0x{:08x} matches no dictionary entry", 
+                                      byte_offset, entry.log_id)),
+        }
+    }
+
+    /// Raw view of a record whose argument count disagrees with the dictionary,
+    /// e.g. ` [mismatch: record has 1 args, dictionary expects 2; raw args: 0x00000007]`
+    fn raw_mismatch_suffix(entry: &BinaryLogEntry, log_entry: &LogEntry) -> String {
+        let raw_args: Vec<String> = entry.arguments.iter()
+            .map(|arg| match arg {
+                Arg::U32(word) => format!("0x{:08x}", word),
+                Arg::Str(text) => format!("{:?}", text),
+            })
+            .collect();
+        format!(" [mismatch: record has {} args, dictionary expects {}; raw args: {}]", 
+               entry.arguments.len(), log_entry.num_args, 
+               if raw_args.is_empty() { "none".to_string() } else { raw_args.join(" ") })
+    }
+
+    /// Build the log reported for an unresolved offset, if unknown entries are kept
+    fn unknown_entry(&self, entry: &BinaryLogEntry) -> Option<ParsedLog> {
+        let (module_name, formatted_message) = self.config.unknown_entries.as_ref()?.render(entry.log_id);
+        let timestamp_ms = self.config.timestamp_unit.to_millis(entry.timestamp_ms);
+
+        Some(ParsedLog {
+            timestamp_formatted: Self::format_timestamp(timestamp_ms),
+            timestamp_ms,
+            timestamp_raw: entry.timestamp_ms,
+            timestamp_monotonic_ms: self.monotonic_millis(entry),
+            log_level: UNKNOWN_LOG_LEVEL,
+            module_name,
+            formatted_message,
+            raw_args: entry.arguments.iter().map(Arg::word).collect(),
+            dropped_logs: None,
+            log_offset: entry.log_id,
+            core: entry.core,
         })
     }
 
+    /// Build the log reported for a gap marker record
+    fn dropped_logs_entry(&self, entry: &BinaryLogEntry) -> ParsedLog {
+        let dropped_logs = entry.arguments.first().map(Arg::word);
+        let formatted_message = match dropped_logs {
+            Some(count) => format!("*** {} logs dropped ***", count),
+            None => "*** unknown number of logs dropped ***".to_string(),
+        };
+
+        let timestamp_ms = self.config.timestamp_unit.to_millis(entry.timestamp_ms);
+
+        ParsedLog {
+            timestamp_formatted: Self::format_timestamp(timestamp_ms),
+            timestamp_ms,
+            timestamp_raw: entry.timestamp_ms,
+            timestamp_monotonic_ms: self.monotonic_millis(entry),
+            log_level: self.config.level_direction.most_severe(),
+            module_name: DROPPED_LOGS_MODULE.to_string(),
+            formatted_message,
+            raw_args: entry.arguments.iter().map(Arg::word).collect(),
+            dropped_logs,
+            log_offset: entry.log_id,
+            core: entry.core,
+        }
+    }
+
     /// Format timestamp from milliseconds to readable format matching expected output
-    fn format_timestamp(timestamp_ms: u32) -> String {
+    fn format_timestamp(timestamp_ms: u64) -> String {
         format!("{}ms", timestamp_ms)
     }
 
     /// Format log message by replacing placeholders with arguments (optimized)
-    fn format_message(&self, template: &str, arguments: &[u32]) -> String {
+    fn format_message(&self, template: &str, arguments: &[Arg]) -> String {
         let mut result = template.to_string();
         let mut arg_index = 0;
+        let mut missing = 0;
 
-        // First handle consecutive hex pattern "0x%x%x%x..." (at least 2 %x) -> "0x32304644"
-        let consecutive_hex_pattern = Regex::new(r"0x%x(?:%x)+").unwrap(); // Matches 0x%x followed by at least one more %x
+        // First handle consecutive hex pattern "0x%x%x%x..." (at least 2 %x) -> "0x32304644".
+        // `%%` is matched so an escaped percent is never read as the start of a conversion
+        let consecutive_hex_pattern = Regex::new(r"%%|0x%x(?:%x)+").unwrap(); // Matches 0x%x followed by at least one more %x
         let mut replacements = Vec::new();
         
         for mat in consecutive_hex_pattern.find_iter(&result) {
             let full_match = mat.as_str();
+            if full_match == "%%" {
+                continue;
+            }
             let hex_count = full_match.matches("%x").count();
             
             if arg_index + hex_count <= arguments.len() {
                 let mut hex_string = String::from("0x");
                 for _ in 0..hex_count {
-                    hex_string.push_str(&format!("{:02X}", arguments[arg_index] & 0xFF));
+                    hex_string.push_str(&format!("{:02X}", arguments[arg_index].word() & 0xFF));
                     arg_index += 1;
                 }
                 replacements.push((mat.range(), hex_string));
             } else {
                 replacements.push((mat.range(), "<missing>".to_string()));
+                missing += 1;
             }
         }
         
@@ -469,38 +1783,179 @@ impl SyslogParser {
             result.replace_range(range, &replacement);
         }
 
-        // Now handle remaining individual placeholders
-        let combined_pattern = Regex::new(r"%(?:l{0,2}([udx])|([s]))").unwrap();
+        // Now handle remaining individual placeholders (with optional flags and width).
+        // `%n` is matched only so it is never substituted: it consumes no argument and stays literal.
+        // `%%` is a literal percent and consumes no argument either
+        let combined_pattern = Regex::new(PLACEHOLDER_PATTERN).unwrap();
         
         result = combined_pattern.replace_all(&result, |caps: &regex::Captures| {
-            let placeholder = if let Some(long_match) = caps.get(1) {
+            if &caps[0] == "%%" {
+                return "%".to_string();
+            }
+            if caps.get(5).is_some() {
+                return caps[0].to_string();
+            }
+            let flags = caps.get(1).map_or("", |m| m.as_str());
+            let width = self.parse_width(caps.get(2).map_or("", |m| m.as_str()));
+            if let Some(wide) = caps.get(6) {
+                if arg_index >= arguments.len() {
+                    missing += 1;
+                    return "<missing>".to_string();
+                }
+                let text = if wide.as_str() == "s" {
+                    Self::decode_utf16_string(arguments, &mut arg_index)
+                } else {
+                    arg_index += 1;
+                    String::from_utf16_lossy(&[arguments[arg_index - 1].word() as u16])
+                };
+                return Self::pad_field("", &text, flags, width);
+            }
+            let placeholder = if let Some(long_match) = caps.get(3) {
                 long_match.as_str()
-            } else if let Some(string_match) = caps.get(2) {
+            } else if let Some(string_match) = caps.get(4) {
                 string_match.as_str()
             } else {
                 "unknown"
             };
+
+            // 64-bit integers span two argument words, low word first
+            if is_long_long(caps) {
+                let words = arguments.get(arg_index..arg_index + 2);
+                arg_index = (arg_index + 2).min(arguments.len());
+                let (prefix, digits) = match words {
+                    Some([Arg::U32(low), Arg::U32(high)]) => Self::format_long_long(placeholder, (*high as u64) << 32 | *low as u64),
+                    Some(_) => ("", "<unknown>".to_string()),
+                    None => {
+                        missing += 1;
+                        return "<missing>".to_string();
+                    }
+                };
+                return Self::pad_field(prefix, &digits, flags, width);
+            }
             
             if arg_index < arguments.len() {
-                let value = match placeholder {
-                    "d" => arguments[arg_index].to_string(),
-                    "u" => arguments[arg_index].to_string(), 
-                    "x" => format!("0x{:X}", arguments[arg_index]),
-                    "s" => "<string>".to_string(),
-                    _ => "<unknown>".to_string(),
+                let (prefix, digits) = match (placeholder, &arguments[arg_index]) {
+                    (_, Arg::Str(text)) => ("", text.clone()),
+                    // `%d` words are two's complement; the sign goes before any zero padding
+                    ("d", Arg::U32(word)) if (*word as i32) < 0 => ("-", (*word as i32).unsigned_abs().to_string()),
+                    ("d", Arg::U32(word)) => ("", word.to_string()),
+                    ("u", Arg::U32(word)) => ("", word.to_string()), 
+                    // Like printf, only the digits: templates spell out `0x%x` when they want a prefix
+                    ("x", Arg::U32(word)) => ("", format!("{:X}", word)),
+                    // Float conversions carry the IEEE-754 bits of an f32 in the word
+                    ("f" | "g" | "e", Arg::U32(word)) => Self::format_float(placeholder, f32::from_bits(*word)),
+                    // A word where a string was expected, e.g. in fixed-size records
+                    ("s", Arg::U32(word)) => ("", Self::packed_ascii(*word)),
+                    _ => ("", "<unknown>".to_string()),
                 };
                 arg_index += 1;
-                value
+                Self::pad_field(prefix, &digits, flags, width)
             } else {
+                missing += 1;
                 "<missing>".to_string()
             }
         }).to_string();
 
+        if missing > 0 {
+            self.missing_arguments.fetch_add(missing, Ordering::Relaxed);
+        }
+        // Surplus arguments are not printed anywhere, so count them to avoid a silent desync
+        if arg_index < arguments.len() {
+            self.extra_arguments.fetch_add(1, Ordering::Relaxed);
+        }
         result
     }
 
+    /// Render a word holding up to four characters packed little-endian, up to the first NUL;
+    /// bytes that aren't printable ASCII show as `.`
+    fn packed_ascii(word: u32) -> String {
+        word.to_le_bytes().iter()
+            .take_while(|&&byte| byte != 0)
+            .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+            .collect()
+    }
+
+    /// Render an `%lld`, `%llu` or `%llx` argument, splitting off the sign like `%d`
+    fn format_long_long(conversion: &str, value: u64) -> (&'static str, String) {
+        match conversion {
+            "d" if (value as i64) < 0 => ("-", (value as i64).unsigned_abs().to_string()),
+            "x" => ("", format!("{:X}", value)),
+            _ => ("", value.to_string()),
+        }
+    }
+
+    /// Render an `%f`, `%g` or `%e` argument, splitting off the sign so zero padding goes after it.
+    /// `%f` and `%e` use printf's default of 6 decimals; `%g` prints the shortest exact value
+    fn format_float(conversion: &str, value: f32) -> (&'static str, String) {
+        let prefix = if value.is_sign_negative() && !value.is_nan() { "-" } else { "" };
+        let value = value.abs();
+        let digits = match conversion {
+            "f" => format!("{:.6}", value),
+            // Rust writes `2.15e1`; printf writes `2.15e+01`
+            "e" => {
+                let formatted = format!("{:.6e}", value);
+                let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+                format!("{}e{:+03}", mantissa, exponent.parse::<i32>().unwrap_or(0))
+            }
+            _ => value.to_string(),
+        };
+        (prefix, digits)
+    }
+
+    /// Decode a `%ls` string packed as UTF-16LE, two code units per argument word (low half
+    /// first), consuming words up to the one holding the NUL unit or the last argument
+    fn decode_utf16_string(arguments: &[Arg], arg_index: &mut usize) -> String {
+        let mut units = Vec::new();
+        while let Some(word) = arguments.get(*arg_index).map(Arg::word) {
+            *arg_index += 1;
+            let (low, high) = (word as u16, (word >> 16) as u16);
+            if low == 0 {
+                break;
+            }
+            units.push(low);
+            if high == 0 {
+                break;
+            }
+            units.push(high);
+        }
+        String::from_utf16_lossy(&units)
+    }
+
+    /// Parse a printf field width, clamping it to the configured maximum
+    fn parse_width(&self, width: &str) -> usize {
+        if width.is_empty() {
+            return 0;
+        }
+
+        // Widths too large for usize are treated as exceeding the cap
+        let requested = width.parse::<usize>().unwrap_or(usize::MAX);
+        if requested > self.config.max_format_width {
+            warning!(self.config, "Warning: Format width {} exceeds maximum of {}, clamping", 
+                     width, self.config.max_format_width);
+            return self.config.max_format_width;
+        }
+        requested
+    }
+
+    /// Pad a formatted field to the given width honoring the `-` and `0` printf flags
+    fn pad_field(prefix: &str, digits: &str, flags: &str, width: usize) -> String {
+        let len = prefix.len() + digits.len();
+        if len >= width {
+            return format!("{}{}", prefix, digits);
+        }
+
+        let fill = width - len;
+        if flags.contains('-') {
+            format!("{}{}{}", prefix, digits, " ".repeat(fill))
+        } else if flags.contains('0') {
+            format!("{}{}{}", prefix, "0".repeat(fill), digits)
+        } else {
+            format!("{}{}{}", " ".repeat(fill), prefix, digits)
+        }
+    }
+
     /// Convert log level number to descriptive string
-    fn log_level_to_string(level: u8) -> &'static str {
+    pub fn log_level_to_string(level: u8) -> &'static str {
         match level {
             0 => "Critical",
             1 => "FatalError",
@@ -513,82 +1968,805 @@ impl SyslogParser {
         }
     }
 
+    /// Convert a log level in the configured numbering to its descriptive string
+    fn level_name(&self, level: u8) -> &'static str {
+        match self.config.level_direction.to_descending(level) {
+            Some(level) => Self::log_level_to_string(level),
+            None => "Unknown",
+        }
+    }
+
     /// Get formatted output as strings for compatibility (optimized)
     pub fn format_logs(&self, logs: &[ParsedLog]) -> Vec<String> {
         self.format_logs_with_options(logs, false)
     }
 
+    /// Export logs as a JSON array of `{timestamp_ms, log_level, log_level_name, module, message}`
+    /// objects, for consumers that want structured fields rather than formatted lines
+    pub fn logs_to_json(&self, logs: &[ParsedLog]) -> Result<String> {
+        #[derive(Serialize)]
+        struct JsonLog<'a> {
+            timestamp_ms: u64,
+            log_level: u8,
+            log_level_name: &'static str,
+            module: &'a str,
+            message: &'a str,
+        }
+
+        let entries: Vec<JsonLog> = logs.iter()
+            .map(|log| JsonLog {
+                timestamp_ms: log.timestamp_ms,
+                log_level: log.log_level,
+                log_level_name: self.level_name(log.log_level),
+                module: &log.module_name,
+                message: &log.formatted_message,
+            })
+            .collect();
+        serde_json::to_string(&entries).with_context(|| "Failed to serialize logs")
+    }
+
+    /// Write logs as CSV with a `timestamp_ms,level,module,message` header, for spreadsheets.
+    ///
+    /// Fields containing a comma, quote or line break are quoted, with quotes doubled (RFC 4180)
+    pub fn write_csv<W: std::io::Write>(&self, logs: &[ParsedLog], w: &mut W) -> Result<()> {
+        writeln!(w, "timestamp_ms,level,module,message").with_context(|| "Failed to write CSV header")?;
+        for log in logs {
+            writeln!(w, "{},{},{},{}", log.timestamp_ms, log.log_level,
+                     csv_field(&log.module_name), csv_field(&log.formatted_message))
+                .with_context(|| "Failed to write CSV record")?;
+        }
+        Ok(())
+    }
+
     /// Get formatted output as strings with option to include log level
     pub fn format_logs_with_options(&self, logs: &[ParsedLog], include_log_level: bool) -> Vec<String> {
-        logs.iter().map(|log| {
-            if include_log_level {
-                format!("{:12}\t[{}]\t[{}]\t{}", 
-                       log.timestamp_formatted,
-                       Self::log_level_to_string(log.log_level),
-                       log.module_name,
-                       log.formatted_message)
-            } else {
-                format!("{:12}\t[{}]\t{}", 
-                       log.timestamp_formatted,
-                       log.module_name,
-                       log.formatted_message)
-            }
-        }).collect()
+        let options = FormatOptions {
+            include_log_level,
+            ..Default::default()
+        };
+        self.format_logs_with(logs, &options)
     }
 
-    /// Get dictionary size
-    pub fn dictionary_size(&self) -> usize {
-        self.dictionary.len()
+    /// Get formatted output as strings using the given format options
+    pub fn format_logs_with(&self, logs: &[ParsedLog], options: &FormatOptions) -> Vec<String> {
+        if options.rebase_timestamps {
+            let options = FormatOptions { rebase_timestamps: false, ..options.clone() };
+            return self.format_logs_with(&rebase_timestamps(logs), &options);
+        }
+        if options.separate_cores {
+            return self.format_logs_per_core(logs, options);
+        }
+
+        let mut lines: Vec<String> = if options.group_by_module {
+            self.format_logs_grouped(logs, options)
+        } else {
+            self.format_log_run(logs, options)
+        };
+        self.push_trailers(&mut lines, logs, options);
+        lines
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
+    /// Append the module counts and summary lines the options ask for
+    fn push_trailers(&self, lines: &mut Vec<String>, logs: &[ParsedLog], options: &FormatOptions) {
+        if !options.include_module_counts && !options.include_summary {
+            return;
+        }
+        let stats = DecodeStats::from_logs(logs);
+        if options.include_module_counts {
+            lines.push(Self::module_counts_line_from_stats(&stats));
+        }
+        if options.include_summary {
+            lines.push(self.summary_line_from_stats(&stats));
+        }
+    }
 
-    fn create_test_dictionary() -> NamedTempFile {
-        let mut temp_file = NamedTempFile::new().unwrap();
-        // Write test dictionary with NULL separators (matching real format)
-        write!(temp_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d").unwrap();
-        write!(temp_file, "\x00").unwrap();
-        write!(temp_file, "0;1;init.c:45;SYS_INIT;System started").unwrap(); 
-        write!(temp_file, "\x00").unwrap();
-        write!(temp_file, "1;2;main.c:67;MAIN_APP;Processing item %d").unwrap();
-        write!(temp_file, "\x00").unwrap();
-        temp_file.flush().unwrap();
-        temp_file
+    /// Format consecutive logs, each with its delta from the one before when deltas are enabled
+    fn format_log_run<'l>(&self, logs: impl IntoIterator<Item = &'l ParsedLog>, options: &FormatOptions) -> Vec<String> {
+        let mut previous = None;
+        logs.into_iter()
+            .map(|log| {
+                let line = self.format_log_after(log, previous, options);
+                previous = Some(log);
+                line
+            })
+            .collect()
     }
 
-    fn create_test_binary() -> Vec<u8> {
-        let mut binary_data = Vec::new();
-        
-        // Entry 1: timestamp=0, log_id=0 (0 args, byte offset 0), no arguments
-        binary_data.extend_from_slice(&0u32.to_le_bytes()); // timestamp
-        binary_data.extend_from_slice(&0u32.to_le_bytes()); // log_id (0 args, byte offset 0)
-        
-        // Entry 2: timestamp=1000, log_id with 2 args at byte offset 0 (first entry)
-        binary_data.extend_from_slice(&1000u32.to_le_bytes()); // timestamp
-        let log_id_with_args = (2u32 << 28) | 0u32; // 2 args, byte offset 0
-        binary_data.extend_from_slice(&log_id_with_args.to_le_bytes());
-        binary_data.extend_from_slice(&42u32.to_le_bytes()); // arg1
-        binary_data.extend_from_slice(&100u32.to_le_bytes()); // arg2
-        
-        // Entry 3: timestamp=2000, log_id=47 (0 args, byte offset 47 for SYS_INIT entry) 
-        binary_data.extend_from_slice(&2000u32.to_le_bytes()); // timestamp
-        binary_data.extend_from_slice(&47u32.to_le_bytes()); // log_id (0 args, byte offset 47)
-        
-        binary_data
+    /// Format logs in sections per module (sorted by name), each headed by `=== MODULE ===`
+    fn format_logs_grouped(&self, logs: &[ParsedLog], options: &FormatOptions) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (module_name, mut module_logs) in partition_by_module(logs) {
+            // Stable sort keeps capture order for entries with the same timestamp
+            module_logs.sort_by_key(|log| log.timestamp_ms);
+            lines.push(format!("=== {} ({} entries) ===", module_name, module_logs.len()));
+            lines.extend(self.format_log_run(module_logs, options));
+        }
+        lines
     }
 
-    #[test]
-    fn test_dictionary_parsing() {
+    /// Format logs in sections per core (sorted by id), each formatted with the remaining options
+    fn format_logs_per_core(&self, logs: &[ParsedLog], options: &FormatOptions) -> Vec<String> {
+        let core_options = FormatOptions { separate_cores: false, include_summary: false, include_module_counts: false, ..options.clone() };
+        let mut lines = Vec::new();
+        for (core, core_logs) in partition_by_core(logs) {
+            let core_logs: Vec<ParsedLog> = core_logs.into_iter().cloned().collect();
+            match core {
+                Some(core) => lines.push(format!("=== core {} ({} entries) ===", core, core_logs.len())),
+                None => lines.push(format!("=== no core ({} entries) ===", core_logs.len())),
+            }
+            lines.extend(self.format_logs_with(&core_logs, &core_options));
+        }
+        self.push_trailers(&mut lines, logs, options);
+        lines
+    }
+
+    /// Format each distinct log once, as its first occurrence, followed by `(xN)`
+    /// with the total number of occurrences anywhere in the capture; lines are
+    /// ordered per `options.dedup_order`
+    pub fn format_logs_unique(&self, logs: &[ParsedLog], options: &FormatOptions, unique_by: UniqueBy) -> Vec<String> {
+        if options.rebase_timestamps {
+            let options = FormatOptions { rebase_timestamps: false, ..options.clone() };
+            return self.format_logs_unique(&rebase_timestamps(logs), &options, unique_by);
+        }
+
+        #[derive(PartialEq, Eq, Hash)]
+        enum Key<'a> {
+            Message(&'a str, &'a str),
+            Offset(u32),
+        }
+
+        let mut first_seen: HashMap<Key, usize> = HashMap::new();
+        let mut unique_logs: Vec<(&ParsedLog, usize)> = Vec::new();
+
+        for log in logs {
+            let key = match unique_by {
+                UniqueBy::Message => Key::Message(&log.module_name, &log.formatted_message),
+                UniqueBy::Offset => Key::Offset(log.log_offset),
+            };
+            match first_seen.get(&key) {
+                Some(&index) => unique_logs[index].1 += 1,
+                None => {
+                    first_seen.insert(key, unique_logs.len());
+                    unique_logs.push((log, 1));
+                }
+            }
+        }
+
+        if options.dedup_order == DedupOrder::Summary {
+            // Stable sort keeps first-seen order among logs with the same count
+            unique_logs.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        }
+
+        unique_logs.iter()
+            .map(|(log, count)| format!("{}\t(x{})", self.format_log(log, options), count))
+            .collect()
+    }
+
+    /// Summarize decoded logs: entry count, time span, count per level and dropped logs
+    pub fn summary_line(&self, logs: &[ParsedLog]) -> String {
+        self.summary_line_from_stats(&DecodeStats::from_logs(logs))
+    }
+
+    /// Summary line for stats gathered while decoding, without keeping the logs
+    pub fn summary_line_from_stats(&self, stats: &DecodeStats) -> String {
+        let mut summary = format!("{} {} entries", SUMMARY_LINE_PREFIX, stats.entry_count);
+
+        if let (Some(first), Some(last)) = (stats.first_timestamp_ms, stats.last_timestamp_ms) {
+            summary.push_str(&format!(", {} to {}", Self::format_timestamp(first), Self::format_timestamp(last)));
+        }
+
+        // Gap markers are counted separately rather than under a log level
+        for (&level, count) in &stats.level_counts {
+            summary.push_str(&format!(", {}: {}", self.level_name(level), count));
+        }
+        if stats.dropped_logs > 0 {
+            summary.push_str(&format!(", {} logs dropped", stats.dropped_logs));
+        }
+
+        summary.push_str(" ===");
+        summary
+    }
+
+    /// Line with the entries per module, e.g. `=== Modules: APP: 12, NET: 3 ===`
+    pub fn module_counts_line_from_stats(stats: &DecodeStats) -> String {
+        let counts: Vec<String> = stats.module_counts.iter()
+            .map(|(module_name, count)| format!("{}: {}", module_name, count))
+            .collect();
+        if counts.is_empty() {
+            return format!("{} none ===", MODULE_COUNTS_LINE_PREFIX);
+        }
+        format!("{} {} ===", MODULE_COUNTS_LINE_PREFIX, counts.join(", "))
+    }
+
+    /// Format a single parsed log as a line of text
+    pub fn format_log(&self, log: &ParsedLog, options: &FormatOptions) -> String {
+        self.format_log_after(log, None, options)
+    }
+
+    /// Format a log that follows `previous` on screen, which its delta is measured from
+    fn format_log_after(&self, log: &ParsedLog, previous: Option<&ParsedLog>, options: &FormatOptions) -> String {
+        let bracket = |field: &str| match options.bracket_style {
+            BracketStyle::Square => format!("[{}]", field),
+            BracketStyle::Plain => field.to_string(),
+        };
+
+        let mut fields = Vec::with_capacity(5);
+        match options.field_separator {
+            None => fields.push(format!("{:12}", log.timestamp_formatted)),
+            Some(_) => fields.push(log.timestamp_formatted.clone()),
+        }
+        if options.include_deltas {
+            let delta_ms = previous.map_or(0, |previous| log.timestamp_ms as i64 - previous.timestamp_ms as i64);
+            fields.push(format!("({:+}ms)", delta_ms));
+        }
+        if options.include_log_level {
+            fields.push(bracket(self.level_name(log.log_level)));
+        }
+        fields.push(bracket(&log.module_name));
+        fields.push(log.formatted_message.clone());
+        if options.include_offset {
+            fields.push(format!("{{offset=0x{:X}}}", log.log_offset));
+        }
+
+        fields.join(options.field_separator.as_deref().unwrap_or("\t"))
+    }
+
+    /// Get dictionary size
+    pub fn dictionary_size(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    /// Iterate over the dictionary as `(byte_offset, entry)` pairs in offset order
+    pub fn entries(&self) -> impl Iterator<Item = (u32, &LogEntry)> {
+        let mut entries: Vec<(u32, &LogEntry)> = self.dictionary.iter().map(|(&offset, entry)| (offset, entry)).collect();
+        entries.sort_unstable_by_key(|&(offset, _)| offset);
+        entries.into_iter()
+    }
+}
+
+/// Follow-mode decoder that keeps partial records between pushes
+pub struct StreamDecoder<'a> {
+    parser: &'a SyslogParser,
+    min_log_level: u8,
+    // Bytes of a record that was split across pushes
+    remainder: Vec<u8>,
+    // Last complete record when continuations are enabled, until the next record shows
+    // whether it continues
+    pending_entry: Option<BinaryLogEntry>,
+    // Absolute raw timestamp of the last record, for delta-encoded captures
+    previous_timestamp: Option<u32>,
+    // Raw timestamp and counter wraps of the last record
+    previous_wrap: Option<(u32, u32)>,
+    // Complete records decoded so far, for checkpoints
+    records_decoded: usize,
+    total_args: usize,
+}
+
+impl StreamDecoder<'_> {
+    /// Feed the next piece of the capture and return the logs completed by it
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<ParsedLog>> {
+        let mut chunk_data = std::mem::take(&mut self.remainder);
+        chunk_data.extend_from_slice(data);
+
+        let (mut entries, remaining_bytes) = self.parser.parse_chunk(&chunk_data)?;
+        self.parser.resolve_timestamps(&mut entries, &mut self.previous_timestamp);
+        self.parser.unwrap_timestamps(&mut entries, &mut self.previous_wrap);
+        self.records_decoded += entries.len();
+        let entries = self.parser.attach_continuations(entries, &mut self.pending_entry);
+        self.remainder = remaining_bytes;
+        self.total_args += entries.iter().map(|entry| entry.arguments.len()).sum::<usize>();
+        self.parser.check_arg_budget(self.total_args)?;
+
+        Ok(entries.iter()
+            .filter_map(|entry| self.parser.process_binary_entry(entry, self.min_log_level))
+            .collect())
+    }
+
+    /// Number of bytes held back waiting for the rest of a record
+    pub fn pending_bytes(&self) -> usize {
+        self.remainder.len()
+    }
+
+    /// End the stream: resolve the bytes still held back as `parse_binary` does at the end of
+    /// a file, then decode them along with the record held back for a possible continuation
+    pub fn finish(&mut self) -> Result<Vec<ParsedLog>> {
+        let mut entries = self.parser.finish_remainder(std::mem::take(&mut self.remainder))?;
+        self.parser.resolve_timestamps(&mut entries, &mut self.previous_timestamp);
+        self.parser.unwrap_timestamps(&mut entries, &mut self.previous_wrap);
+        self.records_decoded += entries.len();
+        let mut entries = self.parser.attach_continuations(entries, &mut self.pending_entry);
+        entries.extend(self.pending_entry.take());
+        self.total_args += entries.iter().map(|entry| entry.arguments.len()).sum::<usize>();
+        self.parser.check_arg_budget(self.total_args)?;
+
+        Ok(entries.iter()
+            .filter_map(|entry| self.parser.process_binary_entry(entry, self.min_log_level))
+            .collect())
+    }
+}
+
+/// Lazy decoder behind `SyslogParser::parse_binary_iter`
+struct BinaryLogIter<'a> {
+    parser: &'a SyslogParser,
+    min_log_level: u8,
+    reader: BufReader<File>,
+    buffer: Vec<u8>,
+    // Bytes of a record that was split across chunks
+    remainder: Vec<u8>,
+    // Records of the current chunk not yet decoded
+    entries: std::collections::VecDeque<BinaryLogEntry>,
+    // Last record of the previous chunk, held until we know no continuation follows it
+    pending_entry: Option<BinaryLogEntry>,
+    // Absolute raw timestamp of the last record, for delta-encoded captures
+    previous_timestamp: Option<u32>,
+    // Raw timestamp and counter wraps of the last record
+    previous_wrap: Option<(u32, u32)>,
+    total_entries: usize,
+    total_args: usize,
+    total_bytes: u64,
+    finished: bool,
+}
+
+impl BinaryLogIter<'_> {
+    /// Queue the records of the next chunk, or the held back last record at the end of the file
+    fn read_chunk(&mut self) -> Result<()> {
+        let bytes_read = self.reader.read(&mut self.buffer)
+            .with_context(|| "Failed to read from binary file")?;
+
+        let entries = if bytes_read == 0 {
+            self.finished = true;
+            let mut entries = self.parser.finish_remainder(std::mem::take(&mut self.remainder))?;
+            self.parser.resolve_timestamps(&mut entries, &mut self.previous_timestamp);
+            self.parser.unwrap_timestamps(&mut entries, &mut self.previous_wrap);
+            let mut entries = self.parser.attach_continuations(entries, &mut self.pending_entry);
+            entries.extend(self.pending_entry.take());
+            entries
+        } else {
+            self.total_bytes += bytes_read as u64;
+            let mut chunk_data = std::mem::take(&mut self.remainder);
+            chunk_data.extend_from_slice(&self.buffer[..bytes_read]);
+
+            let (mut entries, remaining_bytes) = self.parser.parse_chunk(&chunk_data)?;
+            self.remainder = remaining_bytes;
+            self.parser.resolve_timestamps(&mut entries, &mut self.previous_timestamp);
+            self.parser.unwrap_timestamps(&mut entries, &mut self.previous_wrap);
+            self.parser.attach_continuations(entries, &mut self.pending_entry)
+        };
+
+        self.total_args += entries.iter().map(|entry| entry.arguments.len()).sum::<usize>();
+        self.parser.check_arg_budget(self.total_args)?;
+        let previous_total = self.total_entries;
+        self.total_entries += entries.len();
+        if self.total_entries / PROGRESS_REPORT_INTERVAL > previous_total / PROGRESS_REPORT_INTERVAL {
+            status!(self.parser.config, "Processed {} entries...", self.total_entries);
+        }
+        self.parser.report_progress(self.total_entries, self.total_bytes);
+        self.entries.extend(entries);
+        Ok(())
+    }
+}
+
+impl Iterator for BinaryLogIter<'_> {
+    type Item = Result<ParsedLog>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(entry) = self.entries.pop_front() {
+                if let Some(parsed_log) = self.parser.process_binary_entry(&entry, self.min_log_level) {
+                    return Some(Ok(parsed_log));
+                }
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(e) = self.read_chunk() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Small deterministic xorshift generator for synthetic test data
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift, so nudge it
+        Self(if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+}
+
+/// Shift timestamps so the first entry is at 0ms and the rest are relative to it.
+///
+/// A return to 0ms is a reboot (the rule `session::parse_log_sessions` splits on), so the
+/// following boot cycle is left relative to its own start rather than the first entry.
+pub fn rebase_timestamps(logs: &[ParsedLog]) -> Vec<ParsedLog> {
+    let mut base = logs.first().map_or(0, |log| log.timestamp_ms);
+    logs.iter()
+        .map(|log| {
+            if log.timestamp_ms == 0 {
+                base = 0;
+            }
+            let timestamp_ms = log.timestamp_ms.saturating_sub(base);
+            ParsedLog {
+                timestamp_formatted: SyslogParser::format_timestamp(timestamp_ms),
+                timestamp_ms,
+                ..log.clone()
+            }
+        })
+        .collect()
+}
+
+/// Group logs by the core that wrote them, keeping capture order within each core.
+///
+/// Timestamps of different cores interleave non-monotonically in a shared buffer; format
+/// and split sessions (`session::parse_log_sessions`) per core so one core's entries are
+/// not reported as timestamp regressions of another's.
+pub fn partition_by_core(logs: &[ParsedLog]) -> std::collections::BTreeMap<Option<u8>, Vec<&ParsedLog>> {
+    let mut cores: std::collections::BTreeMap<Option<u8>, Vec<&ParsedLog>> = std::collections::BTreeMap::new();
+    for log in logs {
+        cores.entry(log.core).or_default().push(log);
+    }
+    cores
+}
+
+/// Group logs by module name, keeping capture order within each module
+pub fn partition_by_module(logs: &[ParsedLog]) -> std::collections::BTreeMap<&str, Vec<&ParsedLog>> {
+    let mut modules: std::collections::BTreeMap<&str, Vec<&ParsedLog>> = std::collections::BTreeMap::new();
+    for log in logs {
+        modules.entry(log.module_name.as_str()).or_default().push(log);
+    }
+    modules
+}
+
+/// Split raw dictionary contents on NULL separators into trimmed, non-empty lines
+/// paired with the byte offset where each one starts
+fn split_dictionary(contents: &[u8]) -> Vec<(u32, String)> {
+    let mut lines = Vec::new();
+    let mut start_pos = 0;
+    let separators = contents.iter().enumerate().filter(|(_, &b)| b == 0x00).map(|(i, _)| i);
+    // The last entry may not be NULL-terminated
+    for end_pos in separators.chain(std::iter::once(contents.len())) {
+        if start_pos < end_pos {
+            let line = String::from_utf8_lossy(&contents[start_pos..end_pos]);
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                lines.push((start_pos as u32, trimmed.to_string()));
+            }
+        }
+        start_pos = end_pos + 1; // Skip the NULL character
+    }
+    lines
+}
+
+/// A problem found while validating a dictionary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Byte offset of the offending entry in the dictionary
+    pub byte_offset: u32,
+    pub kind: ValidationIssueKind,
+    pub message: String,
+}
+
+/// Category of a dictionary `ValidationIssue`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationIssueKind {
+    /// The line could not be parsed as `num_args;log_level;source;module;message`
+    MalformedLine,
+    /// The message's placeholder count differs from the declared `num_args`
+    ArgCountMismatch,
+    /// No entries could be parsed at all
+    EmptyDictionary,
+    /// Two entries claim the same byte offset, so records at it decode with the later one.
+    /// Entries are keyed by where they start, even across merged files, so loading never
+    /// reports it today
+    DuplicateOffset,
+}
+
+/// Result of validating a dictionary without decoding a binary against it
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryValidation {
+    pub entry_count: usize,
+    /// Distinct module names, sorted
+    pub modules: Vec<String>,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Integrity counts for a loaded dictionary, from `SyslogParser::validate`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DictionaryReport {
+    /// Entries that parsed and can be decoded
+    pub total_entries: usize,
+    /// Lines dropped because they could not be parsed
+    pub malformed_lines: usize,
+    /// Offsets claimed by more than one entry; always 0 for byte-offset dictionaries, whose
+    /// entries each start at their own byte
+    pub duplicate_offsets: usize,
+    /// Entries whose message placeholders disagree with their declared `num_args`
+    pub arg_count_mismatches: usize,
+}
+
+impl DictionaryReport {
+    pub fn problems(&self) -> usize {
+        self.malformed_lines + self.duplicate_offsets + self.arg_count_mismatches
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.problems() == 0
+    }
+}
+
+/// Placeholder count of a message when it disagrees with the declared argument count.
+///
+/// A `%ls` string takes one or more argument words, so with one the count is only a minimum
+fn arg_count_mismatch(entry: &LogEntry) -> Option<usize> {
+    slot_count_mismatch(&argument_slots(&entry.log_message), entry.num_args)
+}
+
+/// `arg_count_mismatch` for a template already split into argument slots
+fn slot_count_mismatch(slots: &[ArgSlot], num_args: u8) -> Option<usize> {
+    let mismatch = if slots.contains(&ArgSlot::WideStr) {
+        slots.len() > num_args as usize
+    } else {
+        slots.len() != num_args as usize
+    };
+    mismatch.then_some(slots.len())
+}
+
+/// Quote a CSV field if it needs it, doubling any quotes inside
+fn csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+/// Whether a placeholder matched by the `format_message` pattern is `%lld`, `%llu` or `%llx`,
+/// which take a 64-bit value in two argument words
+fn is_long_long(caps: &regex::Captures) -> bool {
+    caps.get(3).is_some_and(|conversion| matches!(conversion.as_str(), "d" | "u" | "x"))
+        && caps[0][..caps[0].len() - 1].ends_with("ll")
+}
+
+/// Whether a message has a `%n` conversion, in any of the forms `format_message` leaves
+/// unformatted (`%ln`, `%5n`, ...); `placeholder_pattern` is `PLACEHOLDER_PATTERN` compiled
+fn uses_percent_n(placeholder_pattern: &Regex, template: &str) -> bool {
+    placeholder_pattern.captures_iter(template).any(|caps| caps.get(5).is_some())
+}
+
+/// How the placeholders of a dictionary message consume the argument stream, in order
+fn argument_slots(template: &str) -> Vec<ArgSlot> {
+    // Same placeholders `format_message` substitutes arguments into; `%n` and `%%` consume none
+    let placeholder_pattern = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+    placeholder_pattern.captures_iter(template)
+        .filter(|caps| &caps[0] != "%%" && caps.get(5).is_none())
+        .flat_map(|caps| match (caps.get(4), caps.get(6).map(|m| m.as_str())) {
+            (Some(_), _) => vec![ArgSlot::Str],
+            (_, Some("s")) => vec![ArgSlot::WideStr],
+            _ if is_long_long(&caps) => vec![ArgSlot::Word, ArgSlot::Word],
+            _ => vec![ArgSlot::Word],
+        })
+        .collect()
+}
+
+/// Check raw dictionary contents for malformed lines and argument count mismatches.
+///
+/// A leading UTF-8 BOM is skipped the same way `SyslogParser` does when loading.
+pub fn validate_dictionary_bytes(raw_contents: &[u8]) -> DictionaryValidation {
+    let contents = raw_contents.strip_prefix(UTF8_BOM).unwrap_or(raw_contents);
+
+    let mut entry_count = 0;
+    let mut modules = std::collections::BTreeSet::new();
+    let mut issues = Vec::new();
+
+    for (byte_offset, line) in split_dictionary(contents) {
+        let entry = match SyslogParser::parse_dictionary_line(&line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                issues.push(ValidationIssue {
+                    byte_offset,
+                    kind: ValidationIssueKind::MalformedLine,
+                    message: format!("Failed to parse line '{}': {}", line, e),
+                });
+                continue;
+            }
+        };
+
+        if let Some(slots) = arg_count_mismatch(&entry) {
+            issues.push(ValidationIssue {
+                byte_offset,
+                kind: ValidationIssueKind::ArgCountMismatch,
+                message: format!("Entry declares {} arguments but its message takes {} argument words", 
+                                 entry.num_args, slots),
+            });
+        }
+
+        entry_count += 1;
+        modules.insert(entry.module_name);
+    }
+
+    if entry_count == 0 {
+        issues.push(ValidationIssue {
+            byte_offset: 0,
+            kind: ValidationIssueKind::EmptyDictionary,
+            message: "Dictionary contains no entries (empty or corrupt)".to_string(),
+        });
+    }
+
+    DictionaryValidation {
+        entry_count,
+        modules: modules.into_iter().collect(),
+        issues,
+    }
+}
+
+/// Parse a line produced by `format_log` with the default tab layout back into a `ParsedLog`.
+///
+/// Accepts `Nms\t[MODULE]\tmsg` and `Nms\t[LEVEL]\t[MODULE]\tmsg`, with an optional
+/// trailing `{offset=0x..}` field. Lines without a level field get `u8::MAX` as their
+/// level, and a missing offset is reported as 0. Returns `None` for anything else,
+/// such as banner or summary lines.
+pub fn parse_formatted_line(line: &str) -> Option<ParsedLog> {
+    let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+    if fields.len() < 3 {
+        return None;
+    }
+
+    let timestamp_ms: u64 = fields[0].trim().strip_suffix("ms")?.parse().ok()?;
+    let unbracket = |field: &str| field.strip_prefix('[')?.strip_suffix(']').map(str::to_string);
+
+    // A level field is only present when it is followed by a bracketed module
+    let level = match (unbracket(fields[1]), fields.get(3)) {
+        (Some(name), Some(_)) if unbracket(fields[2]).is_some() => log_level_from_string(&name),
+        _ => None,
+    };
+    let (log_level, rest) = match level {
+        Some(level) => (level, &fields[2..]),
+        None => (u8::MAX, &fields[1..]),
+    };
+    let module_name = unbracket(rest[0])?;
+
+    let mut message_fields = &rest[1..];
+    let mut log_offset = 0;
+    if let Some((last, init)) = message_fields.split_last() {
+        let offset = last.strip_prefix("{offset=0x")
+            .and_then(|hex| hex.strip_suffix('}'))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+        if let (Some(offset), false) = (offset, init.is_empty()) {
+            log_offset = offset;
+            message_fields = init;
+        }
+    }
+    let formatted_message = message_fields.join("\t");
+
+    let dropped_logs = if module_name == DROPPED_LOGS_MODULE {
+        formatted_message.strip_prefix("*** ")
+            .and_then(|message| message.strip_suffix(" logs dropped ***"))
+            .and_then(|count| count.parse().ok())
+    } else {
+        None
+    };
+
+    Some(ParsedLog {
+        timestamp_formatted: SyslogParser::format_timestamp(timestamp_ms),
+        timestamp_ms,
+        log_level,
+        module_name,
+        formatted_message,
+        raw_args: Vec::new(),
+        dropped_logs,
+        log_offset,
+        core: None,
+        timestamp_raw: timestamp_ms as u32,
+        timestamp_monotonic_ms: timestamp_ms,
+    })
+}
+
+/// Convert a descriptive log level name back to its number in descending numbering
+fn log_level_from_string(name: &str) -> Option<u8> {
+    (0..=MAX_NAMED_LOG_LEVEL).find(|&level| SyslogParser::log_level_to_string(level) == name)
+}
+
+/// Serialize dictionary entries into the NULL-separated dictionary format.
+///
+/// Each entry is written at its byte offset so log_ids in existing binaries keep
+/// resolving to the same entries; gaps between entries are filled with NULL bytes,
+/// which `load_dictionary` skips. Fails if an entry would overlap the next offset.
+pub fn serialize_dictionary(entries: &[(u32, LogEntry)]) -> Result<Vec<u8>> {
+    let mut sorted: Vec<&(u32, LogEntry)> = entries.iter().collect();
+    sorted.sort_by_key(|(offset, _)| *offset);
+
+    let mut output = Vec::new();
+    for (offset, entry) in sorted {
+        let offset = *offset as usize;
+        if offset < output.len() {
+            return Err(anyhow::anyhow!("Dictionary entry at byte {} overlaps the previous entry (ends at byte {})", 
+                                     offset, output.len()));
+        }
+
+        output.resize(offset, 0x00);
+        output.extend_from_slice(entry.to_dictionary_line().as_bytes());
+        output.push(0x00);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_dictionary() -> NamedTempFile {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        // Write test dictionary with NULL separators (matching real format)
+        write!(temp_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d").unwrap();
+        write!(temp_file, "\x00").unwrap();
+        write!(temp_file, "0;1;init.c:45;SYS_INIT;System started").unwrap(); 
+        write!(temp_file, "\x00").unwrap();
+        write!(temp_file, "1;2;main.c:67;MAIN_APP;Processing item %d").unwrap();
+        write!(temp_file, "\x00").unwrap();
+        temp_file.flush().unwrap();
+        temp_file
+    }
+
+    fn word_args(words: &[u32]) -> Vec<Arg> {
+        words.iter().copied().map(Arg::U32).collect()
+    }
+
+    fn create_test_binary() -> Vec<u8> {
+        let mut binary_data = Vec::new();
+        
+        // Entry 1: timestamp=0, log_id=0 (0 args, byte offset 0), no arguments
+        binary_data.extend_from_slice(&0u32.to_le_bytes()); // timestamp
+        binary_data.extend_from_slice(&0u32.to_le_bytes()); // log_id (0 args, byte offset 0)
+        
+        // Entry 2: timestamp=1000, log_id with 2 args at byte offset 0 (first entry)
+        binary_data.extend_from_slice(&1000u32.to_le_bytes()); // timestamp
+        let log_id_with_args = 2u32 << 28; // 2 args, byte offset 0
+        binary_data.extend_from_slice(&log_id_with_args.to_le_bytes());
+        binary_data.extend_from_slice(&42u32.to_le_bytes()); // arg1
+        binary_data.extend_from_slice(&100u32.to_le_bytes()); // arg2
+        
+        // Entry 3: timestamp=2000, log_id=47 (0 args, byte offset 47 for SYS_INIT entry) 
+        binary_data.extend_from_slice(&2000u32.to_le_bytes()); // timestamp
+        binary_data.extend_from_slice(&47u32.to_le_bytes()); // log_id (0 args, byte offset 47)
+        
+        binary_data
+    }
+
+    #[test]
+    fn test_dictionary_parsing() {
         let dict_file = create_test_dictionary();
         let parser = SyslogParser::new(dict_file.path()).unwrap();
         assert_eq!(parser.dictionary_size(), 3);
     }
 
+    #[test]
+    fn test_dictionary_entries() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let entries: Vec<(u32, &str)> = parser.entries().map(|(offset, entry)| (offset, entry.module_name.as_str())).collect();
+        assert_eq!(entries, [(0, "TEST_MODULE"), (47, "SYS_INIT"), (85, "MAIN_APP")]);
+    }
+
     #[test]
     fn test_binary_parsing() {
         let dict_file = create_test_dictionary();
@@ -623,16 +2801,16 @@ mod tests {
         let parser = SyslogParser::new(dict_file.path()).unwrap();
         
         let args = vec![42, 100];
-        let result = parser.format_message("Trigger no %d at %d", &args);
+        let result = parser.format_message("Trigger no %d at %d", &word_args(&args));
         assert_eq!(result, "Trigger no 42 at 100");
         
         // Test with missing arguments
-        let result = parser.format_message("Value %d and %d", &vec![42]);
+        let result = parser.format_message("Value %d and %d", &word_args(&[42]));
         assert_eq!(result, "Value 42 and <missing>");
         
         // Test with hex formatting
-        let result = parser.format_message("Address 0x%x", &vec![255]);
-        assert_eq!(result, "Address 0x0xFF");
+        let result = parser.format_message("Address 0x%x", &word_args(&[255]));
+        assert_eq!(result, "Address 0xFF");
     }
 
     #[test]
@@ -669,6 +2847,80 @@ mod tests {
         assert!(formatted[1].contains("Trigger no 42 at 100"));
     }
 
+    #[test]
+    fn test_logs_to_json() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&parser.logs_to_json(&parsed_logs).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 3);
+        assert_eq!(json[1], serde_json::json!({
+            "timestamp_ms": 1000,
+            "log_level": 4,
+            "log_level_name": "Info",
+            "module": "TEST_MODULE",
+            "message": "Trigger no 42 at 100",
+        }));
+        assert_eq!(parser.logs_to_json(&[]).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let mut parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        parsed_logs[0].formatted_message = "Read \"cfg\", retrying\nonce".to_string();
+        parsed_logs.truncate(2);
+
+        let mut csv = Vec::new();
+        parser.write_csv(&parsed_logs, &mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "timestamp_ms,level,module,message\n\
+                                                     0,4,TEST_MODULE,\"Read \"\"cfg\"\", retrying\nonce\"\n\
+                                                     1000,4,TEST_MODULE,Trigger no 42 at 100\n");
+    }
+
+    #[test]
+    fn test_dictionary_from_multiple_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let net_path = temp_dir.path().join("net.log");
+        let app_path = temp_dir.path().join("app.log");
+        std::fs::write(&net_path, "0;3;net.c:10;NET;Link up\x001;3;net.c:20;NET;Retry %d\x00").unwrap();
+        std::fs::write(&app_path, "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+
+        // app.log starts right after net.log's 51 bytes
+        let parser = SyslogParser::from_paths(&[net_path, app_path]).unwrap();
+        assert_eq!(parser.dictionary_size(), 3);
+        assert_eq!(parser.get_entry_by_byte_offset(0).unwrap().log_message, "Link up");
+        assert_eq!(parser.get_entry_by_byte_offset(25).unwrap().log_message, "Retry %d");
+        assert_eq!(parser.get_entry_by_byte_offset(51).unwrap().module_name, "SYS_INIT");
+        assert!(parser.get_entry_by_byte_offset(25 + 51).is_none());
+        // Both files have an entry at their own byte 0, but they no longer collide once merged
+        assert_eq!(parser.validate().duplicate_offsets, 0);
+
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&0u32.to_le_bytes());
+        binary_data.extend_from_slice(&51u32.to_le_bytes());
+        binary_data.extend_from_slice(&10u32.to_le_bytes());
+        binary_data.extend_from_slice(&(1u32 << 28 | 25).to_le_bytes());
+        binary_data.extend_from_slice(&2u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let messages: Vec<_> = parsed_logs.iter().map(|log| log.formatted_message.as_str()).collect();
+        assert_eq!(messages, ["System started", "Retry 2"]);
+    }
+
     #[test]
     fn test_byte_offset_mapping() {
         let dict_file = create_test_dictionary();
@@ -743,6 +2995,7 @@ mod tests {
         assert_eq!(SyslogParser::log_level_to_string(5), "Debug");
         assert_eq!(SyslogParser::log_level_to_string(6), "Verbose");
         assert_eq!(SyslogParser::log_level_to_string(255), "Unknown"); // Test unknown level
+        assert_eq!(SyslogParser::log_level_to_string(DEFAULT_LOG_LEVEL), "Info");
     }
 
     #[test]
@@ -751,19 +3004,19 @@ mod tests {
         let parser = SyslogParser::new(dict_file.path()).unwrap();
         
         // Test %u (unsigned) formatting
-        let result = parser.format_message("Date time set rcvd: %u", &vec![1234567890]);
+        let result = parser.format_message("Date time set rcvd: %u", &word_args(&[1234567890]));
         assert_eq!(result, "Date time set rcvd: 1234567890");
         
         // Test %lu (long unsigned) formatting
-        let result = parser.format_message("Free space in workspace volume : (%lu kb / %lu kb)", &vec![1024, 2048]);
+        let result = parser.format_message("Free space in workspace volume : (%lu kb / %lu kb)", &word_args(&[1024, 2048]));
         assert_eq!(result, "Free space in workspace volume : (1024 kb / 2048 kb)");
         
         // Test mixed placeholders including %lu
-        let result = parser.format_message("Event %d at time %u with status 0x%x and size %lu", &vec![42, 1234567890, 255, 1024]);
-        assert_eq!(result, "Event 42 at time 1234567890 with status 0x0xFF and size 1024");
+        let result = parser.format_message("Event %d at time %u with status 0x%x and size %lu", &word_args(&[42, 1234567890, 255, 1024]));
+        assert_eq!(result, "Event 42 at time 1234567890 with status 0xFF and size 1024");
         
         // Test %lu with missing argument
-        let result = parser.format_message("Size: %lu", &vec![]);
+        let result = parser.format_message("Size: %lu", &word_args(&[]));
         assert_eq!(result, "Size: <missing>");
     }
 
@@ -782,22 +3035,28 @@ mod tests {
         let parser = SyslogParser::new(dict_file.path()).unwrap();
         
         // Test various long format specifiers
-        let result = parser.format_message("Long unsigned: %lu", &vec![4294967295]);
+        let result = parser.format_message("Long unsigned: %lu", &word_args(&[4294967295]));
         assert_eq!(result, "Long unsigned: 4294967295");
         
-        let result = parser.format_message("Long decimal: %ld", &vec![123456]);
+        let result = parser.format_message("Long decimal: %ld", &word_args(&[123456]));
         assert_eq!(result, "Long decimal: 123456");
         
-        let result = parser.format_message("Long hex: %lx", &vec![255]);
-        assert_eq!(result, "Long hex: 0xFF");
+        let result = parser.format_message("Long hex: %lx", &word_args(&[255]));
+        assert_eq!(result, "Long hex: FF");
         
-        // Test double long format specifiers (should also work)
-        let result = parser.format_message("Long long: %llu", &vec![9999]);
+        // Double long specifiers take a 64-bit value in two words, low word first
+        let result = parser.format_message("Long long: %llu", &word_args(&[9999, 0]));
         assert_eq!(result, "Long long: 9999");
+        let result = parser.format_message("Uptime %llu ms, then %d", &word_args(&[0, 1, 7]));
+        assert_eq!(result, "Uptime 4294967296 ms, then 7");
+        let result = parser.format_message("%lld %llx", &word_args(&[u32::MAX - 1, u32::MAX, 0xDEADBEEF, 0x1]));
+        assert_eq!(result, "-2 1DEADBEEF");
+        let result = parser.format_message("Counter %llu", &word_args(&[5]));
+        assert_eq!(result, "Counter <missing>");
         
         // Test mixed format specifiers
-        let result = parser.format_message("Values: %d %u %x %lu %ld", &vec![1, 2, 3, 4, 5]);
-        assert_eq!(result, "Values: 1 2 0x3 4 5");
+        let result = parser.format_message("Values: %d %u %x %lu %ld", &word_args(&[1, 2, 3, 4, 5]));
+        assert_eq!(result, "Values: 1 2 3 4 5");
     }
 
     #[test]
@@ -806,15 +3065,1502 @@ mod tests {
         let parser = SyslogParser::new(dict_file.path()).unwrap();
         
         // Test consecutive %x formatting (should be combined into single hex value)
-        let result = parser.format_message("Session is ....0x%x%x%x%x", &vec![0x32, 0x30, 0x46, 0x44]);
+        let result = parser.format_message("Session is ....0x%x%x%x%x", &word_args(&[0x32, 0x30, 0x46, 0x44]));
         assert_eq!(result, "Session is ....0x32304644");
         
-        // Test individual %x (should have separate 0x prefix)
-        let result = parser.format_message("Address %x and value %x", &vec![0x32, 0x44]);
-        assert_eq!(result, "Address 0x32 and value 0x44");
+        // Test individual %x (digits only, no added prefix)
+        let result = parser.format_message("Address %x and value %x", &word_args(&[0x32, 0x44]));
+        assert_eq!(result, "Address 32 and value 44");
         
         // Test mixed case
-        let result = parser.format_message("ID: 0x%x%x, Status: %x", &vec![0xAB, 0xCD, 0xFF]);
-        assert_eq!(result, "ID: 0xABCD, Status: 0xFF");
+        let result = parser.format_message("ID: 0x%x%x, Status: %x", &word_args(&[0xAB, 0xCD, 0xFF]));
+        assert_eq!(result, "ID: 0xABCD, Status: FF");
+    }
+
+    #[test]
+    fn test_signed_decimal_placeholders() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        assert_eq!(parser.format_message("Delta %d", &word_args(&[0xFFFFFFFF])), "Delta -1");
+        assert_eq!(parser.format_message("Delta %ld", &word_args(&[(-42i32) as u32])), "Delta -42");
+        assert_eq!(parser.format_message("Min %d", &word_args(&[0x80000000])), "Min -2147483648");
+        assert_eq!(parser.format_message("Max %d", &word_args(&[0x7FFFFFFF])), "Max 2147483647");
+
+        // Unsigned placeholders keep the raw value
+        assert_eq!(parser.format_message("Size %u", &word_args(&[0xFFFFFFFF])), "Size 4294967295");
+        assert_eq!(parser.format_message("Size %lu", &word_args(&[0xFFFFFFFF])), "Size 4294967295");
+
+        // Padding keeps the sign in front
+        assert_eq!(parser.format_message("[%05d] [%5d] [%-4d]", &word_args(&[(-7i32) as u32; 3])), "[-0007] [   -7] [-7  ]");
+    }
+
+    #[test]
+    fn test_packed_string_words() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // 0x00434241 packs "ABC"; each specifier renders the word at its own position
+        let words = word_args(&[0x00434241, 42, 255]);
+        assert_eq!(parser.format_message("Mode %s value %d hex %x", &words), "Mode ABC value 42 hex FF");
+        let words = word_args(&[0x00434241, 0x00434241]);
+        assert_eq!(parser.format_message("%d then %s", &words), "4407873 then ABC");
+
+        // Non-printable bytes are masked and padding still applies
+        assert_eq!(parser.format_message("[%s] [%5s]", &word_args(&[0x01004241, 0x4241])), "[AB] [   AB]");
+        assert_eq!(parser.format_message("[%s]", &word_args(&[0x41420141])), "[A.BA]");
+    }
+
+    #[test]
+    fn test_raw_args_are_kept() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&0xFFFFFFFFu32.to_le_bytes());
+        binary_data.extend_from_slice(&1.5f32.to_bits().to_le_bytes());
+
+        // The words are kept as stored, whatever the placeholders made of them
+        let log = parser.decode_record_at(&binary_data, 0).unwrap();
+        assert_eq!(log.formatted_message, format!("Trigger no -1 at {}", 1.5f32.to_bits()));
+        assert_eq!(log.raw_args, [0xFFFFFFFF, 1.5f32.to_bits()]);
+        assert_eq!(f32::from_bits(log.raw_args[1]), 1.5);
+    }
+
+    #[test]
+    fn test_float_placeholders() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // 0x41AC0000 is 21.5f
+        assert_eq!(parser.format_message("Temp %f C", &word_args(&[0x41AC0000])), "Temp 21.500000 C");
+        assert_eq!(parser.format_message("Temp %g C", &word_args(&[0x41AC0000])), "Temp 21.5 C");
+        assert_eq!(parser.format_message("Temp %e C", &word_args(&[0x41AC0000])), "Temp 2.150000e+01 C");
+        assert_eq!(parser.format_message("Temp %012f", &word_args(&[(-1.5f32).to_bits()])), "Temp -0001.500000");
+
+        // Every float consumes exactly one argument, keeping later placeholders aligned
+        assert_eq!(parser.format_message("Temp %f C on sensor %d", &word_args(&[0x41AC0000, 3])), "Temp 21.500000 C on sensor 3");
+        assert_eq!(argument_slots("Temp %f C on sensor %d").len(), 2);
+    }
+
+    #[test]
+    fn test_escaped_percent() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        assert_eq!(parser.format_message("load %d%%", &word_args(&[90])), "load 90%");
+        assert_eq!(parser.format_message("CPU at 90%% load, core %d", &word_args(&[2])), "CPU at 90% load, core 2");
+        assert_eq!(parser.missing_arguments(), 0);
+
+        // `%%x` is a literal percent followed by `x`, also next to a hex run
+        assert_eq!(parser.format_message("%%x then 0x%x%x", &word_args(&[0xAB, 0xCD])), "%x then 0xABCD");
+        assert_eq!(argument_slots("load %d%%, %%d").len(), 1);
+    }
+
+    #[test]
+    fn test_single_hex_placeholder_has_no_prefix() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // The template's own `0x` is the only prefix
+        assert_eq!(parser.format_message("Address 0x%x", &word_args(&[255])), "Address 0xFF");
+        assert_eq!(parser.format_message("Address 0x%lx", &word_args(&[255])), "Address 0xFF");
+        assert_eq!(parser.format_message("Mask %x", &word_args(&[255])), "Mask FF");
+
+        // Digits are uppercase and padding applies to the digits alone
+        assert_eq!(parser.format_message("%x", &word_args(&[0xdeadbeef])), "DEADBEEF");
+        assert_eq!(parser.format_message("0x%08x", &word_args(&[0xabc])), "0x00000ABC");
+    }
+
+    #[test]
+    fn test_format_width_is_capped() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // Regular widths are still honored
+        let result = parser.format_message("Value [%5d] [%-4u] [%03d]", &word_args(&[42, 7, 9]));
+        assert_eq!(result, "Value [   42] [7   ] [009]");
+        assert_eq!(parser.format_message("%08x", &word_args(&[0xABC])), "00000ABC");
+
+        // An enormous width is clamped to the default maximum instead of allocating gigabytes
+        let result = parser.format_message("%2000000000d", &word_args(&[1]));
+        assert_eq!(result.len(), DEFAULT_MAX_FORMAT_WIDTH);
+        assert!(result.ends_with('1'));
+
+        // Widths that don't even fit in usize are clamped as well
+        let result = parser.format_message("%099999999999999999999999d", &word_args(&[1]));
+        assert_eq!(result.len(), DEFAULT_MAX_FORMAT_WIDTH);
+
+        // The cap is configurable
+        let config = ParserConfig { max_format_width: 8, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let result = parser.format_message("[%1000d]", &word_args(&[42]));
+        assert_eq!(result, "[      42]");
+    }
+
+    #[test]
+    fn test_dictionary_export_round_trip() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let exported = parser.export_dictionary().unwrap();
+        assert_eq!(exported, std::fs::read(dict_file.path()).unwrap());
+
+        let exported_file = NamedTempFile::new().unwrap();
+        std::fs::write(exported_file.path(), &exported).unwrap();
+        let reloaded = SyslogParser::new(exported_file.path()).unwrap();
+
+        let mut original_offsets: Vec<u32> = parser.dictionary.keys().copied().collect();
+        let mut reloaded_offsets: Vec<u32> = reloaded.dictionary.keys().copied().collect();
+        original_offsets.sort();
+        reloaded_offsets.sort();
+        assert_eq!(original_offsets, reloaded_offsets);
+
+        for offset in original_offsets {
+            assert_eq!(parser.dictionary[&offset].to_dictionary_line(), 
+                       reloaded.dictionary[&offset].to_dictionary_line());
+        }
+    }
+
+    #[test]
+    fn test_serialize_dictionary_preserves_offsets() {
+        let entry = LogEntry {
+            num_args: 1,
+            log_level: 3,
+            source_file: "net.c".to_string(),
+            source_line: Some(10),
+            module_name: "NET".to_string(),
+            log_message: "Link %d up".to_string(),
+        };
+
+        // Gaps are padded with NULL bytes so the second entry stays at byte 100
+        let bytes = serialize_dictionary(&[(100, entry.clone()), (0, entry.clone())]).unwrap();
+        assert_eq!(&bytes[100..bytes.len() - 1], entry.to_dictionary_line().as_bytes());
+        assert!(bytes[entry.to_dictionary_line().len()..100].iter().all(|&b| b == 0));
+
+        // Overlapping entries can't be laid out
+        assert!(serialize_dictionary(&[(0, entry.clone()), (4, entry)]).is_err());
+    }
+
+    #[test]
+    fn test_source_location_parsing() {
+        let entry = SyslogParser::parse_dictionary_line("2;4;test.c:123;TEST_MODULE;Trigger no %d at %d").unwrap();
+        assert_eq!(entry.source_file, "test.c");
+        assert_eq!(entry.source_line, Some(123));
+        assert_eq!(entry.source_location(), "test.c:123");
+
+        // Missing line number
+        let entry = SyslogParser::parse_dictionary_line("0;1;init.c;SYS_INIT;System started").unwrap();
+        assert_eq!(entry.source_file, "init.c");
+        assert_eq!(entry.source_line, None);
+
+        // Malformed line number keeps the raw field so it round-trips unchanged
+        let entry = SyslogParser::parse_dictionary_line("0;1;init.c:4x5;SYS_INIT;System started").unwrap();
+        assert_eq!(entry.source_file, "init.c:4x5");
+        assert_eq!(entry.source_line, None);
+        assert_eq!(entry.to_dictionary_line(), "0;1;init.c:4x5;SYS_INIT;System started");
+    }
+
+    #[test]
+    fn test_message_keeps_semicolons() {
+        let mut dict_file = NamedTempFile::new().unwrap();
+        write!(dict_file, "1;4;fsm.c:12;FSM;state: %d; then B; done\x00").unwrap();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let (_, entry) = parser.entries().next().unwrap();
+        assert_eq!(entry.module_name, "FSM");
+        assert_eq!(entry.log_message, "state: %d; then B; done");
+        assert_eq!(entry.to_dictionary_line(), "1;4;fsm.c:12;FSM;state: %d; then B; done");
+    }
+
+    #[test]
+    fn test_synthetic_binary_round_trip() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = parser.generate_synthetic_binary(50, 7);
+        assert_eq!(binary_data, parser.generate_synthetic_binary(50, 7)); // deterministic
+
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), u8::MAX).unwrap();
+        assert_eq!(parsed_logs.len(), 50);
+
+        let timestamps: Vec<u32> = parsed_logs.iter()
+            .map(|log| log.timestamp_formatted.trim_end_matches("ms").parse().unwrap())
+            .collect();
+        assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+
+        for log in &parsed_logs {
+            assert!(["TEST_MODULE", "SYS_INIT", "MAIN_APP"].contains(&log.module_name.as_str()));
+            assert!(!log.formatted_message.contains("<missing>"));
+        }
+
+        // Big-endian parsers generate captures they can read back
+        let big_endian = SyslogParser::with_byte_order(dict_file.path(), ByteOrder::Big).unwrap();
+        let big_endian_data = big_endian.generate_synthetic_binary(50, 7);
+        assert_ne!(big_endian_data, binary_data);
+        std::fs::write(temp_binary.path(), &big_endian_data).unwrap();
+        let big_endian_logs = big_endian.parse_binary(temp_binary.path(), u8::MAX).unwrap();
+        assert_eq!(big_endian_logs.len(), 50);
+        assert_eq!(
+            big_endian_logs.iter().map(|log| &log.formatted_message).collect::<Vec<_>>(),
+            parsed_logs.iter().map(|log| &log.formatted_message).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_gap_marker_record() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { gap_marker_offset: Some(0x0FFFFFFF), ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        let mut binary_data = create_test_binary();
+        // Gap marker at timestamp 2500 reporting 17 dropped logs
+        binary_data.extend_from_slice(&2500u32.to_le_bytes());
+        binary_data.extend_from_slice(&((1u32 << 28) | 0x0FFFFFFF).to_le_bytes());
+        binary_data.extend_from_slice(&17u32.to_le_bytes());
+
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        // The marker survives even the most restrictive level filter
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 0).unwrap();
+        assert_eq!(parsed_logs.len(), 1);
+        assert_eq!(parsed_logs[0].timestamp_formatted, "2500ms");
+        assert_eq!(parsed_logs[0].module_name, DROPPED_LOGS_MODULE);
+        assert_eq!(parsed_logs[0].formatted_message, "*** 17 logs dropped ***");
+        assert_eq!(parsed_logs[0].dropped_logs, Some(17));
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 4);
+        assert!(parsed_logs[..3].iter().all(|log| log.dropped_logs.is_none() && !log.is_gap_marker()));
+        assert!(parsed_logs[3].is_gap_marker());
+
+        // A marker without a count reports the loss as unknown rather than zero
+        let mut binary_data = create_test_binary();
+        binary_data.extend_from_slice(&2500u32.to_le_bytes());
+        binary_data.extend_from_slice(&0x0FFFFFFFu32.to_le_bytes());
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs[3].formatted_message, "*** unknown number of logs dropped ***");
+        assert_eq!(parsed_logs[3].dropped_logs, None);
+        assert!(parsed_logs[3].is_gap_marker());
+        let stats = DecodeStats::from_logs(&parsed_logs);
+        assert_eq!((stats.dropped_logs, stats.level_counts.values().sum::<usize>()), (0, 3));
+    }
+
+    #[test]
+    fn test_argument_budget() {
+        let dict_file = create_test_dictionary();
+        let binary_data = create_test_binary(); // 2 arguments in total
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let config = ParserConfig { max_total_args: Some(2), ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        assert_eq!(parser.parse_binary(temp_binary.path(), 5).unwrap().len(), 3);
+
+        let config = ParserConfig { max_total_args: Some(1), ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let err = parser.parse_binary(temp_binary.path(), 5).unwrap_err();
+        assert!(err.to_string().contains("Argument budget exceeded"));
+    }
+
+    #[test]
+    fn test_offset_annotation() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs[2].log_offset, 47);
+
+        let options = FormatOptions { include_offset: true, ..Default::default() };
+        let formatted = parser.format_logs_with(&parsed_logs, &options);
+        assert!(formatted[0].ends_with("\t{offset=0x0}"));
+        assert!(formatted[2].ends_with("System started\t{offset=0x2F}"));
+
+        // Off by default
+        assert!(!parser.format_logs(&parsed_logs)[2].contains("offset="));
+    }
+
+    #[test]
+    fn test_level_direction() {
+        let dict_file = create_test_dictionary();
+        let binary_data = create_test_binary(); // levels 4, 4, 1
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        // Descending (default): keep levels 2 and below
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 2).unwrap();
+        assert_eq!(parsed_logs.len(), 1);
+        assert_eq!(parsed_logs[0].module_name, "SYS_INIT");
+
+        // Ascending: keep levels 2 and above
+        let config = ParserConfig { level_direction: LevelDirection::Ascending, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 2).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert!(parsed_logs.iter().all(|log| log.module_name == "TEST_MODULE"));
+
+        // Names are mirrored: ascending level 4 is descending level 2 (Error)
+        let formatted = parser.format_logs_with_options(&parsed_logs, true);
+        assert!(formatted[0].contains("[Error]\t[TEST_MODULE]"));
+        assert_eq!(parser.level_name(0), "Verbose");
+        assert_eq!(parser.level_name(6), "Critical");
+        assert_eq!(parser.level_name(7), "Unknown");
+    }
+
+    #[test]
+    fn test_empty_dictionary() {
+        let empty_dict = NamedTempFile::new().unwrap();
+        let err = SyslogParser::new(empty_dict.path()).err().unwrap();
+        assert!(matches!(err.downcast_ref::<DecodeError>(), Some(DecodeError::EmptyDictionary { .. })));
+
+        let null_dict = NamedTempFile::new().unwrap();
+        std::fs::write(null_dict.path(), [0u8; 64]).unwrap();
+        let err = SyslogParser::new(null_dict.path()).err().unwrap();
+        assert!(matches!(err.downcast_ref::<DecodeError>(), Some(DecodeError::EmptyDictionary { .. })));
+    }
+
+    #[test]
+    fn test_parse_binary_range_bytes() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = parser.generate_synthetic_binary(300, 3);
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+        let all_logs = parser.parse_binary(temp_binary.path(), u8::MAX).unwrap();
+
+        // Record start offsets, walked using the argument count nibble
+        let mut record_starts = Vec::new();
+        let mut offset = 0;
+        while offset < binary_data.len() {
+            record_starts.push(offset);
+            let log_id = u32::from_le_bytes(binary_data[offset + 4..offset + 8].try_into().unwrap());
+            offset += 8 + (log_id >> 28) as usize * 4;
+        }
+
+        // An unaligned start in the middle of the file must resync to the next record
+        let start = binary_data.len() / 3 + 1;
+        let end = 2 * binary_data.len() / 3;
+        let expected: Vec<&ParsedLog> = record_starts.iter().zip(&all_logs)
+            .filter(|(record_start, _)| **record_start >= start && **record_start < end)
+            .map(|(_, log)| log)
+            .collect();
+
+        let range_logs = parser.parse_binary_range_bytes(temp_binary.path(), start as u64, end as u64, u8::MAX).unwrap();
+        assert!(!range_logs.is_empty());
+        assert_eq!(range_logs.len(), expected.len());
+        for (log, expected) in range_logs.iter().zip(expected) {
+            assert_eq!(log.timestamp_formatted, expected.timestamp_formatted);
+            assert_eq!(log.formatted_message, expected.formatted_message);
+        }
+
+        assert!(parser.parse_binary_range_bytes(temp_binary.path(), 10, 5, 5).is_err());
+    }
+
+    fn encode_varint(mut value: u32, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push((value as u8 & 0x7F) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    #[test]
+    fn test_varint_arguments() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { arg_encoding: ArgEncoding::Varint, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        // "Trigger no %d at %d" with a 2-byte and a 5-byte varint argument
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        encode_varint(300, &mut binary_data);
+        encode_varint(u32::MAX, &mut binary_data);
+        // Followed by a 0-arg SYS_INIT record to check the record length was right
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        assert_eq!(binary_data.len(), 8 + 2 + 5 + 8);
+
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        // %d reads the u32::MAX word as signed
+        assert_eq!(parsed_logs[0].formatted_message, "Trigger no 300 at -1");
+        assert_eq!(parsed_logs[1].module_name, "SYS_INIT");
+
+        // Split the stream inside the 5-byte varint, as a chunk boundary would
+        let split = 8 + 2 + 3;
+        let (entries, remainder) = parser.parse_chunk(&binary_data[..split]).unwrap();
+        assert!(entries.is_empty());
+        assert_eq!(remainder, &binary_data[..split]);
+
+        let mut next_chunk = remainder;
+        next_chunk.extend_from_slice(&binary_data[split..]);
+        let (entries, remainder) = parser.parse_chunk(&next_chunk).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].arguments, vec![Arg::U32(300), Arg::U32(u32::MAX)]);
+        assert_eq!(entries[1].log_id, 47);
+    }
+
+    #[test]
+    fn test_truncated_trailing_record() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // A final "Trigger no %d at %d" record cut off in the middle of its second argument
+        let mut binary_data = create_test_binary();
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&7u32.to_le_bytes());
+        binary_data.extend_from_slice(&[0x01, 0x02]);
+
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 3);
+        assert_eq!(parsed_logs[2].timestamp_formatted, "2000ms");
+        assert!(parsed_logs.iter().all(|log| !log.formatted_message.starts_with("Trigger no 7")));
+    }
+
+    #[test]
+    fn test_stream_decoder() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let binary_data = create_test_binary();
+
+        // Feed the capture in 5-byte pieces so every record is split
+        let mut decoder = parser.stream_decoder(5);
+        let mut parsed_logs = Vec::new();
+        for piece in binary_data.chunks(5) {
+            parsed_logs.extend(decoder.push(piece).unwrap());
+        }
+        assert_eq!(decoder.pending_bytes(), 0);
+        assert_eq!(parsed_logs.len(), 3);
+        assert_eq!(parsed_logs[1].formatted_message, "Trigger no 42 at 100");
+        assert_eq!(parsed_logs[2].module_name, "SYS_INIT");
+    }
+
+    #[test]
+    fn test_lookups_use_parsed_entries() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // Only the parsed map is kept, so lookups must hit entry start offsets exactly
+        assert_eq!(parser.get_entry_by_byte_offset(0).unwrap().module_name, "TEST_MODULE");
+        assert_eq!(parser.get_entry_by_byte_offset(47).unwrap().module_name, "SYS_INIT");
+        assert_eq!(parser.get_entry_by_byte_offset(85).unwrap().module_name, "MAIN_APP");
+        assert!(parser.get_entry_by_byte_offset(48).is_none());
+        assert!(parser.get_entry_by_byte_offset(10_000).is_none());
+    }
+
+    #[test]
+    fn test_timestamp_units() {
+        assert_eq!(TimestampUnit::Milliseconds.to_millis(1234), 1234);
+        assert_eq!(TimestampUnit::Microseconds.to_millis(1_234_567), 1234);
+        // 3 seconds of a 32.768 kHz RTC
+        assert_eq!(TimestampUnit::Ticks { hz: 32_768 }.to_millis(98_304), 3000);
+        assert_eq!(TimestampUnit::Ticks { hz: 32_768 }.to_millis(u32::MAX), 131_071_999);
+
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { timestamp_unit: TimestampUnit::Ticks { hz: 32_768 }, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&98_304u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs[0].timestamp_formatted, "3000ms");
+    }
+
+    #[test]
+    fn test_strict_dictionary_rejects_mid_entry_offset() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { strict_dictionary: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        // Offset 52 lands inside "0;1;init.c:45;SYS_INIT;System started" (which starts at 47)
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&52u32.to_le_bytes());
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let error = parser.parse_binary(temp_binary.path(), 5).unwrap_err();
+        assert_eq!(error.downcast_ref::<DecodeError>(), Some(&DecodeError::UnalignedOffset { log_offset: 52, timestamp_raw: 1000 }));
+        assert!(error.to_string().contains("offset 0x34, which is not the start of a dictionary entry"));
+
+        // Without strict mode the record is dropped and the decode goes on
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 1);
+        assert_eq!(parsed_logs[0].formatted_message, "System started");
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let options = FormatOptions { include_summary: true, ..Default::default() };
+        let formatted = parser.format_logs_with(&parsed_logs, &options);
+        assert_eq!(formatted.len(), 4);
+        assert_eq!(formatted[3], "=== Summary: 3 entries, 0ms to 2000ms, FatalError: 1, Info: 2 ===");
+
+        // Module counts go before the summary, which stays the last line
+        let stats = DecodeStats::from_logs(&parsed_logs);
+        assert_eq!(stats.module_counts, BTreeMap::from([("SYS_INIT".to_string(), 1), ("TEST_MODULE".to_string(), 2)]));
+        let options = FormatOptions { include_module_counts: true, include_summary: true, ..Default::default() };
+        let formatted = parser.format_logs_with(&parsed_logs, &options);
+        assert_eq!(formatted.len(), 5);
+        assert_eq!(formatted[3], "=== Modules: SYS_INIT: 1, TEST_MODULE: 2 ===");
+        assert!(formatted[4].starts_with(SUMMARY_LINE_PREFIX));
+        assert_eq!(SyslogParser::module_counts_line_from_stats(&DecodeStats::default()), "=== Modules: none ===");
+
+        assert_eq!(parser.summary_line(&[]), "=== Summary: 0 entries ===");
+        assert!(!parser.format_logs(&parsed_logs).iter().any(|line| line.starts_with(SUMMARY_LINE_PREFIX)));
+    }
+
+    #[test]
+    fn test_dictionary_with_bom() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0xEF, 0xBB, 0xBF]).unwrap();
+        write!(temp_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00").unwrap();
+        write!(temp_file, "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        temp_file.flush().unwrap();
+        let parser = SyslogParser::new(temp_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        // Offsets are the same as for the dictionary without BOM
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 3);
+        assert_eq!(parsed_logs[0].module_name, "TEST_MODULE");
+        assert_eq!(parsed_logs[1].formatted_message, "Trigger no 42 at 100");
+        assert_eq!(parsed_logs[2].module_name, "SYS_INIT");
+    }
+
+    #[test]
+    fn test_format_logs_unique() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // "Processing item %d" (offset 85) with 7, 8, 7 around a SYS_INIT log
+        let mut binary_data = Vec::new();
+        for (timestamp, log_id, arg) in [(100u32, 85u32, Some(7u32)), (200, 47, None), (300, 85, Some(8)), (400, 85, Some(7))] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&((arg.is_some() as u32) << 28 | log_id).to_le_bytes());
+            if let Some(arg) = arg {
+                binary_data.extend_from_slice(&arg.to_le_bytes());
+            }
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+
+        let unique = parser.format_logs_unique(&parsed_logs, &FormatOptions::default(), UniqueBy::Message);
+        assert_eq!(unique.len(), 3);
+        assert!(unique[0].starts_with("100ms") && unique[0].ends_with("Processing item 7\t(x2)"));
+        assert!(unique[1].ends_with("System started\t(x1)"));
+        assert!(unique[2].ends_with("Processing item 8\t(x1)"));
+
+        let unique = parser.format_logs_unique(&parsed_logs, &FormatOptions::default(), UniqueBy::Offset);
+        assert_eq!(unique.len(), 2);
+        assert!(unique[0].ends_with("Processing item 7\t(x3)"));
+    }
+
+    #[test]
+    fn test_format_logs_unique_order() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // SYS_INIT once, then "Processing item 8" once and "Processing item 7" twice
+        let mut binary_data = Vec::new();
+        for (timestamp, log_id, arg) in [(100u32, 47u32, None), (200, 85, Some(8u32)), (300, 85, Some(7)), (400, 85, Some(7))] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&((arg.is_some() as u32) << 28 | log_id).to_le_bytes());
+            if let Some(arg) = arg {
+                binary_data.extend_from_slice(&arg.to_le_bytes());
+            }
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+
+        let first_seen = parser.format_logs_unique(&parsed_logs, &FormatOptions::default(), UniqueBy::Message);
+        let timestamps: Vec<&str> = first_seen.iter().map(|line| line.split_whitespace().next().unwrap()).collect();
+        assert_eq!(timestamps, ["100ms", "200ms", "300ms"]);
+
+        let options = FormatOptions { dedup_order: DedupOrder::Summary, ..Default::default() };
+        let summary = parser.format_logs_unique(&parsed_logs, &options, UniqueBy::Message);
+        assert_eq!(summary.len(), 3);
+        assert!(summary[0].starts_with("300ms") && summary[0].ends_with("Processing item 7\t(x2)"));
+        assert!(summary[1].ends_with("System started\t(x1)"));
+        assert!(summary[2].ends_with("Processing item 8\t(x1)"));
+    }
+
+    #[test]
+    fn test_field_separator() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+
+        let options = FormatOptions {
+            include_log_level: true,
+            field_separator: Some(" | ".to_string()),
+            bracket_style: BracketStyle::Plain,
+            ..Default::default()
+        };
+        assert_eq!(parser.format_log(&parsed_logs[1], &options), "1000ms | Info | TEST_MODULE | Trigger no 42 at 100");
+
+        let options = FormatOptions {
+            field_separator: Some(",".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(parser.format_log(&parsed_logs[2], &options), "2000ms,[SYS_INIT],System started");
+
+        // Default keeps the tab layout with the padded timestamp
+        assert_eq!(parser.format_log(&parsed_logs[2], &FormatOptions::default()), "2000ms      \t[SYS_INIT]\tSystem started");
+    }
+
+    #[test]
+    fn test_parse_formatted_line_round_trip() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        let binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+
+        // With the level field
+        let with_level = parser.format_logs_with_options(&parsed_logs, true);
+        let parsed = parse_formatted_line(&with_level[1]).unwrap();
+        assert_eq!(parsed.timestamp_ms, 1000);
+        assert_eq!(parsed.log_level, 4);
+        assert_eq!(parsed.module_name, "TEST_MODULE");
+        assert_eq!(parsed.formatted_message, "Trigger no 42 at 100");
+
+        // Without the level field
+        let without_level = parser.format_logs(&parsed_logs);
+        let parsed = parse_formatted_line(&without_level[2]).unwrap();
+        assert_eq!(parsed.timestamp_formatted, "2000ms");
+        assert_eq!(parsed.log_level, u8::MAX);
+        assert_eq!(parsed.module_name, "SYS_INIT");
+        assert_eq!(parsed.formatted_message, "System started");
+
+        // Offset suffix and gap markers
+        let options = FormatOptions { include_log_level: true, include_offset: true, ..Default::default() };
+        let parsed = parse_formatted_line(&parser.format_log(&parsed_logs[2], &options)).unwrap();
+        assert_eq!(parsed.log_level, 1);
+        assert_eq!(parsed.log_offset, 47);
+        assert_eq!(parsed.formatted_message, "System started");
+        let parsed = parse_formatted_line("500ms       \t[Critical]\t[DROPPED]\t*** 12 logs dropped ***").unwrap();
+        assert_eq!(parsed.dropped_logs, Some(12));
+        let parsed = parse_formatted_line("500ms       \t[Critical]\t[DROPPED]\t*** unknown number of logs dropped ***").unwrap();
+        assert_eq!(parsed.dropped_logs, None);
+        assert!(parsed.is_gap_marker());
+
+        assert!(parse_formatted_line("Syslog Parser v0.1.0").is_none());
+        assert!(parse_formatted_line(&parser.summary_line(&parsed_logs)).is_none());
+    }
+
+    #[test]
+    fn test_validate_dictionary_bytes() {
+        let contents = b"2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00not a dictionary line\x001;2;main.c:67;MAIN_APP;No placeholder\x000;1;init.c:45;SYS_INIT;System started";
+        let validation = validate_dictionary_bytes(contents);
+
+        assert_eq!(validation.entry_count, 3);
+        assert_eq!(validation.modules, vec!["MAIN_APP", "SYS_INIT", "TEST_MODULE"]);
+        assert_eq!(validation.issues.len(), 2);
+        assert_eq!(validation.issues[0].byte_offset, 47);
+        assert_eq!(validation.issues[0].kind, ValidationIssueKind::MalformedLine);
+        assert_eq!(validation.issues[1].byte_offset, 69);
+        assert_eq!(validation.issues[1].kind, ValidationIssueKind::ArgCountMismatch);
+
+        let validation = validate_dictionary_bytes(b"\x00\x00");
+        assert_eq!(validation.entry_count, 0);
+        assert_eq!(validation.issues[0].kind, ValidationIssueKind::EmptyDictionary);
+    }
+
+    #[test]
+    fn test_validate_loaded_dictionary() {
+        let mut dict_file = NamedTempFile::new().unwrap();
+        write!(dict_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00not a dictionary line\x001;2;main.c:67;MAIN_APP;No placeholder\x000;1;init.c:45;SYS_INIT;System started").unwrap();
+        let parser = SyslogParser::with_config(dict_file.path(), ParserConfig { quiet: true, ..Default::default() }).unwrap();
+
+        let report = parser.validate();
+        assert_eq!(report, DictionaryReport { total_entries: 3, malformed_lines: 1, duplicate_offsets: 0, arg_count_mismatches: 1 });
+        assert_eq!(report.problems(), 2);
+
+        let dict_file = create_test_dictionary();
+        assert!(SyslogParser::new(dict_file.path()).unwrap().validate().is_clean());
+    }
+
+    #[test]
+    fn test_group_by_module() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // Interleaved modules with an out-of-order MAIN_APP timestamp
+        let mut binary_data = Vec::new();
+        for (timestamp, log_id, args) in [
+            (300u32, 85u32 | (1 << 28), vec![3u32]),
+            (100, 47, vec![]),
+            (200, 85 | (1 << 28), vec![2]),
+            (400, 2 << 28, vec![1, 2]),
+            (500, 47, vec![]),
+        ] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&log_id.to_le_bytes());
+            for arg in args {
+                binary_data.extend_from_slice(&arg.to_le_bytes());
+            }
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+
+        let partitions = partition_by_module(&parsed_logs);
+        assert_eq!(partitions.keys().copied().collect::<Vec<_>>(), vec!["MAIN_APP", "SYS_INIT", "TEST_MODULE"]);
+
+        let options = FormatOptions { group_by_module: true, ..Default::default() };
+        let lines = parser.format_logs_with(&parsed_logs, &options);
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "=== MAIN_APP (2 entries) ===");
+        assert!(lines[1].starts_with("200ms") && lines[1].ends_with("Processing item 2"));
+        assert!(lines[2].starts_with("300ms") && lines[2].ends_with("Processing item 3"));
+        assert_eq!(lines[3], "=== SYS_INIT (2 entries) ===");
+        assert!(lines[4].starts_with("100ms") && lines[5].starts_with("500ms"));
+        assert_eq!(lines[6], "=== TEST_MODULE (1 entries) ===");
+        assert!(lines[7].ends_with("Trigger no 1 at 2"));
+    }
+
+    #[test]
+    fn test_percent_n_consumes_no_argument() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        assert_eq!(parser.format_message("Wrote %d bytes%n to %d", &word_args(&[12, 34])), "Wrote 12 bytes%n to 34");
+        assert_eq!(parser.format_message("%ln%d", &word_args(&[5])), "%ln5");
+
+        // The load-time warning matches the same forms, and not an escaped percent before an `n`
+        let placeholder_pattern = Regex::new(PLACEHOLDER_PATTERN).unwrap();
+        for template in ["Wrote%n", "Wrote %ln", "Wrote %5n", "Wrote %lln"] {
+            assert!(uses_percent_n(&placeholder_pattern, template), "{}", template);
+        }
+        assert!(!uses_percent_n(&placeholder_pattern, "100%%nominal"));
+        assert!(!uses_percent_n(&placeholder_pattern, "Wrote %d"));
+    }
+
+    #[test]
+    fn test_offset_adjustment() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&48u32.to_le_bytes()); // one byte past SYS_INIT at 47
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        assert!(parser.decode_record_at(&binary_data, 0).is_err());
+
+        let config = ParserConfig { offset_adjustment: 1, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let log = parser.decode_record_at(&binary_data, 0).unwrap();
+        assert_eq!((log.module_name.as_str(), log.log_offset), ("SYS_INIT", 47));
+
+        // An offset pushed below 0 resolves to nothing rather than wrapping onto entry 0
+        let config = ParserConfig { offset_adjustment: 100, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        assert!(parser.decode_record_at(&binary_data, 0).is_err());
+    }
+
+    #[test]
+    fn test_unknown_entry_labels() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&500u32.to_le_bytes());
+        binary_data.extend_from_slice(&0x30u32.to_le_bytes()); // 0 args, offset 0x30 starts no entry
+        binary_data.extend_from_slice(&600u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), binary_data).unwrap();
+
+        // Dropped by default
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        assert_eq!(parser.parse_binary(temp_binary.path(), 5).unwrap().len(), 1);
+
+        let config = ParserConfig {
+            unknown_entries: Some(UnknownEntryLabels {
+                module: "???".to_string(),
+                message: "no entry at {offset}".to_string(),
+            }),
+            ..Default::default()
+        };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert_eq!(parser.format_logs_with_options(&parsed_logs[..1], true),
+                   vec!["500ms       \t[Unknown]\t[???]\tno entry at 0x00000030"]);
+    }
+
+    #[test]
+    fn test_fixed_record_layout() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig {
+            record_layout: RecordLayout::Fixed { arg_slots: 2 },
+            ..Default::default()
+        };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        // 16-byte records: timestamp, id, two slots with unused ones zeroed
+        let mut binary_data = Vec::new();
+        for (timestamp, log_id, slots) in [
+            (100u32, 47u32, [0u32, 0]),
+            (200, 85 | (1 << 28), [7, 0]),
+            (300, 2 << 28, [42, 100]),
+        ] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&log_id.to_le_bytes());
+            for slot in slots {
+                binary_data.extend_from_slice(&slot.to_le_bytes());
+            }
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let messages: Vec<&str> = parsed_logs.iter().map(|log| log.formatted_message.as_str()).collect();
+        assert_eq!(messages, vec!["System started", "Processing item 7", "Trigger no 42 at 100"]);
+        assert_eq!(parsed_logs[2].timestamp_ms, 300);
+
+        // The incremental decoder frames records the same way
+        let mut decoder = parser.stream_decoder(5);
+        assert_eq!(decoder.push(&binary_data[..20]).unwrap().len(), 1);
+        assert_eq!(decoder.push(&binary_data[20..]).unwrap().len(), 2);
+
+        // Range decoding resyncs on 16-byte records
+        let parsed_logs = parser.parse_binary_range_bytes(temp_binary.path(), 16, 48, 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert_eq!(parsed_logs[0].formatted_message, "Processing item 7");
+    }
+
+    #[test]
+    fn test_warnings_report_dictionary_and_offset_problems() {
+        let mut dict_file = create_test_dictionary();
+        write!(dict_file, "not a dictionary line\x00").unwrap();
+        dict_file.flush().unwrap();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        assert_eq!(parser.warnings().len(), 1);
+        assert!(parser.warnings()[0].starts_with("Dictionary line at byte 127"));
+
+        // One of three records is unmapped
+        let mut binary_data = create_test_binary();
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        binary_data.extend_from_slice(&0x30u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+        parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let warnings = parser.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1], "1 of 4 records reference offsets missing from the dictionary");
+
+        // Mostly unmapped records hint at the wrong firmware version
+        std::fs::write(temp_binary.path(), [0x30u32.to_le_bytes(); 8].concat()).unwrap();
+        parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert!(parser.warnings().last().unwrap().contains("different firmware version"));
+    }
+
+    #[test]
+    fn test_continuation_records() {
+        let mut dict_file = NamedTempFile::new().unwrap();
+        write!(dict_file, "3;4;wide.c:1;WIDE;a=%d b=%d c=%d\x00").unwrap();
+        dict_file.flush().unwrap();
+        let config = ParserConfig { continuation_offset: Some(0xFFFFFFF), ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        // Base record declares 2 args, the continuation carries the third
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&1u32.to_le_bytes());
+        binary_data.extend_from_slice(&2u32.to_le_bytes());
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&((1u32 << 28) | 0xFFFFFFF).to_le_bytes());
+        binary_data.extend_from_slice(&3u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 1);
+        assert_eq!(parsed_logs[0].formatted_message, "a=1 b=2 c=3");
+
+        // Split right before the continuation, the base record waits for the next chunk
+        let mut decoder = parser.stream_decoder(5);
+        assert!(decoder.push(&binary_data[..16]).unwrap().is_empty());
+        let parsed_logs = decoder.push(&binary_data[16..]).unwrap();
+        assert!(parsed_logs.is_empty());
+        let parsed_logs = decoder.finish().unwrap();
+        assert_eq!(parsed_logs[0].formatted_message, "a=1 b=2 c=3");
+
+        // Range ending at the base record still picks up its continuation
+        let parsed_logs = parser.parse_binary_range_bytes(temp_binary.path(), 0, 16, 5).unwrap();
+        assert_eq!(parsed_logs[0].formatted_message, "a=1 b=2 c=3");
+    }
+
+    #[test]
+    fn test_truncation_policies() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = create_test_binary();
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&7u32.to_le_bytes()); // second argument missing
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        for policy in [TruncationPolicy::Ignore, TruncationPolicy::Warn] {
+            let config = ParserConfig { truncation_policy: policy, ..Default::default() };
+            let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+            assert_eq!(parser.parse_binary(temp_binary.path(), 5).unwrap().len(), 3);
+            assert_eq!(parser.parse_binary_range_bytes(temp_binary.path(), 0, 1000, 5).unwrap().len(), 3);
+        }
+
+        let config = ParserConfig { truncation_policy: TruncationPolicy::Error, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        for result in [parser.parse_binary(temp_binary.path(), 5), parser.parse_binary_range_bytes(temp_binary.path(), 0, 1000, 5)] {
+            let error = result.unwrap_err();
+            assert_eq!(error.downcast_ref::<DecodeError>(), Some(&DecodeError::TruncatedInput { trailing_bytes: 12 }));
+        }
+    }
+
+    #[test]
+    fn test_delta_timestamps() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        // Absolute start, two deltas, then a negative delta that must not move time backwards
+        for (timestamp, log_id) in [(500u32, 47u32), (250, 47), (0, 47), (-100i32 as u32, 47), (1000, 47)] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&log_id.to_le_bytes());
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+        let expected = vec![500, 750, 750, 750, 1750];
+
+        let config = ParserConfig { timestamp_encoding: TimestampEncoding::Delta, quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let timestamps: Vec<u64> = parser.parse_binary(temp_binary.path(), 5).unwrap()
+            .iter().map(|log| log.timestamp_ms).collect();
+        assert_eq!(timestamps, expected);
+
+        // The accumulator carries across pushes, including a record split between them
+        let mut decoder = parser.stream_decoder(5);
+        let mut logs = decoder.push(&binary_data[..20]).unwrap();
+        logs.extend(decoder.push(&binary_data[20..]).unwrap());
+        assert_eq!(logs.iter().map(|log| log.timestamp_ms).collect::<Vec<_>>(), expected);
+
+        assert!(parser.parse_binary_range_bytes(temp_binary.path(), 8, 40, 5).is_err());
+
+        // Absolute decoding of the same bytes reports the raw values
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        assert_eq!(parser.parse_binary(temp_binary.path(), 5).unwrap()[1].timestamp_ms, 250);
+    }
+
+    #[test]
+    fn test_raw_on_mismatch() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        // TEST_MODULE expects 2 arguments but the record declares only 1
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(1u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&7u32.to_le_bytes());
+        // MAIN_APP with the expected single argument
+        binary_data.extend_from_slice(&200u32.to_le_bytes());
+        binary_data.extend_from_slice(&((1u32 << 28) | 85).to_le_bytes());
+        binary_data.extend_from_slice(&3u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let plain = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert!(!plain[0].formatted_message.contains("raw args"));
+
+        let config = ParserConfig { raw_on_mismatch: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert!(logs[0].formatted_message.starts_with("Trigger no 7 at "));
+        assert!(logs[0].formatted_message.ends_with(" [mismatch: record has 1 args, dictionary expects 2; raw args: 0x00000007]"));
+        // Records that agree with the dictionary are unchanged
+        assert_eq!(logs[1].formatted_message, "Processing item 3");
+    }
+
+    #[test]
+    fn test_decode_result_round_trip() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = create_test_binary();
+        // A record whose offset is missing from the dictionary, to produce a warning
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        binary_data.extend_from_slice(&500u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let result = parser.decode(temp_binary.path(), &DecodeOptions { min_log_level: 5 }).unwrap();
+        assert_eq!(result.version, DECODE_RESULT_VERSION);
+        assert_eq!(result.entries.len(), 3);
+        assert_eq!(result.stats.entry_count, 3);
+        assert_eq!(result.stats.first_timestamp_ms, Some(0));
+        assert_eq!(result.stats.last_timestamp_ms, Some(2000));
+        assert_eq!(result.stats.level_counts, BTreeMap::from([(1, 1), (4, 2)]));
+        assert_eq!(result.warnings, ["1 of 4 records reference offsets missing from the dictionary"]);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["entries"][1]["formatted_message"], "Trigger no 42 at 100");
+        assert_eq!(json["stats"]["level_counts"]["4"], 2);
+        let round_tripped: DecodeResult = serde_json::from_value(json).unwrap();
+        assert_eq!(round_tripped, result);
+
+        // A second decode with the same parser reports only its own records
+        let again = parser.decode(temp_binary.path(), &DecodeOptions { min_log_level: 5 }).unwrap();
+        assert_eq!(again.warnings, result.warnings);
+        assert_eq!(parser.warnings(), ["2 of 8 records reference offsets missing from the dictionary"]);
+    }
+
+    #[test]
+    fn test_rebase_timestamps() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        // Capture starts 5000ms after boot, then the device reboots
+        for timestamp in [5000u32, 5250, 6000, 0, 400] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&47u32.to_le_bytes());
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let options = FormatOptions { rebase_timestamps: true, ..Default::default() };
+        let lines = parser.format_logs_with(&parsed_logs, &options);
+        let timestamps: Vec<&str> = lines.iter().map(|line| line.split_whitespace().next().unwrap()).collect();
+        assert_eq!(timestamps, ["0ms", "250ms", "1000ms", "0ms", "400ms"]);
+
+        // Each boot cycle still becomes its own session
+        let sessions = session::parse_log_sessions(&lines.join("\n"));
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[0].content.starts_with("0ms"));
+        assert!(sessions[1].content.contains("400ms"));
+
+        // Logs themselves keep their capture timestamps
+        assert_eq!(parsed_logs[0].timestamp_ms, 5000);
+    }
+
+    #[test]
+    fn test_timestamp_deltas() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        for timestamp in [0u32, 100, 350] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&47u32.to_le_bytes());
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let options = FormatOptions { include_deltas: true, ..Default::default() };
+        let lines = parser.format_logs_with(&parsed_logs, &options);
+        let deltas: Vec<&str> = lines.iter().map(|line| line.split('\t').nth(1).unwrap()).collect();
+        assert_eq!(deltas, ["(+0ms)", "(+100ms)", "(+250ms)"]);
+        assert!(lines[1].starts_with("100ms"));
+
+        // A single log has no previous line to measure from
+        assert!(parser.format_log(&parsed_logs[2], &options).contains("\t(+0ms)\t"));
+    }
+
+    #[test]
+    fn test_decode_record_at() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let binary_data = create_test_binary();
+
+        // Second record: 8 bytes after the first, with two arguments
+        let log = parser.decode_record_at(&binary_data, 8).unwrap();
+        assert_eq!(log.timestamp_ms, 1000);
+        assert_eq!(log.module_name, "TEST_MODULE");
+        assert_eq!(log.formatted_message, "Trigger no 42 at 100");
+
+        // Third record follows the two 4-byte arguments; its level is not filtered
+        let log = parser.decode_record_at(&binary_data, 24).unwrap();
+        assert_eq!(log.module_name, "SYS_INIT");
+
+        // Inside the second record's arguments, misaligned, and past the end
+        let error = parser.decode_record_at(&binary_data, 16).unwrap_err().to_string();
+        assert!(error.contains("not a record boundary"), "{}", error);
+        assert!(parser.decode_record_at(&binary_data, 10).is_err());
+        assert!(parser.decode_record_at(&binary_data, binary_data.len()).is_err());
+    }
+
+    #[test]
+    fn test_missing_arguments_are_counted() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        // TEST_MODULE takes two arguments: give it one, then none
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(1u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&7u32.to_le_bytes());
+        binary_data.extend_from_slice(&200u32.to_le_bytes());
+        binary_data.extend_from_slice(&0u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let result = parser.decode(temp_binary.path(), &DecodeOptions { min_log_level: 5 }).unwrap();
+        assert_eq!(result.entries[1].formatted_message, "Trigger no <missing> at <missing>");
+        assert_eq!(result.stats.missing_arguments, 3);
+        // Only reported as a warning when asked for
+        assert!(result.warnings.is_empty());
+
+        let config = ParserConfig { warn_on_missing_arguments: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let result = parser.decode(temp_binary.path(), &DecodeOptions { min_log_level: 5 }).unwrap();
+        assert_eq!(result.warnings, ["3 placeholders had no argument and were printed as <missing>; the dictionary may not match the capture"]);
+    }
+
+    #[test]
+    fn test_extra_arguments_are_counted() {
+        let dict_file = create_test_dictionary();
+        let mut binary_data = Vec::new();
+        // TEST_MODULE takes two arguments: give it three, then two
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(3u32 << 28).to_le_bytes());
+        for argument in [1u32, 2, 3] {
+            binary_data.extend_from_slice(&argument.to_le_bytes());
+        }
+        binary_data.extend_from_slice(&200u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&4u32.to_le_bytes());
+        binary_data.extend_from_slice(&5u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        let result = parser.decode(temp_binary.path(), &DecodeOptions { min_log_level: 5 }).unwrap();
+        assert_eq!(result.entries[0].formatted_message, "Trigger no 1 at 2");
+        assert_eq!(result.stats.extra_arguments, 1);
+        assert_eq!(result.stats.missing_arguments, 0);
+        assert_eq!(result.warnings, ["1 records had more arguments than placeholders; the dictionary may be from a different firmware version"]);
+    }
+
+    #[test]
+    fn test_utf16_string_arguments() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+
+        // "Héllo" as UTF-16LE units, two per word, NUL-terminated in the high half of the third word
+        let units: Vec<u16> = "Héllo".encode_utf16().collect();
+        let words = vec![
+            units[0] as u32 | (units[1] as u32) << 16,
+            units[2] as u32 | (units[3] as u32) << 16,
+            units[4] as u32,
+            42,
+        ];
+        assert_eq!(parser.format_message("Name %ls id %d", &word_args(&words)), "Name Héllo id 42");
+
+        // A NUL in the low half ends the string on that word; %lc takes one unit
+        let words = vec!['O' as u32 | ('K' as u32) << 16, 0, 'é' as u32];
+        assert_eq!(parser.format_message("[%ls] [%lc]", &word_args(&words)), "[OK] [é]");
+        assert_eq!(parser.format_message("%ls", &word_args(&[])), "<missing>");
+
+        // Strings spanning several words still validate against a larger num_args
+        let validation = validate_dictionary_bytes(b"4;3;ui.c:10;UI;Name %ls id %d\x002;3;ui.c:11;UI;%ls %ls %d\x00");
+        assert_eq!(validation.issues.len(), 1);
+        assert_eq!(validation.issues[0].byte_offset, 30);
+    }
+
+    #[test]
+    fn test_resync_after_corrupt_bytes() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { quiet: true, resync_on_corruption: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+
+        // One garbage byte between the last two records, and a torn header at the end
+        let mut binary_data = create_test_binary();
+        binary_data.insert(24, 0xA5);
+        binary_data.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xF0, 0x01]);
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let messages: Vec<_> = parsed_logs.iter().map(|log| log.formatted_message.as_str()).collect();
+        assert_eq!(messages, ["Trigger no <missing> at <missing>", "Trigger no 42 at 100", "System started"]);
+        assert_eq!(parser.skipped_bytes(), 10);
+        assert!(parser.warnings().iter().any(|warning| warning.contains("Skipped 10 corrupt bytes")));
+
+        // A stream ending in the same bytes is resynced the same way
+        let mut decoder = parser.stream_decoder(5);
+        let mut streamed = decoder.push(&binary_data).unwrap();
+        streamed.extend(decoder.finish().unwrap());
+        assert_eq!(streamed, parsed_logs);
+
+        // A known offset whose arguments would run past the end is skipped too
+        let mut binary_data = create_test_binary();
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        binary_data.extend_from_slice(&((15u32 << 28) | 47).to_le_bytes());
+        binary_data.extend_from_slice(&4000u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        let parser = SyslogParser::with_config(dict_file.path(), ParserConfig { quiet: true, resync_on_corruption: true, ..Default::default() }).unwrap();
+        let mut decoder = parser.stream_decoder(5);
+        let mut streamed = decoder.push(&binary_data).unwrap();
+        assert_eq!(streamed.len(), 3);
+        streamed.extend(decoder.finish().unwrap());
+        let timestamps: Vec<_> = streamed.iter().map(|log| log.timestamp_formatted.as_str()).collect();
+        assert_eq!(timestamps, ["0ms", "1000ms", "2000ms", "4000ms"]);
+        assert_eq!(parser.skipped_bytes(), 8);
+
+        // Without resync the garbage byte misaligns the rest of the capture
+        let config = ParserConfig { quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert!(!parsed_logs.iter().any(|log| log.formatted_message == "System started"));
+        assert_eq!(parser.skipped_bytes(), 0);
+    }
+
+    #[test]
+    fn test_prefer_dictionary_arg_count() {
+        let dict_file = create_test_dictionary();
+        // The first record packs 1 argument but carries the 2 its entry declares
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&1000u32.to_le_bytes());
+        binary_data.extend_from_slice(&(1u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&42u32.to_le_bytes());
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&2000u32.to_le_bytes());
+        binary_data.extend_from_slice(&47u32.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let config = ParserConfig { quiet: true, prefer_dictionary_arg_count: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let messages: Vec<_> = parsed_logs.iter().map(|log| log.formatted_message.as_str()).collect();
+        assert_eq!(messages, ["Trigger no 42 at 100", "System started"]);
+        assert_eq!(parser.corrected_arg_counts(), 1);
+        assert!(parser.warnings().iter().any(|warning| warning.starts_with("1 records declared a different argument count")));
+
+        // Records read while probing for a boundary are not counted again
+        let len = binary_data.len() as u64;
+        let parsed_logs = parser.parse_binary_range_bytes(temp_binary.path(), 0, len, 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert_eq!(parser.corrected_arg_counts(), 2);
+
+        // Trusting the packed count reads the second argument as the next record's timestamp
+        let config = ParserConfig { quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert!(!parsed_logs.iter().any(|log| log.formatted_message == "System started"));
+        assert_eq!(parser.corrected_arg_counts(), 0);
+    }
+
+    #[test]
+    fn test_big_endian_records() {
+        let dict_file = create_test_dictionary();
+        let little_endian = create_test_binary();
+        // Same records with every 32-bit word byte-swapped
+        let big_endian: Vec<u8> = little_endian.chunks(4)
+            .flat_map(|word| word.iter().rev().copied())
+            .collect();
+        let temp_binary = NamedTempFile::new().unwrap();
+
+        let parser = SyslogParser::new(dict_file.path()).unwrap();
+        std::fs::write(temp_binary.path(), &little_endian).unwrap();
+        let expected = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(expected[1].formatted_message, "Trigger no 42 at 100");
+
+        let parser = SyslogParser::with_byte_order(dict_file.path(), ByteOrder::Big).unwrap();
+        std::fs::write(temp_binary.path(), &big_endian).unwrap();
+        assert_eq!(parser.parse_binary(temp_binary.path(), 5).unwrap(), expected);
+        assert_eq!(parser.decode_record_at(&big_endian, 8).unwrap(), expected[1]);
+    }
+
+    #[test]
+    fn test_timestamp_wraparound() {
+        let dict_file = create_test_dictionary();
+        let parser = SyslogParser::with_config(dict_file.path(), ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let write_capture = |timestamps: &[u32]| {
+            let mut binary_data = Vec::new();
+            for timestamp in timestamps {
+                binary_data.extend_from_slice(&timestamp.to_le_bytes());
+                binary_data.extend_from_slice(&47u32.to_le_bytes());
+            }
+            let temp_binary = NamedTempFile::new().unwrap();
+            std::fs::write(temp_binary.path(), binary_data).unwrap();
+            temp_binary
+        };
+
+        let temp_binary = write_capture(&[4294967000, 500, 1000]);
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let raw: Vec<u32> = parsed_logs.iter().map(|log| log.timestamp_raw).collect();
+        assert_eq!(raw, [4294967000, 500, 1000]);
+        let monotonic: Vec<u64> = parsed_logs.iter().map(|log| log.timestamp_monotonic_ms).collect();
+        assert_eq!(monotonic, [4294967000, (1 << 32) + 500, (1 << 32) + 1000]);
+        assert!(monotonic.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // A reboot to 0 restarts the count, a small backward glitch does not
+        let temp_binary = write_capture(&[4294967000, 500, 400, 0, 100]);
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let monotonic: Vec<u64> = parsed_logs.iter().map(|log| log.timestamp_monotonic_ms).collect();
+        assert_eq!(monotonic, [4294967000, (1 << 32) + 500, (1 << 32) + 400, 0, 100]);
+
+        // A reboot whose first record is already a few milliseconds in restarts it too
+        let temp_binary = write_capture(&[4294967000, 500, 90000, 12, 300, 250]);
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let monotonic: Vec<u64> = parsed_logs.iter().map(|log| log.timestamp_monotonic_ms).collect();
+        assert_eq!(monotonic, [4294967000, (1 << 32) + 500, (1 << 32) + 90000, 12, 300, 250]);
+    }
+
+    #[test]
+    fn test_parse_binary_iter() {
+        let dict_file = create_test_dictionary();
+        let config = ParserConfig { quiet: true, truncation_policy: TruncationPolicy::Error, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let mut binary_data = create_test_binary();
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let expected = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let mut logs = parser.parse_binary_iter(temp_binary.path(), 5).unwrap();
+        assert_eq!(logs.next().unwrap().unwrap(), expected[0]);
+        let rest: Vec<ParsedLog> = logs.map(Result::unwrap).collect();
+        assert_eq!(rest, &expected[1..]);
+
+        // Complete records are still yielded before the truncation error ends the iteration
+        binary_data.extend_from_slice(&3000u32.to_le_bytes());
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+        let items: Vec<Result<ParsedLog>> = parser.parse_binary_iter(temp_binary.path(), 5).unwrap().collect();
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(Result::is_ok));
+        assert!(items[3].as_ref().unwrap_err().downcast_ref::<DecodeError>().is_some());
+
+        // Stats gathered on the fly match the ones over the collected logs
+        let mut stats = DecodeStats::default();
+        expected.iter().for_each(|log| stats.add(log));
+        assert_eq!(parser.summary_line_from_stats(&stats), parser.summary_line(&expected));
+    }
+
+    #[test]
+    fn test_inline_string_arguments() {
+        let mut dict_file = NamedTempFile::new().unwrap();
+        write!(dict_file, "2;3;net.c:10;NET;Connected to %s port %d\x000;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        dict_file.flush().unwrap();
+        let parser = SyslogParser::with_config(dict_file.path(), ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let sys_init_offset = "2;3;net.c:10;NET;Connected to %s port %d\x00".len() as u32;
+
+        // Length word, 6 string bytes padded to 8, then the %d word
+        let mut binary_data = Vec::new();
+        binary_data.extend_from_slice(&100u32.to_le_bytes());
+        binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes());
+        binary_data.extend_from_slice(&6u32.to_le_bytes());
+        binary_data.extend_from_slice(b"wlan0\xc3\0\0");
+        binary_data.extend_from_slice(&8080u32.to_le_bytes());
+        binary_data.extend_from_slice(&200u32.to_le_bytes());
+        binary_data.extend_from_slice(&sys_init_offset.to_le_bytes());
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert_eq!(parsed_logs[0].formatted_message, "Connected to wlan0\u{FFFD} port 8080");
+        assert_eq!(parsed_logs[1].module_name, "SYS_INIT");
+
+        // A chunk ending inside the string bytes defers the whole record
+        let (entries, remainder) = parser.parse_chunk(&binary_data[..16]).unwrap();
+        assert!(entries.is_empty());
+        assert_eq!(remainder, &binary_data[..16]);
+        let mut decoder = parser.stream_decoder(5);
+        assert!(decoder.push(&binary_data[..16]).unwrap().is_empty());
+        let logs = decoder.push(&binary_data[16..]).unwrap();
+        assert_eq!(logs, parsed_logs);
+
+        // An implausible length is reported instead of swallowing the capture
+        let mut corrupt = binary_data[..8].to_vec();
+        corrupt.extend_from_slice(&100_000u32.to_le_bytes());
+        corrupt.extend_from_slice(&8080u32.to_le_bytes());
+        let log = parser.decode_record_at(&corrupt, 0).unwrap();
+        assert_eq!(log.formatted_message, "Connected to <invalid string length 100000> port 8080");
+    }
+
+    #[test]
+    fn test_core_ids_separate_interleaved_streams() {
+        let dict_file = create_test_dictionary();
+        // Two cores tag their records in bits 24-25 of the offset field
+        let mut binary_data = Vec::new();
+        for (timestamp, core, log_id, arguments) in [(100u32, 0u32, 47u32, &[][..]), (50, 1, 0, &[1, 2][..]),
+                                                     (200, 0, 85, &[7][..]), (150, 1, 47, &[][..])] {
+            binary_data.extend_from_slice(&timestamp.to_le_bytes());
+            binary_data.extend_from_slice(&((arguments.len() as u32) << 28 | core << 24 | log_id).to_le_bytes());
+            for argument in arguments {
+                binary_data.extend_from_slice(&u32::to_le_bytes(*argument));
+            }
+        }
+        let temp_binary = NamedTempFile::new().unwrap();
+        std::fs::write(temp_binary.path(), &binary_data).unwrap();
+
+        let config = ParserConfig { core_id_field: Some(CoreIdField { shift: 24, width: 2 }), quiet: true, ..Default::default() };
+        let parser = SyslogParser::with_config(dict_file.path(), config).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        let cores: Vec<_> = parsed_logs.iter().map(|log| (log.core, log.module_name.as_str())).collect();
+        assert_eq!(cores, [(Some(0), "SYS_INIT"), (Some(1), "TEST_MODULE"), (Some(0), "MAIN_APP"), (Some(1), "SYS_INIT")]);
+        assert_eq!(parsed_logs[1].formatted_message, "Trigger no 1 at 2");
+        assert_eq!(parser.decode_record_at(&binary_data, 8).unwrap().core, Some(1));
+
+        let options = FormatOptions { separate_cores: true, ..Default::default() };
+        let lines = parser.format_logs_with(&parsed_logs, &options);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "=== core 0 (2 entries) ===");
+        assert!(lines[2].ends_with("Processing item 7"));
+        assert_eq!(lines[3], "=== core 1 (2 entries) ===");
+
+        // Each core's stream is monotonic on its own, the merged capture is not
+        let merged = session::parse_log_sessions(&parser.format_logs(&parsed_logs).join("\n"));
+        assert_eq!(merged[0].timestamp_regressions.len(), 2);
+        for (_, core_logs) in partition_by_core(&parsed_logs) {
+            let core_logs: Vec<ParsedLog> = core_logs.into_iter().cloned().collect();
+            let sessions = session::parse_log_sessions(&parser.format_logs(&core_logs).join("\n"));
+            assert!(sessions[0].timestamp_regressions.is_empty());
+        }
+
+        // Without the field the tagged offsets match no dictionary entry
+        let parser = SyslogParser::with_config(dict_file.path(), ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let parsed_logs = parser.parse_binary(temp_binary.path(), 5).unwrap();
+        assert_eq!(parsed_logs.len(), 2);
+        assert!(parsed_logs.iter().all(|log| log.core.is_none()));
     }
 }
@@ -0,0 +1,593 @@
+//! Boot-cycle session splitting for decoded log text.
+//!
+//! Shared by the backend and the wasm frontend so both split sessions the same way.
+//!
+//! A session's `timestamp` is anchored on its first `Date time set rcvd` line. Later
+//! date lines in the same session (clock re-syncs) do not move the anchor; they are
+//! recorded in `time_adjustments` so absolute times can be corrected from that point on.
+//!
+//! A line matching one of `SessionOptions::reset_markers` (by default the `System Reset
+//! Cause` banner) starts a new boot cycle, as does a return to `0ms`. Any other backward
+//! jump is kept in the session and recorded in `timestamp_regressions`, since it points at
+//! a clock glitch or interleaved buffers rather than a reboot.
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogSession {
+    pub id: usize,
+    pub content: String,
+    /// Absolute time of the session's first date line
+    pub timestamp: Option<SessionTimestamp>,
+    /// `timestamp` rendered in `SessionOptions::timestamp_format`
+    #[serde(default)]
+    pub timestamp_display: Option<String>,
+    /// Clock re-syncs received after the session's first date line
+    #[serde(default)]
+    pub time_adjustments: Vec<TimeAdjustment>,
+    /// Backward timestamp jumps that did not start a new boot cycle
+    #[serde(default)]
+    pub timestamp_regressions: Vec<TimestampRegression>,
+}
+
+/// A log line whose uptime is earlier than the line before it in the same session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampRegression {
+    /// Zero-based line index within the session content
+    pub line: usize,
+    /// Uptime of the previous timestamped line in milliseconds
+    pub previous_ms: u64,
+    /// Uptime of the regressing line in milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// A later `Date time set rcvd` line within a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeAdjustment {
+    /// Device uptime of the date line in milliseconds, when the line carries one
+    pub timestamp_ms: Option<u64>,
+    /// Epoch seconds received from the re-sync
+    pub epoch: u64,
+}
+
+/// Absolute time received on a `Date time set rcvd` line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionTimestamp {
+    /// Epoch seconds; firmware that sends milliseconds is normalized to seconds
+    Epoch { seconds: u64 },
+    /// An ISO-8601 date as the firmware sent it, with its time in epoch milliseconds
+    Iso8601 { text: String, epoch_ms: u64 },
+}
+
+impl SessionTimestamp {
+    pub fn epoch_ms(&self) -> u64 {
+        match self {
+            SessionTimestamp::Epoch { seconds } => seconds.saturating_mul(1000),
+            SessionTimestamp::Iso8601 { epoch_ms, .. } => *epoch_ms,
+        }
+    }
+
+    pub fn epoch_seconds(&self) -> u64 {
+        self.epoch_ms() / 1000
+    }
+
+    /// Render the time, e.g. `Epoch: 1756474625` or `2025-08-29T13:37:05.000Z`
+    pub fn format(&self, format: TimestampFormat) -> String {
+        format_absolute_time(self.epoch_ms(), format)
+    }
+}
+
+/// Epoch values above this are taken as milliseconds; in seconds it is the year 33658
+const EPOCH_MILLIS_THRESHOLD: u64 = 1_000_000_000_000;
+
+pub fn parse_date_time_line(line: &str) -> Option<SessionTimestamp> {
+    // Parse both formats:
+    // "Date time set rcvd: 1756474625" (standalone)
+    // "69808ms		[SYS_PROTOCOL_DATE_TIME]	Date time set rcvd: 1756474625" (with timestamp and module)
+    // The value may also be epoch milliseconds or an ISO-8601 date
+    
+    let start_pos = line.find("Date time set rcvd:")?;
+    let timestamp_str = line[start_pos + "Date time set rcvd:".len()..].trim();
+    if let Ok(epoch) = timestamp_str.parse::<u64>() {
+        let seconds = if epoch > EPOCH_MILLIS_THRESHOLD { epoch / 1000 } else { epoch };
+        return Some(SessionTimestamp::Epoch { seconds });
+    }
+    parse_iso8601(timestamp_str).map(|epoch_ms| SessionTimestamp::Iso8601 {
+        text: timestamp_str.to_string(),
+        epoch_ms,
+    })
+}
+
+/// Parse `YYYY-MM-DDTHH:MM:SS[.fff][Z|±HH:MM]` (a space may replace the `T`) into epoch
+/// milliseconds; a date without a zone is taken as UTC
+fn parse_iso8601(text: &str) -> Option<u64> {
+    let (date, time) = text.split_once(['T', ' '])?;
+    let mut date_fields = date.splitn(3, '-').map(|field| field.parse::<u64>().ok());
+    let (year, month, day) = (date_fields.next()??, date_fields.next()??, date_fields.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Zone designator: the offset is subtracted to get UTC
+    let (time, offset_secs) = match time.strip_suffix('Z') {
+        Some(time) => (time, 0),
+        None => match time.rfind(['+', '-']) {
+            Some(sign_pos) => {
+                let (hours, minutes) = time[sign_pos + 1..].split_once(':')?;
+                let offset = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+                (&time[..sign_pos], if &time[sign_pos..sign_pos + 1] == "-" { -offset } else { offset })
+            }
+            None => (time, 0),
+        },
+    };
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time_fields = time.splitn(3, ':').map(|field| field.parse::<u64>().ok());
+    let (hour, minute, second) = (time_fields.next()??, time_fields.next()??, time_fields.next()??);
+    if hour > 23 || minute > 59 || second > 60 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let millis = format!("{:0<3}", &fraction[..fraction.len().min(3)]).parse::<u64>().ok()?;
+
+    let secs = days_from_civil(year, month, day)? * 86_400 + hour * 3600 + minute * 60 + second;
+    let secs = u64::try_from(secs as i64 - offset_secs).ok()?;
+    Some(secs * 1000 + millis)
+}
+
+pub fn epoch_to_local_time(epoch: u64) -> String {
+    // For backend, we'll use a simpler format
+    // This could be enhanced to use proper datetime formatting
+    format!("Epoch: {}", epoch)
+}
+
+/// How anchored (absolute) timestamps are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    /// `Epoch: 1756474625`, expanded to a local date by the frontend
+    #[default]
+    Epoch,
+    /// RFC 3339 in UTC with milliseconds, e.g. `2024-01-02T03:04:05.678Z`
+    Iso8601,
+}
+
+/// Render an absolute time given in milliseconds since the Unix epoch
+pub fn format_absolute_time(epoch_ms: u64, format: TimestampFormat) -> String {
+    match format {
+        TimestampFormat::Epoch => epoch_to_local_time(epoch_ms / 1000),
+        TimestampFormat::Iso8601 => {
+            let secs_of_day = (epoch_ms / 1000) % 86_400;
+            let (year, month, day) = civil_from_days(epoch_ms / 86_400_000);
+            format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+                    year, month, day,
+                    secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60,
+                    epoch_ms % 1000)
+        }
+    }
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) proleptic Gregorian date
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each 400-year era
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // March = 0
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Convert a proleptic Gregorian date to days since 1970-01-01, `None` before 1970
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    // Same March-based eras as `civil_from_days`
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146_097 + day_of_era).checked_sub(719_468)
+}
+
+/// Extract timestamp in milliseconds from a log line
+/// Expected format: "1234ms\t\t[MODULE]\t\tmessage"
+fn extract_timestamp_from_line(line: &str) -> Option<u64> {
+    // The decoder pads the timestamp field with spaces before the tab
+    let (timestamp_field, _) = line.split_once('\t')?;
+    timestamp_field.trim_end().strip_suffix("ms")?.parse::<u64>().ok()
+}
+
+/// Reset banner that starts a session unless other `reset_markers` are configured
+pub const DEFAULT_RESET_MARKER: &str = "System Reset Cause";
+
+/// Options controlling where sessions split and how their content is built
+#[derive(Debug, Clone)]
+pub struct SessionOptions {
+    /// Keep blank lines and leading indentation, stripping only trailing whitespace and
+    /// collapsing runs of blank lines into one; by default lines are trimmed and blank
+    /// lines dropped
+    pub normalize_whitespace: bool,
+    /// Format of each session's `timestamp_display`
+    pub timestamp_format: TimestampFormat,
+    /// A line matching any of these starts a new session, for firmware with its own reset
+    /// banner such as `Reboot reason:` or `--- BOOT ---`
+    pub reset_markers: Vec<Regex>,
+    /// Start a new session when timestamps return to 0ms after non-zero ones
+    pub use_timestamp_reset: bool,
+    /// Suppress the status lines printed while splitting, like `ParserConfig::quiet`
+    pub quiet: bool,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        Self {
+            normalize_whitespace: false,
+            timestamp_format: TimestampFormat::default(),
+            reset_markers: vec![Regex::new(&regex::escape(DEFAULT_RESET_MARKER)).unwrap()],
+            use_timestamp_reset: true,
+            quiet: false,
+        }
+    }
+}
+
+/// Append a line to the session content, honoring the whitespace options
+fn push_line(session: &mut String, line: &str, options: &SessionOptions) {
+    let line = if options.normalize_whitespace { line.trim_end() } else { line.trim() };
+    // A blank line directly after another blank line is collapsed away
+    if line.is_empty() && (!options.normalize_whitespace || session.ends_with("\n\n")) {
+        return;
+    }
+    session.push_str(line);
+    session.push('\n');
+}
+
+pub fn parse_log_sessions(log_content: &str) -> Vec<LogSession> {
+    parse_log_sessions_with_options(log_content, &SessionOptions::default())
+}
+
+/// Split sessions over decoded output from several captures of one device, in order
+///
+/// The outputs are concatenated before splitting, so a boot cycle that continues from
+/// one file into the next becomes a single session instead of two.
+pub fn parse_log_sessions_merged<S: AsRef<str>>(log_contents: &[S], options: &SessionOptions) -> Vec<LogSession> {
+    let merged = log_contents.iter()
+        .map(|content| content.as_ref().trim_end_matches('\n'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    parse_log_sessions_with_options(&merged, options)
+}
+
+pub fn parse_log_sessions_with_options(log_content: &str, options: &SessionOptions) -> Vec<LogSession> {
+    let mut splitter = SessionSplitter::new(options.clone());
+    let mut sessions: Vec<LogSession> = log_content.lines()
+        .filter_map(|line| splitter.push_line(line))
+        .collect();
+    sessions.extend(splitter.finish());
+    
+    status!(options, "Parsed {} sessions from log content", sessions.len());
+    for (i, session) in sessions.iter().enumerate() {
+        status!(options, "Session {}: {} lines, timestamp: {:?}", 
+                 i, 
+                 session.content.lines().count(),
+                 session.timestamp);
+        for regression in &session.timestamp_regressions {
+            warning!(options, "Warning: session {} line {} jumps back from {}ms to {}ms",
+                     i, regression.line, regression.previous_ms, regression.timestamp_ms);
+        }
+    }
+    
+    sessions
+}
+
+/// Splits decoded lines into sessions as they arrive, for callers that stream sessions
+/// out before the whole capture is decoded.
+///
+/// `push_line` returns a session once the line after it starts a new one, and `finish`
+/// returns the last. Sessions with a single line are dropped, and ids count only the
+/// sessions returned, matching `parse_log_sessions_with_options`.
+pub struct SessionSplitter {
+    options: SessionOptions,
+    current_session: String,
+    next_id: usize,
+    current_session_time: Option<SessionTimestamp>,
+    current_time_adjustments: Vec<TimeAdjustment>,
+    // Whether the current session has had non-zero timestamps, so a later 0ms is a reboot
+    seen_non_zero_timestamp: bool,
+    current_regressions: Vec<TimestampRegression>,
+    last_timestamp_ms: Option<u64>,
+}
+
+impl SessionSplitter {
+    pub fn new(options: SessionOptions) -> Self {
+        Self {
+            options,
+            current_session: String::new(),
+            next_id: 0,
+            current_session_time: None,
+            current_time_adjustments: Vec::new(),
+            seen_non_zero_timestamp: false,
+            current_regressions: Vec::new(),
+            last_timestamp_ms: None,
+        }
+    }
+
+    /// Add one decoded line, returning the previous session if this line ends it
+    pub fn push_line(&mut self, raw_line: &str) -> Option<LogSession> {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            // Blank lines never start or end a session; normalized content keeps them
+            if !self.current_session.is_empty() {
+                push_line(&mut self.current_session, raw_line, &self.options);
+            }
+            return None;
+        }
+        
+        // Skip decoder messages that shouldn't be displayed
+        if line.contains("Using default dictionnay") || 
+           line.contains("Using default dictionary") ||
+           line.starts_with("Using default") {
+            return None;
+        }
+        
+        // Check for "Date time set rcvd" line to extract epoch timestamp
+        if let Some(session_time) = parse_date_time_line(line) {
+            if self.current_session_time.is_none() {
+                self.current_session_time = Some(session_time);
+            } else {
+                self.current_time_adjustments.push(TimeAdjustment {
+                    timestamp_ms: extract_timestamp_from_line(line),
+                    epoch: session_time.epoch_seconds(),
+                });
+            }
+            push_line(&mut self.current_session, raw_line, &self.options);
+            return None;
+        }
+        
+        // Extract timestamp from log line to track boot cycle logic
+        let timestamp_ms = extract_timestamp_from_line(line);
+        
+        // A reset banner starts a new session, as does a return to 0ms after
+        // non-zero timestamps (a new boot cycle)
+        let starts_session = self.options.reset_markers.iter().any(|marker| marker.is_match(line))
+            || (self.options.use_timestamp_reset && timestamp_ms == Some(0) && self.seen_non_zero_timestamp);
+        if starts_session {
+            let finished = self.take_session();
+            self.last_timestamp_ms = timestamp_ms;
+            
+            // Add the line that starts it to the new session
+            push_line(&mut self.current_session, raw_line, &self.options);
+            return finished;
+        }
+
+        // A backward jump that is not a reset to 0ms is a clock anomaly, not a reboot
+        if let (Some(ts), Some(previous_ms)) = (timestamp_ms, self.last_timestamp_ms) {
+            if ts < previous_ms {
+                self.current_regressions.push(TimestampRegression {
+                    line: self.current_session.lines().count(),
+                    previous_ms,
+                    timestamp_ms: ts,
+                });
+            }
+        }
+        if timestamp_ms.is_some() {
+            self.last_timestamp_ms = timestamp_ms;
+        }
+
+        // Add the line to the current session
+        push_line(&mut self.current_session, raw_line, &self.options);
+        
+        // Track if we've seen non-zero timestamps
+        if let Some(ts) = timestamp_ms {
+            if ts > 0 {
+                self.seen_non_zero_timestamp = true;
+            }
+        }
+        None
+    }
+
+    /// End the input, returning the last session
+    pub fn finish(mut self) -> Option<LogSession> {
+        self.take_session()
+    }
+
+    /// Close the current session and reset for the next, returning it unless it has only one line
+    fn take_session(&mut self) -> Option<LogSession> {
+        if self.current_session.is_empty() {
+            return None;
+        }
+        let content = self.current_session.trim().to_string();
+        let time_adjustments = std::mem::take(&mut self.current_time_adjustments);
+        let timestamp_regressions = std::mem::take(&mut self.current_regressions);
+        let timestamp = self.current_session_time.take();
+        let timestamp_display = timestamp.as_ref().map(|timestamp| timestamp.format(self.options.timestamp_format));
+        self.current_session.clear();
+        self.seen_non_zero_timestamp = false;
+
+        // Sessions with only one line are likely not useful boot sessions
+        if content.lines().filter(|line| !line.trim().is_empty()).count() <= 1 {
+            return None;
+        }
+        self.next_id += 1;
+        Some(LogSession {
+            id: self.next_id - 1,
+            content,
+            timestamp,
+            timestamp_display,
+            time_adjustments,
+            timestamp_regressions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_resync_keeps_first_anchor() {
+        let log_content = "\
+0ms\t[SYS]\tSystem Reset Cause: POR
+100ms\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: 1756474625
+5000ms\t[APP]\tWorking
+9000ms\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: 1756474640";
+
+        let sessions = parse_log_sessions(log_content);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].timestamp, Some(SessionTimestamp::Epoch { seconds: 1756474625 }));
+        assert_eq!(sessions[0].time_adjustments, vec![TimeAdjustment {
+            timestamp_ms: Some(9000),
+            epoch: 1756474640,
+        }]);
+        assert!(sessions[0].content.contains("Date time set rcvd: 1756474640"));
+    }
+
+    #[test]
+    fn test_date_time_line_values() {
+        let parse = |value: &str| parse_date_time_line(&format!("100ms\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: {}", value));
+
+        assert_eq!(parse("1756474625"), Some(SessionTimestamp::Epoch { seconds: 1756474625 }));
+        // Milliseconds are recognized by magnitude and normalized to seconds
+        assert_eq!(parse("1756474625123"), Some(SessionTimestamp::Epoch { seconds: 1756474625 }));
+
+        let iso = parse("2025-08-29T13:37:05.250Z").unwrap();
+        assert_eq!(iso, SessionTimestamp::Iso8601 { text: "2025-08-29T13:37:05.250Z".to_string(), epoch_ms: 1_756_474_625_250 });
+        assert_eq!(iso.epoch_seconds(), 1756474625);
+        assert_eq!(parse("2025-08-29 15:37:05+02:00").unwrap().epoch_ms(), 1_756_474_625_000);
+        assert_eq!(parse("2024-02-29T00:00:00").unwrap().epoch_ms(), 1_709_164_800_000);
+
+        assert_eq!(parse("2025-13-01T00:00:00Z"), None);
+        assert_eq!(parse("soon"), None);
+        assert_eq!(parse_date_time_line("100ms\t[APP]\tStarted"), None);
+
+        // The typed timestamp is what the JSON carries, not a formatted string
+        let json = serde_json::to_value(SessionTimestamp::Epoch { seconds: 1756474625 }).unwrap();
+        assert_eq!(json, serde_json::json!({"kind": "epoch", "seconds": 1756474625}));
+    }
+
+    #[test]
+    fn test_whitespace_normalization() {
+        let log_content = "0ms\t[SYS]\tSystem Reset Cause: POR\n\n\n\n100ms\t[APP]\tStarted   \n\n200ms\t[APP]\tReady";
+
+        // By default lines are trimmed and blank lines dropped
+        let sessions = parse_log_sessions(log_content);
+        assert_eq!(sessions[0].content, "0ms\t[SYS]\tSystem Reset Cause: POR\n100ms\t[APP]\tStarted\n200ms\t[APP]\tReady");
+
+        let options = SessionOptions { normalize_whitespace: true, ..Default::default() };
+        let sessions = parse_log_sessions_with_options(log_content, &options);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].content,
+                   "0ms\t[SYS]\tSystem Reset Cause: POR\n\n100ms\t[APP]\tStarted\n\n200ms\t[APP]\tReady");
+    }
+
+    #[test]
+    fn test_iso8601_timestamps() {
+        assert_eq!(format_absolute_time(1_704_164_645_678, TimestampFormat::Iso8601), "2024-01-02T03:04:05.678Z");
+        assert_eq!(format_absolute_time(0, TimestampFormat::Iso8601), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_absolute_time(951_782_400_000, TimestampFormat::Iso8601), "2000-02-29T00:00:00.000Z");
+        assert_eq!(format_absolute_time(1_704_164_645_678, TimestampFormat::Epoch), "Epoch: 1704164645");
+
+        let timestamp = SessionTimestamp::Epoch { seconds: 1756474625 };
+        assert_eq!(timestamp.format(TimestampFormat::Iso8601), "2025-08-29T13:37:05.000Z");
+        assert_eq!(timestamp.format(TimestampFormat::Epoch), "Epoch: 1756474625");
+
+        // Sessions carry the formatted time next to the typed one
+        let log_content = "0ms\t[SYS]\tSystem Reset Cause: POR\n100ms\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: 1756474625";
+        let sessions = parse_log_sessions(log_content);
+        assert_eq!(sessions[0].timestamp_display.as_deref(), Some("Epoch: 1756474625"));
+        let options = SessionOptions { timestamp_format: TimestampFormat::Iso8601, ..Default::default() };
+        let sessions = parse_log_sessions_with_options(log_content, &options);
+        assert_eq!(sessions[0].timestamp, Some(SessionTimestamp::Epoch { seconds: 1756474625 }));
+        assert_eq!(sessions[0].timestamp_display.as_deref(), Some("2025-08-29T13:37:05.000Z"));
+    }
+
+    #[test]
+    fn test_custom_reset_markers() {
+        let log_content = "\
+0ms\t[SYS]\t--- BOOT ---
+100ms\t[APP]\tStarted
+0ms\t[SYS]\tWatchdog fired
+50ms\t[SYS]\t--- BOOT ---
+150ms\t[APP]\tStarted
+900ms\t[SYS]\tSystem Reset Cause: POR
+950ms\t[APP]\tStarted";
+
+        let options = SessionOptions {
+            reset_markers: vec![Regex::new(r"--- BOOT ---|Reboot reason:").unwrap()],
+            use_timestamp_reset: false,
+            ..Default::default()
+        };
+        let sessions = parse_log_sessions_with_options(log_content, &options);
+        let line_counts: Vec<usize> = sessions.iter().map(|session| session.content.lines().count()).collect();
+        assert_eq!(line_counts, [3, 4]);
+        assert!(sessions[1].content.starts_with("50ms\t[SYS]\t--- BOOT ---"));
+
+        // The default banner and the 0ms reset split the same lines differently
+        let line_counts: Vec<usize> = parse_log_sessions(log_content).iter()
+            .map(|session| session.content.lines().count())
+            .collect();
+        assert_eq!(line_counts, [2, 3, 2]);
+    }
+
+    #[test]
+    fn test_session_continues_across_files() {
+        let first_file = "0ms\t[SYS]\tSystem Reset Cause: POR\n100ms\t[APP]\tStarted\n";
+        let second_file = "200ms\t[APP]\tStill running\n300ms\t[APP]\tDone";
+
+        let merged = parse_log_sessions_merged(&[first_file, second_file], &SessionOptions::default());
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].content.lines().count(), 4);
+        assert!(merged[0].content.ends_with("300ms\t[APP]\tDone"));
+
+        // Split independently, the second file becomes its own session
+        assert_eq!(parse_log_sessions(first_file).len() + parse_log_sessions(second_file).len(), 2);
+    }
+
+    #[test]
+    fn test_splitter_emits_sessions_as_they_end() {
+        let mut splitter = SessionSplitter::new(SessionOptions::default());
+        assert!(splitter.push_line("0ms\t[SYS]\tSystem Reset Cause: POR").is_none());
+        assert!(splitter.push_line("100ms\t[APP]\tStarted").is_none());
+
+        // The next boot cycle closes the first session
+        let first = splitter.push_line("0ms\t[SYS]\tSystem Reset Cause: WDT").unwrap();
+        assert_eq!(first.id, 0);
+        assert_eq!(first.content, "0ms\t[SYS]\tSystem Reset Cause: POR\n100ms\t[APP]\tStarted");
+
+        // A single-line session is dropped without using up an id
+        assert!(splitter.push_line("0ms\t[SYS]\tSystem Reset Cause: BOR").is_none());
+        assert!(splitter.push_line("50ms\t[APP]\tStarted").is_none());
+        let last = splitter.finish().unwrap();
+        assert_eq!(last.id, 1);
+        assert!(last.content.ends_with("50ms\t[APP]\tStarted"));
+    }
+
+    #[test]
+    fn test_mid_session_backward_jump_is_flagged() {
+        let log_content = "\
+0ms\t[SYS]\tSystem Reset Cause: POR
+1000ms\t[APP]\tStarted
+5000ms\t[APP]\tWorking
+3000ms\t[APP]\tInterleaved buffer
+6000ms\t[APP]\tDone";
+
+        let sessions = parse_log_sessions(log_content);
+        assert_eq!(sessions.len(), 1, "a backward jump must not split the session");
+        assert_eq!(sessions[0].content.lines().count(), 5);
+        assert_eq!(sessions[0].timestamp_regressions, vec![TimestampRegression {
+            line: 3,
+            previous_ms: 5000,
+            timestamp_ms: 3000,
+        }]);
+
+        // A return to 0ms is still a reboot and not a regression
+        let rebooted = format!("{}\n0ms\t[SYS]\tBoot\n10ms\t[APP]\tStarted", log_content);
+        let sessions = parse_log_sessions(&rebooted);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].timestamp_regressions.len(), 1);
+        assert!(sessions[1].timestamp_regressions.is_empty());
+    }
+}
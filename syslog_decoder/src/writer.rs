@@ -0,0 +1,174 @@
+//! Buffered output of decoded logs for streaming consumers.
+//!
+//! `SyslogParser::stream_writer` wraps any `Write` and renders logs as text lines or NDJSON
+//! as they are decoded. Output is buffered and handed to the writer according to a
+//! `FlushPolicy`: a live tail wants every entry as soon as it is decoded, while a bulk
+//! export is faster when the writer sees large, infrequent writes.
+use std::io::{self, Write};
+
+use crate::{FormatOptions, ParsedLog, SyslogParser};
+
+/// When buffered output is written through to the underlying writer and flushed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// After every entry, for live tails
+    #[default]
+    Immediate,
+    /// Once this many entries are buffered
+    EveryEntries(usize),
+    /// Once at least this many bytes are buffered
+    EveryBytes(usize),
+}
+
+/// How each log is rendered
+#[derive(Debug, Clone)]
+pub enum StreamFormat {
+    /// One formatted line per log, as `SyslogParser::format_log` renders it
+    Text(FormatOptions),
+    /// One JSON object per line with the fields of `ParsedLog`
+    Ndjson,
+}
+
+/// Writes decoded logs to `W`, flushing per its `FlushPolicy`
+pub struct StreamWriter<'a, W: Write> {
+    parser: &'a SyslogParser,
+    writer: W,
+    format: StreamFormat,
+    flush_policy: FlushPolicy,
+    buffer: Vec<u8>,
+    buffered_entries: usize,
+}
+
+impl<W: Write> StreamWriter<'_, W> {
+    /// Render logs into the buffer, flushing whenever the policy says so
+    pub fn write_logs(&mut self, logs: &[ParsedLog]) -> io::Result<()> {
+        for log in logs {
+            match &self.format {
+                StreamFormat::Text(options) => self.buffer.extend_from_slice(self.parser.format_log(log, options).as_bytes()),
+                StreamFormat::Ndjson => serde_json::to_writer(&mut self.buffer, log)?,
+            }
+            self.buffer.push(b'\n');
+            self.buffered_entries += 1;
+
+            let due = match self.flush_policy {
+                FlushPolicy::Immediate => true,
+                FlushPolicy::EveryEntries(entries) => self.buffered_entries >= entries,
+                FlushPolicy::EveryBytes(bytes) => self.buffer.len() >= bytes,
+            };
+            if due {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write everything buffered to the underlying writer and flush it
+    pub fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+            self.buffered_entries = 0;
+        }
+        self.writer.flush()
+    }
+
+    /// Flush what is left at the end of the stream and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl SyslogParser {
+    /// Create a writer that renders decoded logs to `writer`, e.g. the output of a `StreamDecoder`
+    pub fn stream_writer<W: Write>(&self, writer: W, format: StreamFormat, flush_policy: FlushPolicy) -> StreamWriter<'_, W> {
+        StreamWriter {
+            parser: self,
+            writer,
+            format,
+            flush_policy,
+            buffer: Vec::new(),
+            buffered_entries: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserConfig;
+
+    /// Records the size of every write and the number of flushes
+    #[derive(Default)]
+    struct CountingWriter {
+        writes: Vec<usize>,
+        flushes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    fn decode_test_logs(parser: &SyslogParser) -> Vec<ParsedLog> {
+        let mut binary_data = Vec::new();
+        for timestamp in 0..5u32 {
+            binary_data.extend_from_slice(&(timestamp * 100).to_le_bytes());
+            binary_data.extend_from_slice(&47u32.to_le_bytes());
+        }
+        parser.stream_decoder(5).push(&binary_data).unwrap()
+    }
+
+    #[test]
+    fn test_flush_policies() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        std::fs::write(&dict_path, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x000;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let parser = SyslogParser::with_config(&dict_path, ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let logs = decode_test_logs(&parser);
+        let line_len = parser.format_log(&logs[0], &FormatOptions::default()).len() + 1;
+
+        let write_all = |flush_policy| {
+            let mut writer = parser.stream_writer(CountingWriter::default(), StreamFormat::Text(FormatOptions::default()), flush_policy);
+            writer.write_logs(&logs).unwrap();
+            writer.finish().unwrap()
+        };
+
+        // One write and flush per entry, plus the final flush
+        let writer = write_all(FlushPolicy::Immediate);
+        assert_eq!(writer.writes, [line_len; 5]);
+        assert_eq!(writer.flushes, 6);
+
+        // Two batches of two entries, the last entry on finish
+        let writer = write_all(FlushPolicy::EveryEntries(2));
+        assert_eq!(writer.writes, [2 * line_len, 2 * line_len, line_len]);
+        assert_eq!(writer.flushes, 3);
+
+        // Nothing reaches the writer until three lines fill the byte threshold
+        let writer = write_all(FlushPolicy::EveryBytes(3 * line_len - 1));
+        assert_eq!(writer.writes, [3 * line_len, 2 * line_len]);
+        assert_eq!(writer.flushes, 2);
+    }
+
+    #[test]
+    fn test_ndjson_output() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dict_path = temp_dir.path().join("dictionary.log");
+        std::fs::write(&dict_path, "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+        let parser = SyslogParser::with_config(&dict_path, ParserConfig { quiet: true, ..Default::default() }).unwrap();
+        let logs = parser.stream_decoder(5).push(&[0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+
+        let mut writer = parser.stream_writer(Vec::new(), StreamFormat::Ndjson, FlushPolicy::EveryBytes(4096));
+        writer.write_logs(&logs).unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let parsed: ParsedLog = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(parsed, logs[0]);
+    }
+}
@@ -0,0 +1,262 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+fn write_test_dictionary() -> NamedTempFile {
+    let mut dict_file = NamedTempFile::new().unwrap();
+    write!(dict_file, "2;4;test.c:123;TEST_MODULE;Trigger no %d at %d\x00").unwrap();
+    write!(dict_file, "0;1;init.c:45;SYS_INIT;System started\x00").unwrap();
+    dict_file.flush().unwrap();
+    dict_file
+}
+
+fn write_test_binary() -> NamedTempFile {
+    let mut binary_data = Vec::new();
+    binary_data.extend_from_slice(&1000u32.to_le_bytes()); // timestamp
+    binary_data.extend_from_slice(&(2u32 << 28).to_le_bytes()); // 2 args, byte offset 0
+    binary_data.extend_from_slice(&42u32.to_le_bytes());
+    binary_data.extend_from_slice(&100u32.to_le_bytes());
+    binary_data.extend_from_slice(&2000u32.to_le_bytes()); // timestamp
+    binary_data.extend_from_slice(&47u32.to_le_bytes()); // 0 args, byte offset 47
+
+    let binary_file = NamedTempFile::new().unwrap();
+    std::fs::write(binary_file.path(), binary_data).unwrap();
+    binary_file
+}
+
+/// Serve files over plain HTTP on a local port; unknown paths get a 404
+fn serve_files(files: Vec<(&'static str, Vec<u8>)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            reader.read_line(&mut request_line).unwrap();
+            // Drain the request headers
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or("");
+            match files.iter().find(|(name, _)| *name == path) {
+                Some((_, body)) => {
+                    write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len()).unwrap();
+                    stream.write_all(body).unwrap();
+                }
+                None => {
+                    write!(stream, "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").unwrap();
+                }
+            }
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn run_parser(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_syslog_parser"))
+        .args(args)
+        .output()
+        .expect("Failed to run syslog_parser")
+}
+
+#[test]
+fn test_quiet_mode_prints_only_decoded_logs() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+
+    let output = run_parser(&[
+        dict_file.path().to_str().unwrap(),
+        binary_file.path().to_str().unwrap(),
+        "5",
+        "--quiet",
+    ]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("[TEST_MODULE]\tTrigger no 42 at 100"));
+    assert!(lines[1].ends_with("[SYS_INIT]\tSystem started"));
+    assert!(output.stderr.is_empty());
+    // Piped output is plain text for tools that don't understand ANSI escapes
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_default_mode_prints_status() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+
+    let output = run_parser(&[
+        dict_file.path().to_str().unwrap(),
+        binary_file.path().to_str().unwrap(),
+        "5",
+    ]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Loaded 2 dictionary entries"));
+    assert!(stdout.contains("Parsing binary file"));
+}
+
+#[test]
+fn test_gen_then_decode_round_trip() {
+    let dict_file = write_test_dictionary();
+    let binary_file = NamedTempFile::new().unwrap();
+    let dict_path = dict_file.path().to_str().unwrap();
+    let binary_path = binary_file.path().to_str().unwrap();
+
+    let output = run_parser(&["gen", dict_path, binary_path, "--count", "25", "--seed", "3"]);
+    assert!(output.status.success());
+
+    let output = run_parser(&[dict_path, binary_path, "255", "--quiet"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 25);
+    assert!(stdout.lines().all(|line| line.contains("[TEST_MODULE]") || line.contains("[SYS_INIT]")));
+}
+
+#[test]
+fn test_fail_on_sets_exit_code() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let dict_path = dict_file.path().to_str().unwrap();
+    let binary_path = binary_file.path().to_str().unwrap();
+
+    // SYS_INIT is level 1 (FatalError): failing on level 1 trips, output is still printed
+    let output = run_parser(&[dict_path, binary_path, "5", "--quiet", "--fail-on", "1"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 2);
+
+    // Nothing is as severe as level 0 (Critical)
+    let output = run_parser(&[dict_path, binary_path, "5", "--quiet", "--fail-on", "0"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_reports_violations() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let dict_path = dict_file.path().to_str().unwrap();
+
+    let output = run_parser(&["check", dict_path, binary_file.path().to_str().unwrap()]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Records: 2\nUnmapped offsets: 0\n"));
+
+    // Offset 9 is not the start of a dictionary entry
+    let mut binary_data = std::fs::read(binary_file.path()).unwrap();
+    binary_data.extend_from_slice(&3000u32.to_le_bytes());
+    binary_data.extend_from_slice(&9u32.to_le_bytes());
+    let malformed_file = NamedTempFile::new().unwrap();
+    std::fs::write(malformed_file.path(), binary_data).unwrap();
+
+    let output = run_parser(&["check", dict_path, malformed_file.path().to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(4));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Unmapped offsets: 1\n"));
+    assert!(stdout.contains("Capture has 1 format violations"));
+}
+
+#[test]
+fn test_decodes_from_urls() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let base_url = serve_files(vec![
+        ("/dict.log", std::fs::read(dict_file.path()).unwrap()),
+        ("/capture.bin", std::fs::read(binary_file.path()).unwrap()),
+    ]);
+
+    let dict_url = format!("{}/dict.log", base_url);
+    let binary_url = format!("{}/capture.bin", base_url);
+    let output = run_parser(&[&dict_url, &binary_url, "5", "--quiet"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.lines().next().unwrap().ends_with("[TEST_MODULE]\tTrigger no 42 at 100"));
+
+    let missing_url = format!("{}/missing.bin", base_url);
+    let output = run_parser(&[&dict_url, &missing_url, "5", "--quiet"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("HTTP 404"), "unexpected stderr: {}", stderr);
+}
+
+#[test]
+fn test_progress_json_goes_to_stderr() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let dict_path = dict_file.path().to_str().unwrap();
+    let binary_path = binary_file.path().to_str().unwrap();
+
+    let output = run_parser(&[dict_path, binary_path, "5", "--quiet", "--progress", "json"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 2);
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let progress: Vec<serde_json::Value> = stderr.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(!progress.is_empty());
+    assert_eq!(progress.last().unwrap(), &serde_json::json!({"entries": 2, "bytes": 24}));
+
+    // No progress unless asked for
+    let output = run_parser(&[dict_path, binary_path, "5", "--quiet"]);
+    assert!(output.stderr.is_empty());
+
+    let output = run_parser(&[dict_path, binary_path, "5", "--progress", "text"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_level_range() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let dict_path = dict_file.path().to_str().unwrap();
+    let binary_path = binary_file.path().to_str().unwrap();
+    let decoded = |args: &[&str]| {
+        let output = run_parser(&[&[dict_path, binary_path, "--quiet"], args].concat());
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // TEST_MODULE logs at level 4 and SYS_INIT at level 1
+    assert_eq!(decoded(&["--min-level", "2", "--max-level", "4"]).lines().count(), 1);
+    assert!(decoded(&["--min-level", "2"]).contains("Trigger no 42 at 100"));
+    assert!(decoded(&["-l", "1"]).contains("System started"));
+    assert!(!decoded(&["-l", "1"]).contains("Trigger"));
+
+    let output = run_parser(&[dict_path, binary_path, "5", "--max-level", "4"]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = run_parser(&[dict_path, binary_path, "--min-level", "5", "--max-level", "4"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_output_file() {
+    let dict_file = write_test_dictionary();
+    let binary_file = write_test_binary();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let output_path = temp_dir.path().join("decoded.log");
+
+    let output = run_parser(&[
+        dict_file.path().to_str().unwrap(),
+        binary_file.path().to_str().unwrap(),
+        "5",
+        "-o",
+        output_path.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    // Status lines stay on stdout, the logs go to the file
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Trigger no 42 at 100"));
+
+    let contents = std::fs::read_to_string(&output_path).unwrap();
+    assert_eq!(contents.lines().count(), 2);
+    assert!(contents.contains("Trigger no 42 at 100"));
+    assert!(!contents.contains("\x1b["));
+}
@@ -0,0 +1,31 @@
+#![cfg(target_arch = "wasm32")]
+
+use fw_log_decoder::parse_log_sessions;
+use wasm_bindgen_test::*;
+
+#[derive(serde::Deserialize)]
+struct Session {
+    id: usize,
+    content: String,
+    timestamp: Option<String>,
+}
+
+#[wasm_bindgen_test]
+fn test_parse_log_sessions_splits_boot_cycles() {
+    let log_text = "0ms\t[Info]\t[BOOT]\tSystem Reset Cause: POR\n\
+                    100ms\t[Info]\t[SYS_PROTOCOL_DATE_TIME]\tDate time set rcvd: 1756474625\n\
+                    200ms\t[Info]\t[APP]\tRunning\n\
+                    0ms\t[Info]\t[BOOT]\tBooting again\n\
+                    50ms\t[Info]\t[APP]\tRunning again";
+
+    let value = parse_log_sessions(log_text).unwrap();
+    let sessions: Vec<Session> = serde_wasm_bindgen::from_value(value).unwrap();
+
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].id, 0);
+    assert_eq!(sessions[0].timestamp.as_deref(), Some("Epoch: 1756474625"));
+    assert_eq!(sessions[0].content.lines().count(), 3);
+    assert_eq!(sessions[1].id, 1);
+    assert!(sessions[1].timestamp.is_none());
+    assert!(sessions[1].content.starts_with("0ms"));
+}